@@ -0,0 +1,51 @@
+/*
+ * C interface to audio-time's conversion math (built with the `ffi` feature).
+ *
+ * Every function returns an AudioTimeStatus, and only writes its result
+ * through `out` on success. Conversions into frames (and bytes or samples)
+ * truncate any partial frame, and conversions into nanoseconds truncate any
+ * partial nanosecond.
+ */
+
+#ifndef AUDIO_TIME_H
+#define AUDIO_TIME_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct AudioTimeSystem {
+    uint32_t sample_rate;
+    uint8_t channels;
+    uint8_t byte_depth;
+} AudioTimeSystem;
+
+typedef enum AudioTimeStatus {
+    /* The conversion succeeded, and its result was written to `out`. */
+    AUDIO_TIME_OK = 0,
+    /* `out` is null. */
+    AUDIO_TIME_NULL_POINTER = 1,
+    /* The system's sample rate, number of channels or byte depth is zero. */
+    AUDIO_TIME_INVALID_SYSTEM = 2,
+    /* The result does not fit in a uint64_t. */
+    AUDIO_TIME_OVERFLOW = 3,
+    /* The number of samples or bytes is not a whole number of frames. */
+    AUDIO_TIME_MISALIGNED = 4,
+} AudioTimeStatus;
+
+AudioTimeStatus audio_time_frames_to_nanos(AudioTimeSystem sys, uint64_t frames, uint64_t *out);
+AudioTimeStatus audio_time_nanos_to_frames(AudioTimeSystem sys, uint64_t nanos, uint64_t *out);
+AudioTimeStatus audio_time_frames_to_bytes(AudioTimeSystem sys, uint64_t frames, uint64_t *out);
+AudioTimeStatus audio_time_bytes_to_frames(AudioTimeSystem sys, uint64_t bytes, uint64_t *out);
+AudioTimeStatus audio_time_frames_to_samples(AudioTimeSystem sys, uint64_t frames, uint64_t *out);
+AudioTimeStatus audio_time_samples_to_frames(AudioTimeSystem sys, uint64_t samples, uint64_t *out);
+AudioTimeStatus audio_time_bytes_to_nanos(AudioTimeSystem sys, uint64_t bytes, uint64_t *out);
+AudioTimeStatus audio_time_nanos_to_bytes(AudioTimeSystem sys, uint64_t nanos, uint64_t *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AUDIO_TIME_H */
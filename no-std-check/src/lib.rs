@@ -0,0 +1,42 @@
+//! Compile test for using `audio-time` without its `std` feature:
+//!
+//! ```sh
+//! cargo build --manifest-path no-std-check/Cargo.toml
+//! ```
+//!
+//! Building this crate builds `audio-time` as a `no_std` library.
+
+#![no_std]
+
+use core::time::Duration;
+
+use audio_time::{Bytes, ChannelLayout, Frames, OverflowError, SampleRate, Samples, AUDIO_CD};
+
+pub fn frames_of(dur: Duration) -> Frames<AUDIO_CD> {
+    Frames::from_duration(dur)
+}
+
+pub fn bytes_of(dur: Duration) -> Bytes<AUDIO_CD> {
+    Frames::<AUDIO_CD>::from_duration(dur).into_bytes()
+}
+
+pub fn duration_of(bytes: Bytes<AUDIO_CD>) -> Result<Duration, OverflowError> {
+    bytes.try_into()
+}
+
+pub fn samples_of(buf: &[u8]) -> Option<Samples<AUDIO_CD>> {
+    let frames = Frames::<AUDIO_CD>::of_byte_slice(buf).ok()?;
+    Some(frames.into_samples())
+}
+
+pub fn nanos_of(frames: Frames<AUDIO_CD>) -> Result<u64, OverflowError> {
+    frames.to_nanos_u64()
+}
+
+pub fn channels_of(layout: ChannelLayout) -> u8 {
+    layout.channels().get()
+}
+
+pub fn hz(rate: SampleRate) -> u32 {
+    rate.get().get()
+}
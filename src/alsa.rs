@@ -0,0 +1,310 @@
+//! Conversions between [`DynSystem`]s and ALSA PCM formats
+//! (`snd_pcm_format_t`).
+//!
+//! Formats are identified by the numeric values of the `SND_PCM_FORMAT_*`
+//! constants, so no ALSA bindings are needed. Note that ALSA's `S24_LE` is a
+//! 24-bit sample in a 32-bit container, while packed 24-bit samples are
+//! `S24_3LE`.
+
+use crate::{
+    stream_format::{format_lookups, s24_32},
+    DynSampleType, DynSystem, SampleType, StreamParamsError, UnsupportedSampleTypeError, I24, U24,
+};
+
+pub const SND_PCM_FORMAT_S8: i32 = 0;
+pub const SND_PCM_FORMAT_U8: i32 = 1;
+pub const SND_PCM_FORMAT_S16_LE: i32 = 2;
+pub const SND_PCM_FORMAT_S16_BE: i32 = 3;
+pub const SND_PCM_FORMAT_U16_LE: i32 = 4;
+pub const SND_PCM_FORMAT_U16_BE: i32 = 5;
+/// 24-bit samples in the low bits of 32-bit containers.
+pub const SND_PCM_FORMAT_S24_LE: i32 = 6;
+pub const SND_PCM_FORMAT_S24_BE: i32 = 7;
+pub const SND_PCM_FORMAT_U24_LE: i32 = 8;
+pub const SND_PCM_FORMAT_U24_BE: i32 = 9;
+pub const SND_PCM_FORMAT_S32_LE: i32 = 10;
+pub const SND_PCM_FORMAT_S32_BE: i32 = 11;
+pub const SND_PCM_FORMAT_U32_LE: i32 = 12;
+pub const SND_PCM_FORMAT_U32_BE: i32 = 13;
+pub const SND_PCM_FORMAT_FLOAT_LE: i32 = 14;
+pub const SND_PCM_FORMAT_FLOAT_BE: i32 = 15;
+pub const SND_PCM_FORMAT_FLOAT64_LE: i32 = 16;
+pub const SND_PCM_FORMAT_FLOAT64_BE: i32 = 17;
+/// Packed 24-bit samples.
+pub const SND_PCM_FORMAT_S24_3LE: i32 = 32;
+pub const SND_PCM_FORMAT_S24_3BE: i32 = 33;
+pub const SND_PCM_FORMAT_U24_3LE: i32 = 34;
+pub const SND_PCM_FORMAT_U24_3BE: i32 = 35;
+
+/// The supported formats, along with their sample types.
+const FORMATS: &[(i32, SampleType)] = &[
+    (SND_PCM_FORMAT_S8, SampleType::new::<i8>()),
+    (SND_PCM_FORMAT_U8, SampleType::new::<u8>()),
+    (SND_PCM_FORMAT_S16_LE, SampleType::new_le::<i16>()),
+    (SND_PCM_FORMAT_S16_BE, SampleType::new_be::<i16>()),
+    (SND_PCM_FORMAT_U16_LE, SampleType::new_le::<u16>()),
+    (SND_PCM_FORMAT_U16_BE, SampleType::new_be::<u16>()),
+    (SND_PCM_FORMAT_S24_LE, s24_32(SampleType::new_le::<i32>())),
+    (SND_PCM_FORMAT_S24_BE, s24_32(SampleType::new_be::<i32>())),
+    (SND_PCM_FORMAT_U24_LE, s24_32(SampleType::new_le::<u32>())),
+    (SND_PCM_FORMAT_U24_BE, s24_32(SampleType::new_be::<u32>())),
+    (SND_PCM_FORMAT_S32_LE, SampleType::new_le::<i32>()),
+    (SND_PCM_FORMAT_S32_BE, SampleType::new_be::<i32>()),
+    (SND_PCM_FORMAT_U32_LE, SampleType::new_le::<u32>()),
+    (SND_PCM_FORMAT_U32_BE, SampleType::new_be::<u32>()),
+    (SND_PCM_FORMAT_FLOAT_LE, SampleType::new_le::<f32>()),
+    (SND_PCM_FORMAT_FLOAT_BE, SampleType::new_be::<f32>()),
+    (SND_PCM_FORMAT_FLOAT64_LE, SampleType::new_le::<f64>()),
+    (SND_PCM_FORMAT_FLOAT64_BE, SampleType::new_be::<f64>()),
+    (SND_PCM_FORMAT_S24_3LE, SampleType::new_le::<I24>()),
+    (SND_PCM_FORMAT_S24_3BE, SampleType::new_be::<I24>()),
+    (SND_PCM_FORMAT_U24_3LE, SampleType::new_le::<U24>()),
+    (SND_PCM_FORMAT_U24_3BE, SampleType::new_be::<U24>()),
+];
+
+format_lookups!(i32);
+
+/// Error returned for `snd_pcm_format_t` values that aren't interleaved PCM
+/// formats supported by this module.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Unsupported PCM format {0}")]
+pub struct UnsupportedFormatError(pub i32);
+
+/// Error returned when [`HwParams`] can't be described by a [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HwParamsError {
+    #[error(transparent)]
+    UnsupportedFormat(#[from] UnsupportedFormatError),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+}
+
+/// The sample format of `format`.
+///
+/// ```
+/// # use audio_time::{alsa, Endianness};
+/// #
+/// let sample_type = alsa::to_dyn_sample_type(alsa::SND_PCM_FORMAT_S24_LE).unwrap();
+/// assert_eq!(4, sample_type.byte_depth().get());
+/// assert_eq!(24, sample_type.bit_depth().get());
+/// assert_eq!(Endianness::Little, sample_type.endianness());
+/// ```
+pub const fn to_dyn_sample_type(format: i32) -> Result<DynSampleType, UnsupportedFormatError> {
+    match sample_type_of(format) {
+        Some(sample_type) => Ok(DynSampleType::from_sample_type(sample_type).unwrap()),
+        None => Err(UnsupportedFormatError(format)),
+    }
+}
+
+/// The format of samples in `sample_type`, or `None` if ALSA has no such
+/// format.
+///
+/// Native-endian sample types get the format of the target's byte order.
+pub const fn from_dyn_sample_type(sample_type: &DynSampleType) -> Option<i32> {
+    match sample_type.to_sample_type() {
+        Some(sample_type) => format_of(&sample_type),
+        None => None,
+    }
+}
+
+/// The equivalent of `snd_pcm_format_physical_width`: the number of bits each
+/// sample of `format` occupies, or `None` for unsupported formats.
+#[inline]
+pub const fn physical_width(format: i32) -> Option<u32> {
+    match format {
+        SND_PCM_FORMAT_S8 | SND_PCM_FORMAT_U8 => Some(8),
+        SND_PCM_FORMAT_S16_LE..=SND_PCM_FORMAT_U16_BE => Some(16),
+        SND_PCM_FORMAT_S24_LE..=SND_PCM_FORMAT_FLOAT_BE => Some(32),
+        SND_PCM_FORMAT_FLOAT64_LE | SND_PCM_FORMAT_FLOAT64_BE => Some(64),
+        SND_PCM_FORMAT_S24_3LE..=SND_PCM_FORMAT_U24_3BE => Some(24),
+        _ => None,
+    }
+}
+
+/// The equivalent of `snd_pcm_frames_to_bytes`: the size of a period of
+/// `period_frames` interleaved frames, or `None` for unsupported formats or
+/// on overflow.
+///
+/// ```
+/// # use audio_time::alsa;
+/// #
+/// assert_eq!(
+///     Some(6_144),
+///     alsa::bytes_per_period(alsa::SND_PCM_FORMAT_S24_3LE, 2, 1_024)
+/// );
+/// ```
+#[inline]
+pub const fn bytes_per_period(format: i32, channels: u32, period_frames: u64) -> Option<u64> {
+    let Some(width) = physical_width(format) else {
+        return None;
+    };
+    let frame_size = width as u64 / 8 * channels as u64;
+    period_frames.checked_mul(frame_size)
+}
+
+/// The format, rate and number of channels of an interleaved PCM, as
+/// negotiated through `snd_pcm_hw_params_t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HwParams {
+    /// One of the `SND_PCM_FORMAT_*` constants.
+    pub format: i32,
+    pub rate: u32,
+    pub channels: u32,
+}
+
+impl HwParams {
+    /// The system of a PCM with these parameters.
+    ///
+    /// The layout is derived from the number of channels alone (see
+    /// [`ChannelLayout::from_channels`](crate::ChannelLayout::from_channels)).
+    pub const fn to_system(&self) -> Result<DynSystem, HwParamsError> {
+        let Some(sample_type) = sample_type_of(self.format) else {
+            return Err(HwParamsError::UnsupportedFormat(UnsupportedFormatError(
+                self.format,
+            )));
+        };
+        match DynSystem::from_stream_params(self.rate, self.channels, sample_type) {
+            Ok(system) => Ok(system),
+            Err(err) => Err(HwParamsError::Params(err)),
+        }
+    }
+
+    /// The parameters of a PCM in `system`.
+    ///
+    /// Native-endian sample types get the format of the target's byte order.
+    pub const fn from_system(system: &DynSystem) -> Result<Self, UnsupportedSampleTypeError> {
+        match format_of(&system.sample_type) {
+            Some(format) => Ok(Self {
+                format,
+                rate: system.sample_rate.get().get(),
+                channels: system.channel_layout.channels().get() as u32,
+            }),
+            None => Err(UnsupportedSampleTypeError(system.sample_type)),
+        }
+    }
+
+    /// The size of a period of `period_frames` frames with these parameters
+    /// (see [`bytes_per_period`]).
+    #[inline]
+    pub const fn bytes_per_period(&self, period_frames: u64) -> Option<u64> {
+        bytes_per_period(self.format, self.channels, period_frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, DynFrames, Endianness, SampleKind};
+
+    const fn params(format: i32, rate: u32, channels: u32) -> HwParams {
+        HwParams {
+            format,
+            rate,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_s24_3le_period() {
+        let params = params(SND_PCM_FORMAT_S24_3LE, 48_000, 2);
+        let system = params.to_system().unwrap();
+        assert_eq!(SampleType::new_le::<I24>(), system.sample_type);
+        assert_eq!(6, system.frame_size());
+
+        for period in [1, 256, 1_024, 4_800] {
+            assert_eq!(
+                Some(DynFrames::new(period, system).into_bytes().get() as u64),
+                params.bytes_per_period(period as u64),
+                "{period}"
+            );
+        }
+        assert_eq!(Some(6_144), params.bytes_per_period(1_024));
+
+        // not to be confused with 24-in-32
+        let params = HwParams {
+            format: SND_PCM_FORMAT_S24_LE,
+            ..params
+        };
+        assert_eq!(8, params.to_system().unwrap().frame_size());
+        assert_eq!(Some(8_192), params.bytes_per_period(1_024));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &(format, _) in FORMATS {
+            let sample_type = to_dyn_sample_type(format).unwrap();
+            assert_eq!(Some(format), from_dyn_sample_type(&sample_type), "{format}");
+
+            for (rate, channels) in [(8_000, 1), (44_100, 2), (192_000, 8)] {
+                let params = params(format, rate, channels);
+                let system = params.to_system().unwrap();
+                assert_eq!(Ok(params), HwParams::from_system(&system), "{format}");
+
+                // catches mismatched byte depths in the mapping
+                assert_eq!(
+                    Some(DynFrames::new(1_000, system).into_bytes().get() as u64),
+                    params.bytes_per_period(1_000),
+                    "{format}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_endianness() {
+        let sample_type = to_dyn_sample_type(SND_PCM_FORMAT_FLOAT_BE).unwrap();
+        assert_eq!(SampleKind::Float, sample_type.kind());
+        assert_eq!(Endianness::Big, sample_type.endianness());
+
+        let native = sample_type.with_endianness(Endianness::Native);
+        let expected = if cfg!(target_endian = "big") {
+            SND_PCM_FORMAT_FLOAT_BE
+        } else {
+            SND_PCM_FORMAT_FLOAT_LE
+        };
+        assert_eq!(Some(expected), from_dyn_sample_type(&native));
+
+        let system = DynSystem::from_system(system!(48_000, Stereo, i16));
+        let expected = if cfg!(target_endian = "big") {
+            SND_PCM_FORMAT_S16_BE
+        } else {
+            SND_PCM_FORMAT_S16_LE
+        };
+        assert_eq!(expected, HwParams::from_system(&system).unwrap().format);
+    }
+
+    #[test]
+    fn test_unsupported() {
+        // SND_PCM_FORMAT_MU_LAW
+        assert_eq!(Err(UnsupportedFormatError(20)), to_dyn_sample_type(20));
+        assert_eq!(None, bytes_per_period(20, 1, 1_024));
+        // SND_PCM_FORMAT_UNKNOWN
+        assert_eq!(
+            Err(HwParamsError::UnsupportedFormat(UnsupportedFormatError(-1))),
+            params(-1, 48_000, 2).to_system()
+        );
+
+        assert_eq!(
+            Err(HwParamsError::Params(StreamParamsError::ZeroRate)),
+            params(SND_PCM_FORMAT_S16_LE, 0, 2).to_system()
+        );
+        for channels in [0, 256] {
+            assert_eq!(
+                Err(HwParamsError::Params(
+                    StreamParamsError::UnsupportedChannels(channels)
+                )),
+                params(SND_PCM_FORMAT_S16_LE, 48_000, channels).to_system()
+            );
+        }
+
+        // 20 bits in 3 bytes (SND_PCM_FORMAT_S20_3LE)
+        let s20_3 = DynSampleType::new(SampleKind::SignedInt, 3, 20).unwrap();
+        assert_eq!(None, from_dyn_sample_type(&s20_3));
+
+        let system = DynSystem::from_system(system!(48_000, Stereo, i64));
+        assert_eq!(
+            Err(UnsupportedSampleTypeError(system.sample_type)),
+            HwParams::from_system(&system)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! [`Arbitrary`] implementations, generating only values that uphold each
+//! type's invariants (e.g. [`Bytes`] are always a whole number of frames).
+
+use core::num::NonZeroU8;
+
+use ::arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+
+use crate::{
+    dyn_system::SAMPLE_FORMATS, AmbisonicsOrder, Bytes, ChannelLayout, Channels, DynSystem, Frames,
+    SampleRate, Samples, System,
+};
+
+impl<'a, const SYS: System> Arbitrary<'a> for Frames<SYS> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(usize::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+/// Draws a number of frames that can be multiplied by `per_frame` without
+/// overflowing.
+fn arbitrary_frames<const SYS: System>(
+    u: &mut Unstructured,
+    per_frame: usize,
+) -> Result<Frames<SYS>> {
+    Ok(Frames::new(u.int_in_range(0..=usize::MAX / per_frame)?))
+}
+
+impl<'a, const SYS: System> Arbitrary<'a> for Samples<SYS> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let channels = SYS.channel_layout.channels().get() as usize;
+        Ok(arbitrary_frames(u, channels)?.into_samples())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a, const SYS: System> Arbitrary<'a> for Bytes<SYS> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let frame_size = SYS.frame_size().get() as usize;
+        Ok(arbitrary_frames(u, frame_size)?.into_bytes())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for SampleRate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u32::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ChannelLayout {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=6)? {
+            0 => Self::Mono,
+            1 => Self::Stereo,
+            2 => Self::Quad,
+            3 => Self::Surround51,
+            4 => Self::Surround71,
+            5 => Self::Ambisonics(AmbisonicsOrder::new(u.int_in_range(0..=14)?).unwrap()),
+            _ => {
+                let channels = NonZeroU8::new(u.int_in_range(3..=u8::MAX)?).unwrap();
+                Self::Other(Channels::new(channels).unwrap())
+            }
+        })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        // the variant, and possibly the order or number of channels
+        (1, Some(2))
+    }
+}
+
+/// The sample type is one of the formats supported by
+/// [`DynSystem::from_str`](core::str::FromStr::from_str).
+impl<'a> Arbitrary<'a> for DynSystem {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            sample_rate: u.arbitrary()?,
+            channel_layout: u.arbitrary()?,
+            sample_type: u.choose(SAMPLE_FORMATS)?.1,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and_all(&[
+            SampleRate::size_hint(depth),
+            ChannelLayout::size_hint(depth),
+            (1, Some(1)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    /// Deterministic, but not structured, fuzzer input.
+    fn data(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let data = data(32_000);
+        let mut u = Unstructured::new(&data);
+
+        for _ in 0..4_000 {
+            let bytes = Bytes::<AUDIO_CD>::arbitrary(&mut u)?;
+            assert_eq!(0, bytes.get() % 4);
+
+            let frames = Frames::from(bytes);
+            assert_eq!(bytes, frames.into_bytes());
+            assert_eq!(bytes.get() / 2, Samples::from(bytes).get());
+            let _ = Duration::try_from(bytes);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_systems() -> Result<()> {
+        let data = data(8_000);
+        let mut u = Unstructured::new(&data);
+
+        for _ in 0..1_000 {
+            let samples = Samples::<AUDIO_CD>::arbitrary(&mut u)?;
+            assert_eq!(0, samples.get() % 2);
+
+            let system = DynSystem::arbitrary(&mut u)?;
+            #[cfg(feature = "std")]
+            assert_eq!(
+                Ok(system),
+                system.to_format_string().unwrap().parse::<DynSystem>()
+            );
+        }
+
+        assert_eq!((6, Some(7)), DynSystem::size_hint(0));
+
+        Ok(())
+    }
+}
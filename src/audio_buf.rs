@@ -0,0 +1,167 @@
+//! Measuring [`audio_core`] buffers (e.g. those of the `audio` crate) in
+//! frames and bytes.
+
+use core::time::Duration;
+
+use audio_core::{Buf, Channel, ExactSizeBuf};
+
+use crate::{DynBytes, DynFrames, DynSystem, Rounding};
+
+/// Error returned when a buffer has a different number of channels than the
+/// system it is measured in.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Buffer has {actual} channels, but the system has {expected}")]
+pub struct ChannelMismatchError {
+    /// The number of channels in the system.
+    pub expected: usize,
+    /// The number of channels in the buffer.
+    pub actual: usize,
+}
+
+/// Error returned from [`validate_buf_duration`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufDurationError {
+    #[error(transparent)]
+    ChannelMismatch(#[from] ChannelMismatchError),
+    #[error("Buffer holds {frames} frames, but at least {required} are needed")]
+    TooShort {
+        frames: usize,
+        /// The number of frames needed, saturated to `usize::MAX`.
+        required: usize,
+    },
+}
+
+#[inline]
+fn check_channels<B: Buf>(buf: &B, system: DynSystem) -> Result<(), ChannelMismatchError> {
+    let expected = system.channel_layout.channels().get() as usize;
+    match buf.channels() {
+        actual if actual == expected => Ok(()),
+        actual => Err(ChannelMismatchError { expected, actual }),
+    }
+}
+
+/// The number of whole frames in `buf`, i.e. the length of its shortest
+/// channel, measured in `system`.
+///
+/// Buffers don't have to know their exact size (and their channels may have
+/// different lengths), so this looks at every channel rather than trusting
+/// [`Buf::frames_hint`].
+pub fn frames_of_buf<B: Buf>(
+    buf: &B,
+    system: DynSystem,
+) -> Result<DynFrames, ChannelMismatchError> {
+    check_channels(buf, system)?;
+    let frames = buf.iter_channels().map(|channel| channel.len()).min();
+
+    Ok(DynFrames::new(frames.unwrap_or(0), system))
+}
+
+/// The number of bytes that the frames in `buf` take up when encoded in
+/// `system` (whatever `buf`'s own sample type is).
+///
+/// # Panics
+///
+/// Panics if the number of bytes overflows.
+#[track_caller]
+pub fn bytes_of_buf<B: ExactSizeBuf>(
+    buf: &B,
+    system: DynSystem,
+) -> Result<DynBytes, ChannelMismatchError> {
+    check_channels(buf, system)?;
+    Ok(DynFrames::new(buf.frames(), system).into_bytes())
+}
+
+/// Checks that `buf` holds at least `min` worth of whole frames in `system`
+/// (rounded up to a whole frame), returning the number of frames it holds.
+pub fn validate_buf_duration<B: Buf>(
+    buf: &B,
+    system: DynSystem,
+    min: Duration,
+) -> Result<DynFrames, BufDurationError> {
+    let frames = frames_of_buf(buf, system)?;
+    let required = system.sample_rate.periods_in_rounded(min, Rounding::Up);
+
+    if frames.get() as u128 >= required {
+        Ok(frames)
+    } else {
+        Err(BufDurationError::TooShort {
+            frames: frames.get(),
+            required: required.try_into().unwrap_or(usize::MAX),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use audio::buf::Dynamic;
+
+    use super::*;
+    use crate::{presets::DAT_48K, system, System};
+
+    const SYS: System = system!(48_000, Stereo, f32);
+
+    #[test]
+    fn test_measure() {
+        let system = DynSystem::from_system(SYS);
+        let buf = Dynamic::<f32>::with_topology(2, 480);
+
+        let frames = frames_of_buf(&buf, system).unwrap();
+        assert_eq!(DynFrames::new(480, system), frames);
+        assert_eq!(Duration::from_millis(10), frames.into_duration());
+        assert_eq!(3_840, bytes_of_buf(&buf, system).unwrap().get());
+
+        // measured in the system's encoding, not the buffer's
+        let dat = DynSystem::from_system(DAT_48K);
+        assert_eq!(1_920, bytes_of_buf(&buf, dat).unwrap().get());
+
+        // ragged channels only have as many whole frames as the shortest one
+        let ragged = audio::wrap::dynamic(vec![vec![0_f32; 480], vec![0.; 240]]);
+        assert_eq!(Some(480), ragged.frames_hint());
+        assert_eq!(240, frames_of_buf(&ragged, system).unwrap().get());
+    }
+
+    #[test]
+    fn test_channel_mismatch() {
+        let system = DynSystem::from_system(SYS);
+        let err = ChannelMismatchError {
+            expected: 2,
+            actual: 1,
+        };
+
+        let mono = Dynamic::<f32>::with_topology(1, 480);
+        assert_eq!(Err(err), frames_of_buf(&mono, system));
+        assert_eq!(Err(err), bytes_of_buf(&mono, system));
+        assert_eq!(
+            Err(BufDurationError::ChannelMismatch(err)),
+            validate_buf_duration(&mono, system, Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_validate_duration() {
+        let system = DynSystem::from_system(SYS);
+        let buf = Dynamic::<f32>::with_topology(2, 480);
+
+        for min in [Duration::ZERO, Duration::from_millis(10)] {
+            assert_eq!(
+                Ok(480),
+                validate_buf_duration(&buf, system, min).map(|f| f.get())
+            );
+        }
+        // a fraction of a frame more than the buffer holds
+        assert_eq!(
+            Err(BufDurationError::TooShort {
+                frames: 480,
+                required: 481
+            }),
+            validate_buf_duration(&buf, system, Duration::from_nanos(10_000_001))
+        );
+        assert_eq!(
+            Err(BufDurationError::TooShort {
+                frames: 480,
+                required: usize::MAX
+            }),
+            validate_buf_duration(&buf, system, Duration::MAX)
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use core::time::Duration;
+
+use crate::{
+    convert::{bits_to_samples, samples_to_bits},
+    impl_fmt, Bytes, Samples, System,
+};
+
+mod sealed {
+    use super::frame_bits;
+    use crate::System;
+
+    /// An audio time span, measured in the number of meaningful bits required
+    /// for its representation.
+    ///
+    /// Unlike [`Bytes`](crate::Bytes), which count whole sample containers,
+    /// `Bits` count [`SYS.bits_per_sample()`](System::bits_per_sample) for
+    /// each sample, so they can describe tightly packed streams (e.g. 20-bit
+    /// audio).
+    ///
+    /// The `usize` contained in this struct is invariantly held to be divisible
+    /// (without remainder) by the number of bits in a single frame.
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[repr(transparent)]
+    pub struct Bits<const SYS: System>(usize);
+
+    impl<const SYS: System> Bits<SYS> {
+        /// Create a `Bits` if the given value is divisible by the number of
+        /// bits in a single frame
+        /// ([`SYS.bits_per_sample()`](System::bits_per_sample) times
+        /// [`SYS.channel_layout.channels()`](crate::ChannelLayout::channels)).
+        #[inline]
+        pub const fn new(n: usize) -> Option<Self> {
+            if n % frame_bits::<SYS>() == 0 {
+                Some(Self(n))
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        pub const fn get(&self) -> usize {
+            self.0
+        }
+    }
+}
+
+pub use self::sealed::Bits;
+
+/// The number of bits in a single frame.
+#[inline]
+const fn frame_bits<const SYS: System>() -> usize {
+    SYS.bits_per_sample().get() as usize * SYS.channel_layout.channels().get() as usize
+}
+
+/// Deserializes the same newtype as a derived implementation would, rejecting
+/// counts that [`Bits::new`] would reject.
+#[cfg(feature = "serde")]
+impl<'de, const SYS: System> serde::Deserialize<'de> for Bits<SYS> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Bits")]
+        struct Raw(usize);
+
+        let Raw(n) = Raw::deserialize(deserializer)?;
+        Self::new(n).ok_or_else(|| crate::span_serde::misaligned(n, frame_bits::<SYS>()))
+    }
+}
+
+impl_fmt!(Bits);
+
+/// Error returned when a number of [`Bits`] does not fill a whole number of
+/// bytes.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("{remainder} trailing bits do not fill a whole byte")]
+pub struct UnalignedBitsError {
+    remainder: u8,
+}
+
+impl UnalignedBitsError {
+    /// The number of bits left over after dividing into whole bytes.
+    #[inline]
+    pub const fn remainder(&self) -> u8 {
+        self.remainder
+    }
+}
+
+impl<const SYS: System> Bits<SYS> {
+    /// Equivalent to `Duration::try_from(bits).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn into_duration(self) -> Duration {
+        self.into_samples().into_duration()
+    }
+
+    /// Equivalent to `Bits::try_from(duration).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn from_duration(dur: Duration) -> Self {
+        Self::from_samples(Samples::from_duration(dur))
+    }
+
+    /// Equivalent to `Samples::from(bits)`.
+    #[inline]
+    #[track_caller]
+    pub const fn into_samples(self) -> Samples<SYS> {
+        bits_to_samples(self)
+    }
+
+    /// Equivalent to `Bits::try_from(samples).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn from_samples(samples: Samples<SYS>) -> Self {
+        match samples_to_bits(samples) {
+            Ok(bits) => bits,
+            Err(_) => {
+                panic!("Overflowed trying to convert samples to bits")
+            }
+        }
+    }
+
+    /// Equivalent to `Bytes::try_from(bits).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn into_bytes(self) -> Bytes<SYS> {
+        self.into_samples().into_bytes()
+    }
+
+    /// Equivalent to `Bits::try_from(bytes).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn from_bytes(bytes: Bytes<SYS>) -> Self {
+        Self::from_samples(bytes.into_samples())
+    }
+
+    /// The number of bytes needed to hold these bits when tightly packed.
+    ///
+    /// Fails if the bits do not fill a whole number of bytes, in which case
+    /// the error carries the number of leftover bits. See also
+    /// [`to_bytes_ceil`](Bits::to_bytes_ceil).
+    #[inline]
+    pub const fn to_bytes_exact(self) -> Result<usize, UnalignedBitsError> {
+        let remainder = (self.get() % 8) as u8;
+
+        if remainder == 0 {
+            Ok(self.get() / 8)
+        } else {
+            Err(UnalignedBitsError { remainder })
+        }
+    }
+
+    /// The number of bytes needed to hold these bits when tightly packed,
+    /// rounding up to a whole byte. Useful for allocating buffers.
+    #[inline]
+    pub const fn to_bytes_ceil(self) -> usize {
+        self.get() / 8 + (self.get() % 8 != 0) as usize
+    }
+
+    /// The bits held in `bytes` tightly packed bytes, the inverse of
+    /// [`to_bytes_exact`](Bits::to_bytes_exact).
+    ///
+    /// Returns `None` if the bytes don't hold a whole number of frames (e.g. a
+    /// single byte of 3-channel DSD), or if the number of bits overflows.
+    #[inline]
+    pub const fn from_packed_bytes(bytes: usize) -> Option<Self> {
+        match bytes.checked_mul(8) {
+            Some(n) => Self::new(n),
+            None => None,
+        }
+    }
+}
+
+impl<const SYS: System> From<Bits<SYS>> for usize {
+    #[inline]
+    fn from(value: Bits<SYS>) -> Self {
+        value.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU8;
+
+    use super::*;
+    use crate::{system, Frames, OverflowError, SampleType};
+
+    #[test]
+    fn test_byte_aligned() -> Result<(), OverflowError> {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        let bits = Bits::<SYS>::try_from(Frames::<SYS>::new(1_000).into_bytes())?;
+        assert_eq!(32_000, bits.get());
+        assert_eq!(Ok(4_000), bits.to_bytes_exact());
+        assert_eq!(4_000, bits.to_bytes_ceil());
+        assert_eq!(4_000, Bytes::try_from(bits)?.get());
+        assert_eq!(2_000, Samples::from(bits).get());
+        assert_eq!(Duration::from_millis(1_000 / 48), Duration::try_from(bits)?);
+
+        // not a whole number of frames
+        assert!(Bits::<SYS>::new(16).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_twenty_bit() -> Result<(), OverflowError> {
+        const SYS: System = System {
+            sample_type: match SampleType::new::<i32>().with_bit_depth(NonZeroU8::new(20).unwrap())
+            {
+                Some(sample_type) => sample_type,
+                None => panic!(),
+            },
+            ..system!(48_000, Mono, i32)
+        };
+
+        let bits = Bits::<SYS>::new(60).unwrap();
+        assert_eq!(3, Samples::from(bits).get());
+
+        // three 20-bit samples don't fill a whole number of bytes...
+        assert_eq!(4, bits.to_bytes_exact().unwrap_err().remainder());
+        assert_eq!(8, bits.to_bytes_ceil());
+        // ...but still take up three whole 4-byte containers
+        assert_eq!(12, Bytes::try_from(bits)?.get());
+        assert_eq!(bits, Bits::try_from(Bytes::<SYS>::new(12).unwrap())?);
+
+        assert!(Bits::<SYS>::new(30).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dsd() {
+        const SYS: System = System {
+            channel_layout: crate::ChannelLayout::Mono,
+            ..crate::presets::DSD64_STEREO
+        };
+
+        // 8 frames of mono DSD occupy a single byte...
+        let byte = Bits::from_samples(Frames::<SYS>::new(8).into_samples());
+        assert_eq!(Ok(1), byte.to_bytes_exact());
+        assert_eq!(Some(byte), Bits::<SYS>::from_packed_bytes(1));
+
+        // ...so frames only round-trip through packed bytes on 8-frame
+        // boundaries
+        for frames in 0..=32 {
+            let bits = Bits::from_samples(Frames::<SYS>::new(frames).into_samples());
+            let round_trip = bits
+                .to_bytes_exact()
+                .ok()
+                .and_then(Bits::<SYS>::from_packed_bytes)
+                .map(|bits| Frames::from(bits.into_samples()));
+
+            assert_eq!(
+                frames % 8 == 0,
+                round_trip == Some(Frames::new(frames)),
+                "{frames}"
+            );
+        }
+        assert_eq!(
+            2,
+            Bits::from_samples(Frames::<SYS>::new(9).into_samples()).to_bytes_ceil()
+        );
+
+        assert!(Bits::<SYS>::from_packed_bytes(usize::MAX).is_none());
+        const SURROUND: System = System {
+            sample_type: SYS.sample_type,
+            ..system!(2_822_400, 3, u8)
+        };
+        assert!(Bits::<SURROUND>::from_packed_bytes(1).is_none());
+        assert!(Bits::<SURROUND>::from_packed_bytes(3).is_some());
+    }
+
+    #[test]
+    fn test_overflow() {
+        const SYS: System = system!(8_000, Mono, u8);
+
+        let max = Bytes::<SYS>::new(usize::MAX / 8).unwrap();
+        assert_eq!(usize::MAX / 8 * 8, Bits::try_from(max).unwrap().get());
+
+        let overflowing = Bytes::<SYS>::new(usize::MAX / 8 + 1).unwrap();
+        assert!(Bits::try_from(overflowing).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        let bits: Bits<SYS> = serde_json::from_str("64").unwrap();
+        assert_eq!(64, bits.get());
+        assert_eq!("64", serde_json::to_string(&bits).unwrap());
+
+        let err = serde_json::from_str::<Bits<SYS>>("48").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("48 is not a whole number of frames (not divisible by 32, remainder 16)"));
+        let bin = bincode::serialize(&48_usize).unwrap();
+        assert!(bincode::deserialize::<Bits<SYS>>(&bin).is_err());
+    }
+}
@@ -0,0 +1,401 @@
+use core::{mem::size_of, ops::Range};
+#[cfg(feature = "std")]
+use std::collections::TryReserveError;
+
+#[cfg(feature = "std")]
+use crate::{
+    convert::{frames_to_bytes, frames_to_samples},
+    OverflowError,
+};
+use crate::{Frames, SampleType, Samples, System};
+
+/// Error returned when a buffer does not contain a whole number of frames.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Buffer is not a whole number of frames ({remainder} trailing elements)")]
+pub struct MisalignedError {
+    /// The number of trailing elements (bytes or samples) that do not make up
+    /// a whole frame.
+    pub remainder: usize,
+}
+
+/// Error returned when validating a slice of samples against a [`System`].
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SampleSliceError {
+    #[error("Sample size mismatch (expected {expected} bytes, found {found})")]
+    SampleSize { expected: u8, found: usize },
+    #[error(transparent)]
+    Misaligned(#[from] MisalignedError),
+}
+
+/// Error returned when allocating a sample buffer for a [`System`] whose
+/// sample type is not `S`.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Sample type mismatch (expected {expected}, found {found})")]
+pub struct TypeMismatchError {
+    pub expected: SampleType,
+    pub found: SampleType,
+}
+
+/// Error returned when allocating a buffer fails.
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum AllocError {
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+    #[error(transparent)]
+    TryReserve(#[from] TryReserveError),
+    #[error(transparent)]
+    TypeMismatch(#[from] TypeMismatchError),
+}
+
+/// A zeroed vector of `len` elements, without aborting if allocation fails.
+#[cfg(feature = "std")]
+#[inline]
+fn try_zeroed_vec<S: audio_core::Sample>(len: usize) -> Result<Vec<S>, TryReserveError> {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(len)?;
+    vec.resize(len, S::ZERO);
+    Ok(vec)
+}
+
+#[cfg(feature = "std")]
+impl<const SYS: System> Frames<SYS> {
+    /// A zeroed byte buffer large enough to hold this many frames.
+    ///
+    /// Note that zeroed `u8` samples aren't silent (see
+    /// [`SampleType::silence_byte`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the buffer overflows, or if allocation fails. See
+    /// [`try_alloc_byte_vec`](Frames::try_alloc_byte_vec) for a fallible
+    /// version.
+    #[inline]
+    #[track_caller]
+    pub fn alloc_byte_vec(&self) -> Vec<u8> {
+        vec![0; self.into_bytes().get()]
+    }
+
+    /// Fallible version of [`alloc_byte_vec`](Frames::alloc_byte_vec).
+    #[inline]
+    pub fn try_alloc_byte_vec(&self) -> Result<Vec<u8>, AllocError> {
+        Ok(try_zeroed_vec(frames_to_bytes(*self)?.get())?)
+    }
+
+    /// Like [`alloc_byte_vec`](Frames::alloc_byte_vec), but returns a boxed
+    /// slice.
+    #[inline]
+    #[track_caller]
+    pub fn alloc_boxed_bytes(&self) -> Box<[u8]> {
+        self.alloc_byte_vec().into_boxed_slice()
+    }
+
+    /// Fallible version of [`alloc_boxed_bytes`](Frames::alloc_boxed_bytes).
+    #[inline]
+    pub fn try_alloc_boxed_bytes(&self) -> Result<Box<[u8]>, AllocError> {
+        Ok(self.try_alloc_byte_vec()?.into_boxed_slice())
+    }
+
+    /// A buffer of [zeroed](audio_core::Sample::ZERO) samples, large enough to
+    /// hold this many (interleaved) frames.
+    ///
+    /// Fails if `S` is not `SYS`'s sample type. Note that zeroed `u8` samples
+    /// aren't silent (see [`SampleType::silence_byte`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the buffer overflows, or if allocation fails. See
+    /// [`try_alloc_sample_vec`](Frames::try_alloc_sample_vec) for a fallible
+    /// version.
+    #[inline]
+    #[track_caller]
+    pub fn alloc_sample_vec<S: audio_core::Sample + 'static>(
+        &self,
+    ) -> Result<Vec<S>, TypeMismatchError> {
+        check_sample_type::<S, SYS>()?;
+        Ok(vec![S::ZERO; self.into_samples().get()])
+    }
+
+    /// Fallible version of [`alloc_sample_vec`](Frames::alloc_sample_vec).
+    #[inline]
+    pub fn try_alloc_sample_vec<S: audio_core::Sample + 'static>(
+        &self,
+    ) -> Result<Vec<S>, AllocError> {
+        check_sample_type::<S, SYS>()?;
+        Ok(try_zeroed_vec(frames_to_samples(*self)?.get())?)
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The number of frames in an interleaved byte buffer.
+    ///
+    /// Fails if the buffer's length is not a multiple of
+    /// [`SYS.frame_size()`](System::frame_size).
+    #[inline]
+    pub const fn of_byte_slice(buf: &[u8]) -> Result<Self, MisalignedError> {
+        let frame_size = SYS.frame_size().get() as usize;
+
+        match buf.len() % frame_size {
+            0 => Ok(Self::new(buf.len() / frame_size)),
+            remainder => Err(MisalignedError { remainder }),
+        }
+    }
+
+    /// The number of frames in an interleaved sample buffer.
+    ///
+    /// Fails if `S` is not the same size as `SYS`'s sample type, or if the
+    /// buffer's length is not a multiple of the number of channels.
+    #[inline]
+    pub const fn of_sample_slice<S>(buf: &[S]) -> Result<Self, SampleSliceError> {
+        let expected = SYS.sample_type.byte_depth().get();
+        if size_of::<S>() != expected as usize {
+            return Err(SampleSliceError::SampleSize {
+                expected,
+                found: size_of::<S>(),
+            });
+        }
+
+        let channels = SYS.channel_layout.channels().get() as usize;
+        match buf.len() % channels {
+            0 => Ok(Self::new(buf.len() / channels)),
+            remainder => Err(SampleSliceError::Misaligned(MisalignedError { remainder })),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline]
+const fn check_sample_type<S: 'static, const SYS: System>() -> Result<(), TypeMismatchError> {
+    if SYS.sample_type.is::<S>() {
+        Ok(())
+    } else {
+        Err(TypeMismatchError {
+            expected: SYS.sample_type,
+            found: SampleType::of_type::<S>(),
+        })
+    }
+}
+
+/// Frame-aware indexing into interleaved sample buffers.
+///
+/// All methods check that `S` is the same size as `SYS`'s sample type, so a
+/// buffer can't accidentally be indexed using the wrong system.
+pub trait SampleSliceExt<S> {
+    /// The number of frames in this buffer, see
+    /// [`Frames::of_sample_slice`].
+    fn len_frames<const SYS: System>(&self) -> Result<Frames<SYS>, SampleSliceError>;
+
+    /// The samples of the frames in `range`, or `None` if the range is out of
+    /// bounds (or reversed), or if `S` is not the same size as `SYS`'s sample
+    /// type.
+    fn slice_frames<const SYS: System>(&self, range: Range<Frames<SYS>>) -> Option<&[S]>;
+
+    /// Mutable version of [`slice_frames`](SampleSliceExt::slice_frames).
+    fn slice_frames_mut<const SYS: System>(
+        &mut self,
+        range: Range<Frames<SYS>>,
+    ) -> Option<&mut [S]>;
+
+    /// The samples in `range`, or `None` if the range is out of bounds (or
+    /// reversed), or if `S` is not the same size as `SYS`'s sample type.
+    fn slice_samples<const SYS: System>(&self, range: Range<Samples<SYS>>) -> Option<&[S]>;
+
+    /// Mutable version of [`slice_samples`](SampleSliceExt::slice_samples).
+    fn slice_samples_mut<const SYS: System>(
+        &mut self,
+        range: Range<Samples<SYS>>,
+    ) -> Option<&mut [S]>;
+}
+
+/// The range of sample indices making up the frames in `range`, if `S` is the
+/// same size as `SYS`'s sample type.
+#[inline]
+const fn sample_indices<S, const SYS: System>(range: Range<Frames<SYS>>) -> Option<Range<usize>> {
+    if size_of::<S>() != SYS.sample_type.byte_depth().get() as usize {
+        return None;
+    }
+
+    let channels = SYS.channel_layout.channels().get() as usize;
+    match (
+        range.start.get().checked_mul(channels),
+        range.end.get().checked_mul(channels),
+    ) {
+        (Some(start), Some(end)) => Some(start..end),
+        _ => None,
+    }
+}
+
+impl<S> SampleSliceExt<S> for [S] {
+    #[inline]
+    fn len_frames<const SYS: System>(&self) -> Result<Frames<SYS>, SampleSliceError> {
+        Frames::of_sample_slice(self)
+    }
+
+    #[inline]
+    fn slice_frames<const SYS: System>(&self, range: Range<Frames<SYS>>) -> Option<&[S]> {
+        self.get(sample_indices::<S, SYS>(range)?)
+    }
+
+    #[inline]
+    fn slice_frames_mut<const SYS: System>(
+        &mut self,
+        range: Range<Frames<SYS>>,
+    ) -> Option<&mut [S]> {
+        self.get_mut(sample_indices::<S, SYS>(range)?)
+    }
+
+    #[inline]
+    fn slice_samples<const SYS: System>(&self, range: Range<Samples<SYS>>) -> Option<&[S]> {
+        self.slice_frames(Frames::from(range.start)..Frames::from(range.end))
+    }
+
+    #[inline]
+    fn slice_samples_mut<const SYS: System>(
+        &mut self,
+        range: Range<Samples<SYS>>,
+    ) -> Option<&mut [S]> {
+        self.slice_frames_mut(Frames::from(range.start)..Frames::from(range.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_byte_slice() {
+        assert_eq!(
+            Ok(Frames::new(256)),
+            Frames::<AUDIO_CD>::of_byte_slice(&[0; 1_024])
+        );
+        assert_eq!(Ok(Frames::new(0)), Frames::<AUDIO_CD>::of_byte_slice(&[]));
+        assert_eq!(
+            Err(MisalignedError { remainder: 3 }),
+            Frames::<AUDIO_CD>::of_byte_slice(&[0; 1_023])
+        );
+    }
+
+    #[test]
+    fn test_sample_slice() {
+        assert_eq!(
+            Ok(Frames::new(512)),
+            Frames::<AUDIO_CD>::of_sample_slice(&[0i16; 1_024])
+        );
+        assert_eq!(
+            Err(SampleSliceError::Misaligned(MisalignedError {
+                remainder: 1
+            })),
+            Frames::<AUDIO_CD>::of_sample_slice(&[0i16; 1_023])
+        );
+        assert_eq!(
+            Err(SampleSliceError::SampleSize {
+                expected: 4,
+                found: 2
+            }),
+            Frames::<{ system!(48_000, Stereo, f32) }>::of_sample_slice(&[0i16; 1_024])
+        );
+    }
+
+    #[test]
+    fn test_slice_frames() {
+        let mut buf: Vec<i16> = (0..1_024).collect();
+        assert_eq!(Ok(Frames::<AUDIO_CD>::new(512)), buf.len_frames());
+
+        let range = Frames::<AUDIO_CD>::new(100)..Frames::new(200);
+        let frames = buf.slice_frames(range.clone()).unwrap();
+        assert_eq!(2 * 100, frames.len());
+        assert_eq!([200, 201], frames[..2]);
+        assert_eq!(
+            Some(frames),
+            buf.slice_samples(range.start.into_samples()..range.end.into_samples())
+        );
+
+        buf.slice_frames_mut(range).unwrap().fill(0);
+        assert_eq!(199, buf[199]);
+        assert_eq!(0, buf[200]);
+        assert_eq!(0, buf[399]);
+        assert_eq!(400, buf[400]);
+
+        let samples = Samples::<AUDIO_CD>::new(2).unwrap()..Samples::new(6).unwrap();
+        assert_eq!([2, 3, 4, 5], buf.slice_samples_mut(samples).unwrap());
+
+        // out of bounds, or reversed
+        assert!(buf
+            .slice_frames(Frames::<AUDIO_CD>::new(500)..Frames::new(513))
+            .is_none());
+        assert!(buf
+            .slice_frames(Frames::<AUDIO_CD>::new(2)..Frames::new(1))
+            .is_none());
+        assert!(buf
+            .slice_frames(Frames::<AUDIO_CD>::new(usize::MAX)..Frames::new(usize::MAX))
+            .is_none());
+        assert_eq!(
+            Some(&[][..]),
+            buf.slice_frames(Frames::<AUDIO_CD>::new(512)..Frames::new(512))
+        );
+    }
+
+    #[test]
+    fn test_slice_sample_size() {
+        const F32: System = system!(48_000, Stereo, f32);
+
+        let buf = [0i16; 1_024];
+        assert!(buf
+            .slice_frames(Frames::<F32>::new(0)..Frames::new(1))
+            .is_none());
+        assert!(buf.len_frames::<F32>().is_err());
+        assert!([0f32; 4]
+            .slice_frames(Frames::<F32>::new(0)..Frames::new(1))
+            .is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_alloc() -> Result<(), AllocError> {
+        let frames = Frames::<AUDIO_CD>::from_duration(std::time::Duration::from_millis(20));
+        assert_eq!(882, frames.get());
+
+        assert_eq!(3_528, frames.alloc_byte_vec().len());
+        assert_eq!(3_528, frames.try_alloc_byte_vec()?.len());
+        assert_eq!(3_528, frames.alloc_boxed_bytes().len());
+        assert_eq!(3_528, frames.try_alloc_boxed_bytes()?.len());
+        assert!(frames.alloc_byte_vec().iter().all(|&b| b == 0));
+
+        let samples = frames.alloc_sample_vec::<i16>().unwrap();
+        assert_eq!(1_764, samples.len());
+        assert_eq!(Ok(Frames::new(882)), samples.len_frames::<AUDIO_CD>());
+        assert_eq!(1_764, frames.try_alloc_sample_vec::<i16>()?.len());
+
+        assert_eq!(
+            Err(TypeMismatchError {
+                expected: SampleType::new::<i16>(),
+                found: SampleType::new::<u16>(),
+            }),
+            frames.alloc_sample_vec::<u16>()
+        );
+        assert!(matches!(
+            frames.try_alloc_sample_vec::<f32>(),
+            Err(AllocError::TypeMismatch(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_alloc_too_large() {
+        assert!(matches!(
+            Frames::<AUDIO_CD>::new(usize::MAX).try_alloc_byte_vec(),
+            Err(AllocError::Overflow(_))
+        ));
+        assert!(matches!(
+            Frames::<AUDIO_CD>::new(usize::MAX / 4).try_alloc_boxed_bytes(),
+            Err(AllocError::TryReserve(_))
+        ));
+        assert!(matches!(
+            Frames::<AUDIO_CD>::new(usize::MAX / 2).try_alloc_sample_vec::<i16>(),
+            Err(AllocError::TryReserve(_))
+        ));
+    }
+}
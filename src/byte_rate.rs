@@ -0,0 +1,118 @@
+use core::{fmt, num::NonZeroU64, time::Duration};
+
+use crate::sample_rate::duration_of_ticks;
+
+/// The throughput of an audio stream, measured in bytes per second.
+///
+/// The [`Display`](fmt::Display) implementation prints the rate in kilobytes
+/// per second (e.g. `176.4 kB/s`), or in kilobits per second when the
+/// alternate flag is used (e.g. `format!("{rate:#}")` prints `1411 kbps`).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct ByteRate(NonZeroU64);
+
+impl ByteRate {
+    #[inline]
+    pub const fn new(bytes_per_second: NonZeroU64) -> Self {
+        Self(bytes_per_second)
+    }
+
+    #[inline]
+    pub const fn bytes_per_second(&self) -> u64 {
+        self.0.get()
+    }
+
+    /// Returns `None` on overflow.
+    #[inline]
+    pub const fn bits_per_second(&self) -> Option<u64> {
+        self.bytes_per_second().checked_mul(8)
+    }
+
+    /// The time it takes to stream `bytes` bytes at this rate, truncated to
+    /// whole nanoseconds.
+    #[inline]
+    pub const fn duration_for(&self, bytes: u64) -> Duration {
+        duration_of_ticks(bytes, self.bytes_per_second())
+    }
+}
+
+impl fmt::Display for ByteRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            let bits = self.bytes_per_second() as u128 * 8;
+            if bits >= 1_000 {
+                write!(f, "{} kbps", bits / 1_000)
+            } else {
+                write!(f, "{bits} bps")
+            }
+        } else {
+            let bytes = self.bytes_per_second();
+            if bytes >= 1_000_000 {
+                write!(f, "{:.1} MB/s", bytes as f64 / 1_000_000.)
+            } else if bytes >= 1_000 {
+                write!(f, "{:.1} kB/s", bytes as f64 / 1_000.)
+            } else {
+                write!(f, "{bytes} B/s")
+            }
+        }
+    }
+}
+
+impl From<NonZeroU64> for ByteRate {
+    fn from(value: NonZeroU64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<ByteRate> for NonZeroU64 {
+    fn from(value: ByteRate) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_audio_cd() {
+        assert_eq!(176_400, AUDIO_CD.bytes_per_second());
+        assert_eq!(1_411_200, AUDIO_CD.bit_rate());
+
+        let rate = AUDIO_CD.byte_rate();
+        assert_eq!("176.4 kB/s", rate.to_string());
+        assert_eq!("1411 kbps", format!("{rate:#}"));
+        assert_eq!(Duration::from_secs(60), rate.duration_for(176_400 * 60));
+        assert_eq!(Duration::from_millis(500), rate.duration_for(88_200));
+    }
+
+    #[test]
+    fn test_wide() {
+        let rate = system!(192_000, Stereo, f64).byte_rate();
+        assert_eq!(3_072_000, rate.bytes_per_second());
+        assert_eq!("3.1 MB/s", rate.to_string());
+        assert_eq!("24576 kbps", format!("{rate:#}"));
+
+        const SURROUND: crate::System = system!(192_000, Surround71, f64);
+        assert_eq!(12_288_000, SURROUND.bytes_per_second());
+        assert_eq!(98_304_000, SURROUND.bit_rate());
+        let rate = SURROUND.byte_rate();
+        assert_eq!("12.3 MB/s", rate.to_string());
+        assert_eq!("98304 kbps", format!("{rate:#}"));
+        assert_eq!(
+            Duration::from_millis(250),
+            rate.duration_for(12_288_000 / 4)
+        );
+
+        // doesn't fit in a `u32`
+        const SYS: crate::System = system!(4_000_000_000, Stereo, f64);
+        assert_eq!(64_000_000_000, SYS.bytes_per_second());
+        assert_eq!(512_000_000_000, SYS.bit_rate());
+        assert_eq!(
+            Duration::from_secs(1),
+            SYS.byte_rate().duration_for(64_000_000_000)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use core::{fmt, time::Duration};
 
 use crate::{
     convert::{bytes_to_samples, samples_to_bytes},
@@ -15,7 +15,6 @@ mod sealed {
     /// (without remainder) by the size of a single frame
     /// ([`SYS.frame_size()`](System::frame_size)).
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct Bytes<const SYS: System>(usize);
 
@@ -79,6 +78,108 @@ impl<const SYS: System> Bytes<SYS> {
     }
 }
 
+impl<const SYS: System> Bytes<SYS> {
+    /// A [`Display`](fmt::Display) adapter printing this size in human-readable
+    /// units, e.g. `32.0 MiB`.
+    ///
+    /// ```
+    /// # use audio_time::{Bytes, AUDIO_CD};
+    /// #
+    /// let bytes = Bytes::<AUDIO_CD>::new(33_554_432).unwrap();
+    /// assert_eq!("32.0 MiB", bytes.display_size().to_string());
+    /// assert_eq!("33.6 MB", bytes.display_size().decimal().to_string());
+    /// assert_eq!(
+    ///     "32.0 MiB ≈ 3 m 10 s",
+    ///     bytes.display_size().with_duration().to_string()
+    /// );
+    /// ```
+    #[inline]
+    pub const fn display_size(&self) -> DisplaySize<SYS> {
+        DisplaySize {
+            bytes: *self,
+            decimal: false,
+            with_duration: false,
+        }
+    }
+}
+
+/// Helper struct for printing [`Bytes`] in human-readable units, created by
+/// [`Bytes::display_size`].
+///
+/// Uses binary units (KiB, MiB, ...) unless [`decimal`](DisplaySize::decimal)
+/// is called.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplaySize<const SYS: System> {
+    bytes: Bytes<SYS>,
+    decimal: bool,
+    with_duration: bool,
+}
+
+impl<const SYS: System> DisplaySize<SYS> {
+    /// Use decimal units (kB, MB, ...) instead of binary ones.
+    #[inline]
+    pub const fn decimal(self) -> Self {
+        Self {
+            decimal: true,
+            ..self
+        }
+    }
+
+    /// Append the (whole-second) duration of the bytes, e.g. `32.0 MiB ≈ 3 m
+    /// 10 s`.
+    #[inline]
+    pub const fn with_duration(self) -> Self {
+        Self {
+            with_duration: true,
+            ..self
+        }
+    }
+}
+
+impl<const SYS: System> fmt::Display for DisplaySize<SYS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BINARY: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+        const DECIMAL: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+        let (base, units) = if self.decimal {
+            (1_000., DECIMAL)
+        } else {
+            (1_024., BINARY)
+        };
+
+        let mut mantissa = self.bytes.get() as f64;
+        let mut unit = 0;
+        while mantissa >= base && unit < units.len() - 1 {
+            mantissa /= base;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} {}", self.bytes.get(), units[0])?;
+        } else {
+            write!(f, "{mantissa:.1} {}", units[unit])?;
+        }
+
+        if self.with_duration {
+            if let Ok(dur) = Duration::try_from(self.bytes) {
+                let secs = dur.as_secs();
+                let (hours, minutes, seconds) = (secs / 3_600, secs / 60 % 60, secs % 60);
+
+                f.write_str(" ≈ ")?;
+                if hours > 0 {
+                    write!(f, "{hours} h ")?;
+                }
+                if hours > 0 || minutes > 0 {
+                    write!(f, "{minutes} m ")?;
+                }
+                write!(f, "{seconds} s")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<const SYS: System> From<Bytes<SYS>> for usize {
     #[inline]
     fn from(value: Bytes<SYS>) -> Self {
@@ -92,3 +193,35 @@ macro_rules! bytes {
         ::audio_time::Bytes::new($n).unwrap()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    fn display(n: usize) -> DisplaySize<AUDIO_CD> {
+        Bytes::<AUDIO_CD>::new(n).unwrap().display_size()
+    }
+
+    #[test]
+    fn test_display_size() {
+        assert_eq!("0 B", display(0).to_string());
+        assert_eq!("0 B", display(0).decimal().to_string());
+        assert_eq!("0 B ≈ 0 s", display(0).with_duration().to_string());
+        assert_eq!("1020 B", display(1_020).to_string());
+        assert_eq!("1.0 kB", display(1_020).decimal().to_string());
+
+        assert_eq!("1.0 KiB", display(1_024).to_string());
+        assert_eq!("4.0 KiB", display(4_096).to_string());
+        assert_eq!("1.0 MiB", display(1_048_576).to_string());
+        assert_eq!("1.5 MiB", display(1_572_864).to_string());
+
+        let gibs = display(5 * 1_024 * 1_024 * 1_024);
+        assert_eq!("5.0 GiB", gibs.to_string());
+        assert_eq!("5.4 GB", gibs.decimal().to_string());
+        assert_eq!("5.0 GiB ≈ 8 h 27 m 14 s", gibs.with_duration().to_string());
+
+        // plain `Display` is unchanged
+        assert_eq!("4096", Bytes::<AUDIO_CD>::new(4_096).unwrap().to_string());
+    }
+}
@@ -1,17 +1,648 @@
-use std::{marker::ConstParamTy, num::NonZeroU8};
+#[cfg(feature = "std")]
+use core::str::FromStr;
+use core::{fmt, marker::ConstParamTy, num::NonZeroU8};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ConstParamTy)]
+mod sealed {
+    use core::marker::ConstParamTy;
+
+    use nonzero_const_param::NonZeroU8;
+
+    /// A number of channels that doesn't correspond to one of the named
+    /// [`ChannelLayout`](crate::ChannelLayout)s, i.e. at least 3.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
+    #[repr(transparent)]
+    pub struct Channels(NonZeroU8);
+
+    impl Channels {
+        /// Create a `Channels` if `n` is at least 3 (use
+        /// [`Mono`](crate::ChannelLayout::Mono) or
+        /// [`Stereo`](crate::ChannelLayout::Stereo) for fewer channels).
+        #[inline]
+        pub const fn new(n: core::num::NonZeroU8) -> Option<Self> {
+            if n.get() > 2 {
+                Some(Self(NonZeroU8::from_std(n)))
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        pub const fn get(&self) -> core::num::NonZeroU8 {
+            self.0.into_std()
+        }
+    }
+
+    /// The order of an [ambisonics](crate::ChannelLayout::Ambisonics) layout,
+    /// at most 14 (so that its `(order + 1)²` channels fit in a `u8`).
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
+    #[repr(transparent)]
+    pub struct AmbisonicsOrder(u8);
+
+    impl AmbisonicsOrder {
+        /// Create an `AmbisonicsOrder` if `order` is at most 14.
+        #[inline]
+        pub const fn new(order: u8) -> Option<Self> {
+            if order <= 14 {
+                Some(Self(order))
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        pub const fn get(&self) -> u8 {
+            self.0
+        }
+    }
+}
+
+pub use self::sealed::{AmbisonicsOrder, Channels};
+
+impl fmt::Debug for AmbisonicsOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+impl fmt::Debug for Channels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+/// Error returned when converting fewer than 3 channels into [`Channels`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("expected at least 3 channels")]
+pub struct TooFewChannelsError;
+
+impl TryFrom<u8> for Channels {
+    type Error = TooFewChannelsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        NonZeroU8::new(value)
+            .and_then(Self::new)
+            .ok_or(TooFewChannelsError)
+    }
+}
+
+impl From<Channels> for u8 {
+    fn from(value: Channels) -> Self {
+        value.get().get()
+    }
+}
+
+/// The position of a single channel in a [`ChannelLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChannelPosition {
+    FrontLeft,
+    FrontRight,
+    Center,
+    Lfe,
+    RearLeft,
+    RearRight,
+    SideLeft,
+    SideRight,
+    /// A channel with no speaker position, e.g. in an
+    /// [`Other`](ChannelLayout::Other) layout.
+    Discrete,
+}
+
+/// Error returned when a WAV channel mask disagrees with the number of
+/// channels.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Channel mask {mask:#x} does not describe {channels} channels")]
+pub struct UnknownMaskError {
+    pub mask: u32,
+    pub channels: NonZeroU8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
 pub enum ChannelLayout {
     Mono,
     Stereo,
+    /// Front left, front right, back left and back right.
+    Quad,
+    /// 5.1 surround: front left, front right, front center, LFE, back left and
+    /// back right.
+    Surround51,
+    /// 7.1 surround: 5.1, plus side left and side right.
+    Surround71,
+    /// Ambisonics of the given order, with `(order + 1)²` channels in ACN
+    /// order (e.g. 4 channels for first order, 16 for third order).
+    Ambisonics(AmbisonicsOrder),
+    /// Any other number of channels, e.g. for multichannel stems or
+    /// multi-microphone captures.
+    Other(Channels),
 }
 
 impl ChannelLayout {
+    /// The layout with the given number of channels: [`Mono`](Self::Mono) for
+    /// 1, [`Stereo`](Self::Stereo) for 2, and [`Other`](Self::Other)
+    /// otherwise.
+    #[inline]
+    pub const fn from_channels(channels: NonZeroU8) -> Self {
+        match channels.get() {
+            1 => Self::Mono,
+            2 => Self::Stereo,
+            _ => Self::Other(Channels::new(channels).unwrap()),
+        }
+    }
+
+    /// An [ambisonics](Self::Ambisonics) layout of the given order, or `None`
+    /// if the order is larger than 14.
+    ///
+    /// ```
+    /// # #![feature(const_option)]
+    /// # use audio_time::{system, ChannelLayout, System};
+    /// #
+    /// const THIRD_ORDER: System = system!(48_000, ChannelLayout::ambisonics(3).unwrap(), f32);
+    /// assert_eq!(16, THIRD_ORDER.channel_layout.channels().get());
+    /// ```
+    #[inline]
+    pub const fn ambisonics(order: u8) -> Option<Self> {
+        match AmbisonicsOrder::new(order) {
+            Some(order) => Some(Self::Ambisonics(order)),
+            None => None,
+        }
+    }
+
+    /// The order of this layout, if it is an [ambisonics](Self::Ambisonics)
+    /// layout.
+    #[inline]
+    pub const fn ambisonics_order(&self) -> Option<u8> {
+        match self {
+            Self::Ambisonics(order) => Some(order.get()),
+            _ => None,
+        }
+    }
+
     pub const fn channels(&self) -> NonZeroU8 {
-        NonZeroU8::new(match self {
-            Self::Mono => 1,
-            Self::Stereo => 2,
+        match self {
+            Self::Mono => NonZeroU8::new(1).unwrap(),
+            Self::Stereo => NonZeroU8::new(2).unwrap(),
+            Self::Quad => NonZeroU8::new(4).unwrap(),
+            Self::Surround51 => NonZeroU8::new(6).unwrap(),
+            Self::Surround71 => NonZeroU8::new(8).unwrap(),
+            Self::Ambisonics(order) => {
+                let n = order.get() + 1;
+                NonZeroU8::new(n * n).unwrap()
+            }
+            Self::Other(channels) => channels.get(),
+        }
+    }
+
+    /// The position of each channel in this layout, in interleaving order.
+    ///
+    /// The order is the one used by WAV (and SMPTE), e.g. for 5.1: front left,
+    /// front right, center, LFE, rear left and rear right. All channels of
+    /// [`Ambisonics`](Self::Ambisonics) and [`Other`](Self::Other) layouts
+    /// are [`Discrete`](ChannelPosition::Discrete).
+    #[inline]
+    pub const fn positions(&self) -> &'static [ChannelPosition] {
+        use ChannelPosition::*;
+
+        match self {
+            Self::Mono => &[Center],
+            Self::Stereo => &[FrontLeft, FrontRight],
+            Self::Quad => &[FrontLeft, FrontRight, RearLeft, RearRight],
+            Self::Surround51 => &[FrontLeft, FrontRight, Center, Lfe, RearLeft, RearRight],
+            Self::Surround71 => &[
+                FrontLeft, FrontRight, Center, Lfe, RearLeft, RearRight, SideLeft, SideRight,
+            ],
+            Self::Ambisonics(_) | Self::Other(_) => {
+                let (positions, _) =
+                    [Discrete; u8::MAX as usize].split_at(self.channels().get() as usize);
+                positions
+            }
+        }
+    }
+
+    /// The interleaved index of the channel at `position`, if this layout has
+    /// one.
+    ///
+    /// ```
+    /// # use audio_time::{ChannelLayout, ChannelPosition};
+    /// #
+    /// assert_eq!(
+    ///     Some(3),
+    ///     ChannelLayout::Surround51.index_of(ChannelPosition::Lfe)
+    /// );
+    /// assert_eq!(None, ChannelLayout::Stereo.index_of(ChannelPosition::Lfe));
+    /// ```
+    #[inline]
+    pub const fn index_of(&self, position: ChannelPosition) -> Option<u8> {
+        if matches!(position, ChannelPosition::Discrete) {
+            return None;
+        }
+
+        let positions = self.positions();
+        let mut i = 0;
+        while i < positions.len() {
+            if positions[i] as u8 == position as u8 {
+                return Some(i as u8);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// The `dwChannelMask` describing this layout in a `WAVE_FORMAT_EXTENSIBLE`
+    /// header, or `None` for [`Ambisonics`](Self::Ambisonics) and
+    /// [`Other`](Self::Other) layouts.
+    #[inline]
+    pub const fn to_wave_mask(&self) -> Option<u32> {
+        match self {
+            Self::Mono => Some(0x4),
+            Self::Stereo => Some(0x3),
+            Self::Quad => Some(0x33),
+            Self::Surround51 => Some(0x3f),
+            Self::Surround71 => Some(0x63f),
+            Self::Ambisonics(_) | Self::Other(_) => None,
+        }
+    }
+
+    /// The layout described by a `WAVE_FORMAT_EXTENSIBLE` header's
+    /// `dwChannelMask` and channel count.
+    ///
+    /// Masks that don't correspond to one of the named layouts (including a
+    /// mask of 0, i.e. unspecified) fall back to
+    /// [`from_channels`](Self::from_channels). Fails if the number of bits set
+    /// in a non-zero mask disagrees with `channels`.
+    #[inline]
+    pub const fn from_wave_mask(mask: u32, channels: NonZeroU8) -> Result<Self, UnknownMaskError> {
+        if mask != 0 && mask.count_ones() != channels.get() as u32 {
+            return Err(UnknownMaskError { mask, channels });
+        }
+
+        Ok(match mask {
+            0x4 => Self::Mono,
+            0x3 => Self::Stereo,
+            0x33 => Self::Quad,
+            0x3f => Self::Surround51,
+            0x63f => Self::Surround71,
+            _ => Self::from_channels(channels),
         })
-        .unwrap()
+    }
+
+    /// Equivalent to `self == other`, but usable in const contexts.
+    #[inline]
+    pub(crate) const fn const_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Other(a), Self::Other(b)) => a.get().get() == b.get().get(),
+            (Self::Ambisonics(a), Self::Ambisonics(b)) => a.get() == b.get(),
+            (Self::Ambisonics(_) | Self::Other(_), _)
+            | (_, Self::Ambisonics(_) | Self::Other(_)) => false,
+            // named layouts are uniquely identified by their channel masks
+            _ => matches!(
+                (self.to_wave_mask(), other.to_wave_mask()),
+                (Some(a), Some(b)) if a == b
+            ),
+        }
+    }
+}
+
+/// Formats the layout's canonical lowercase name (`mono`, `stereo`, `quad`,
+/// `5.1` or `7.1`), `ambisonics` followed by the order (e.g. `ambisonics3`)
+/// for [`Ambisonics`](ChannelLayout::Ambisonics) layouts, or the number of
+/// channels followed by `ch` (e.g. `16ch`) for [`Other`](ChannelLayout::Other)
+/// layouts.
+impl fmt::Display for ChannelLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Mono => f.write_str("mono"),
+            Self::Stereo => f.write_str("stereo"),
+            Self::Quad => f.write_str("quad"),
+            Self::Surround51 => f.write_str("5.1"),
+            Self::Surround71 => f.write_str("7.1"),
+            Self::Ambisonics(order) => write!(f, "ambisonics{}", order.get()),
+            Self::Other(channels) => write!(f, "{}ch", channels.get()),
+        }
+    }
+}
+
+/// Error returned when parsing a [`ChannelLayout`].
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error(
+    "Unknown channel layout `{0}` (expected one of `mono`, `stereo`, `quad`, `5.1`, `7.1`, \
+     `ambisonics<order>`, or a number of channels such as `2` or `6ch`)"
+)]
+pub struct ParseChannelLayoutError(String);
+
+impl ChannelLayout {
+    /// Parses a layout, without allocating (see the `FromStr` implementation).
+    #[cfg(any(feature = "serde", feature = "std"))]
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        const AMBISONICS: &str = "ambisonics";
+
+        let named = [
+            ("mono", Self::Mono),
+            ("stereo", Self::Stereo),
+            ("quad", Self::Quad),
+            ("5.1", Self::Surround51),
+            ("7.1", Self::Surround71),
+        ];
+        if let Some(&(_, layout)) = named.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+            return Some(layout);
+        }
+
+        if let Some(order) = s
+            .get(..AMBISONICS.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(AMBISONICS))
+            .map(|_| &s[AMBISONICS.len()..])
+        {
+            return order.parse().ok().and_then(Self::ambisonics);
+        }
+
+        let channels = match s.len().checked_sub(2).map(|i| s.split_at_checked(i)) {
+            Some(Some((channels, suffix))) if suffix.eq_ignore_ascii_case("ch") => channels,
+            _ => s,
+        };
+        channels
+            .parse()
+            .ok()
+            .and_then(NonZeroU8::new)
+            .map(Self::from_channels)
+    }
+}
+
+/// Parses the layout's canonical name (see the `Display` implementation),
+/// case-insensitively, or a number of channels (optionally followed by `ch`,
+/// e.g. `6ch`), which is interpreted using
+/// [`from_channels`](ChannelLayout::from_channels).
+#[cfg(feature = "std")]
+impl FromStr for ChannelLayout {
+    type Err = ParseChannelLayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| ParseChannelLayoutError(s.to_owned()))
+    }
+}
+
+/// Serialized as the layout's canonical name (see the `Display`
+/// implementation) in human-readable formats, and as the number of channels (a
+/// `u8`) in binary formats. This representation is stable.
+///
+/// Since binary formats only encode the number of channels, the named
+/// multichannel layouts ([`Quad`](ChannelLayout::Quad),
+/// [`Surround51`](ChannelLayout::Surround51),
+/// [`Surround71`](ChannelLayout::Surround71) and
+/// [`Ambisonics`](ChannelLayout::Ambisonics)) are deserialized from them as
+/// their [`from_channels`](ChannelLayout::from_channels) equivalents.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ChannelLayout {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u8(self.channels().get())
+        }
+    }
+}
+
+/// Accepts both the name and the number of channels (see the `Serialize`
+/// implementation), and fails for zero channels.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ChannelLayout {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected, Visitor};
+
+        struct LayoutVisitor;
+
+        impl Visitor<'_> for LayoutVisitor {
+            type Value = ChannelLayout;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a channel layout name, or a non-zero number of channels")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                ChannelLayout::parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .ok()
+                    .and_then(NonZeroU8::new)
+                    .map(ChannelLayout::from_channels)
+                    .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v)
+                    .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+                    .and_then(|v| self.visit_u64(v))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(LayoutVisitor)
+        } else {
+            deserializer.deserialize_u8(LayoutVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_channels() {
+        let layout = |n| ChannelLayout::from_channels(NonZeroU8::new(n).unwrap());
+
+        assert_eq!(ChannelLayout::Mono, layout(1));
+        assert_eq!(ChannelLayout::Stereo, layout(2));
+        for n in [3, 16, 255] {
+            assert_eq!(n, layout(n).channels().get());
+            assert!(matches!(layout(n), ChannelLayout::Other(_)));
+        }
+
+        assert!(Channels::new(NonZeroU8::new(2).unwrap()).is_none());
+        assert_eq!(Err(TooFewChannelsError), Channels::try_from(0));
+        assert_eq!(Err(TooFewChannelsError), Channels::try_from(2));
+        assert_eq!(3, u8::from(Channels::try_from(3).unwrap()));
+    }
+
+    #[test]
+    fn test_wave_mask() {
+        let channels = |n| NonZeroU8::new(n).unwrap();
+
+        for layout in [
+            ChannelLayout::Mono,
+            ChannelLayout::Stereo,
+            ChannelLayout::Quad,
+            ChannelLayout::Surround51,
+            ChannelLayout::Surround71,
+        ] {
+            let mask = layout.to_wave_mask().unwrap();
+            assert_eq!(
+                Ok(layout),
+                ChannelLayout::from_wave_mask(mask, layout.channels())
+            );
+        }
+        assert_eq!(Some(0x3f), ChannelLayout::Surround51.to_wave_mask());
+        assert_eq!(Some(0x63f), ChannelLayout::Surround71.to_wave_mask());
+        assert_eq!(
+            None,
+            ChannelLayout::from_channels(channels(6)).to_wave_mask()
+        );
+
+        // unknown masks fall back to a count-only layout
+        assert_eq!(
+            Ok(ChannelLayout::from_channels(channels(3))),
+            ChannelLayout::from_wave_mask(0x7, channels(3))
+        );
+        assert_eq!(
+            Ok(ChannelLayout::from_channels(channels(6))),
+            ChannelLayout::from_wave_mask(0, channels(6))
+        );
+        assert_eq!(
+            Err(UnknownMaskError {
+                mask: 0x3f,
+                channels: channels(8)
+            }),
+            ChannelLayout::from_wave_mask(0x3f, channels(8))
+        );
+
+        assert!(ChannelLayout::Surround51.const_eq(&ChannelLayout::Surround51));
+        assert!(!ChannelLayout::Surround51.const_eq(&ChannelLayout::from_channels(channels(6))));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_display_from_str() {
+        let channels = |n| ChannelLayout::from_channels(NonZeroU8::new(n).unwrap());
+
+        for (layout, s) in [
+            (ChannelLayout::Mono, "mono"),
+            (ChannelLayout::Stereo, "stereo"),
+            (ChannelLayout::Quad, "quad"),
+            (ChannelLayout::Surround51, "5.1"),
+            (ChannelLayout::Surround71, "7.1"),
+            (channels(6), "6ch"),
+            (channels(255), "255ch"),
+        ] {
+            assert_eq!(s, layout.to_string());
+            assert_eq!(Ok(layout), s.parse());
+        }
+
+        for (s, layout) in [
+            ("1", ChannelLayout::Mono),
+            ("2", ChannelLayout::Stereo),
+            ("2ch", ChannelLayout::Stereo),
+            ("STEREO", ChannelLayout::Stereo),
+            ("16", channels(16)),
+        ] {
+            assert_eq!(Ok(layout), s.parse());
+        }
+
+        for s in ["", "0", "0ch", "256ch", "5.2", "surround"] {
+            assert_eq!(
+                Err(ParseChannelLayoutError(s.to_owned())),
+                s.parse::<ChannelLayout>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        assert_eq!(
+            Some(3),
+            ChannelLayout::Surround51.index_of(ChannelPosition::Lfe)
+        );
+        assert_eq!(
+            Some(7),
+            ChannelLayout::Surround71.index_of(ChannelPosition::SideRight)
+        );
+        assert_eq!(None, ChannelLayout::Stereo.index_of(ChannelPosition::Lfe));
+        assert_eq!(
+            Some(0),
+            ChannelLayout::Mono.index_of(ChannelPosition::Center)
+        );
+
+        let other = ChannelLayout::from_channels(NonZeroU8::new(16).unwrap());
+        assert_eq!(None, other.index_of(ChannelPosition::Discrete));
+
+        for layout in [
+            ChannelLayout::Mono,
+            ChannelLayout::Stereo,
+            ChannelLayout::Quad,
+            ChannelLayout::Surround51,
+            ChannelLayout::Surround71,
+            other,
+            ChannelLayout::from_channels(NonZeroU8::new(255).unwrap()),
+        ] {
+            assert_eq!(
+                layout.channels().get() as usize,
+                layout.positions().len(),
+                "{layout}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let channels = |n| ChannelLayout::from_channels(NonZeroU8::new(n).unwrap());
+
+        for (layout, json) in [
+            (ChannelLayout::Mono, r#""mono""#),
+            (ChannelLayout::Surround51, r#""5.1""#),
+            (channels(16), r#""16ch""#),
+        ] {
+            assert_eq!(json, serde_json::to_string(&layout).unwrap());
+            assert_eq!(layout, serde_json::from_str::<ChannelLayout>(json).unwrap());
+        }
+
+        // numeric form
+        assert_eq!(
+            ChannelLayout::Stereo,
+            serde_json::from_str::<ChannelLayout>("2").unwrap()
+        );
+        assert_eq!(
+            channels(6),
+            serde_json::from_str::<ChannelLayout>("6").unwrap()
+        );
+        for invalid in ["0", "256", "-1", r#""0ch""#, r#""surround""#] {
+            assert!(
+                serde_json::from_str::<ChannelLayout>(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+
+        for layout in [ChannelLayout::Mono, ChannelLayout::Stereo, channels(16)] {
+            let bin = bincode::serialize(&layout).unwrap();
+            assert_eq!([layout.channels().get()], *bin);
+            assert_eq!(layout, bincode::deserialize::<ChannelLayout>(&bin).unwrap());
+        }
+        let bin = bincode::serialize(&ChannelLayout::Surround51).unwrap();
+        assert_eq!(
+            channels(6),
+            bincode::deserialize::<ChannelLayout>(&bin).unwrap()
+        );
+        assert!(bincode::deserialize::<ChannelLayout>(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_ambisonics() {
+        for (order, channels) in [(0, 1), (1, 4), (2, 9), (3, 16), (14, 225)] {
+            let layout = ChannelLayout::ambisonics(order).unwrap();
+            assert_eq!(channels, layout.channels().get());
+            assert_eq!(Some(order), layout.ambisonics_order());
+            assert_eq!(channels as usize, layout.positions().len());
+            #[cfg(feature = "std")]
+            assert_eq!(Ok(layout), layout.to_string().parse());
+        }
+        assert!(ChannelLayout::ambisonics(15).is_none());
+        assert_eq!(None, ChannelLayout::Quad.ambisonics_order());
+
+        let first_order = ChannelLayout::ambisonics(1).unwrap();
+        assert_ne!(ChannelLayout::Quad, first_order);
+        assert!(!ChannelLayout::Quad.const_eq(&first_order));
+        assert!(first_order.const_eq(&ChannelLayout::ambisonics(1).unwrap()));
+        assert_eq!(None, first_order.to_wave_mask());
+        #[cfg(feature = "std")]
+        assert!("ambisonics15".parse::<ChannelLayout>().is_err());
     }
 }
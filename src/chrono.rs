@@ -0,0 +1,104 @@
+//! Conversions between [`Frames`]/[`FramesDelta`] and chrono's (signed)
+//! [`TimeDelta`], with nanosecond precision (limited to deltas of ±292 years).
+
+use ::chrono::TimeDelta;
+
+use crate::{
+    signed_duration::{frames_delta_from_nanos, frames_delta_to_nanos, frames_from_nanos},
+    Frames, FramesDelta, OverflowError, SignedDurationError, System,
+};
+
+/// Truncates any partial frame.
+impl<const SYS: System> TryFrom<TimeDelta> for Frames<SYS> {
+    type Error = SignedDurationError;
+
+    #[inline]
+    fn try_from(value: TimeDelta) -> Result<Self, Self::Error> {
+        let nanos = value
+            .num_nanoseconds()
+            .ok_or(SignedDurationError::Overflow)?;
+        frames_from_nanos(nanos.into())
+    }
+}
+
+/// Truncates any partial nanosecond.
+impl<const SYS: System> TryFrom<Frames<SYS>> for TimeDelta {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Frames<SYS>) -> Result<Self, Self::Error> {
+        let nanos = i64::try_from(value.to_nanos_u64()?).map_err(|_| OverflowError(()))?;
+        Ok(Self::nanoseconds(nanos))
+    }
+}
+
+/// Truncates any partial frame, i.e. rounds towards zero.
+impl<const SYS: System> TryFrom<TimeDelta> for FramesDelta<SYS> {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: TimeDelta) -> Result<Self, Self::Error> {
+        let nanos = value.num_nanoseconds().ok_or(OverflowError(()))?;
+        frames_delta_from_nanos(nanos.into())
+    }
+}
+
+/// Truncates any partial nanosecond, i.e. rounds towards zero.
+impl<const SYS: System> TryFrom<FramesDelta<SYS>> for TimeDelta {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: FramesDelta<SYS>) -> Result<Self, Self::Error> {
+        let nanos = i64::try_from(frames_delta_to_nanos(value)?).map_err(|_| OverflowError(()))?;
+        Ok(Self::nanoseconds(nanos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[test]
+    fn test_time_delta() -> Result<(), OverflowError> {
+        let delta = TimeDelta::milliseconds(1_500);
+        let frames = Frames::<AUDIO_CD>::try_from(delta).unwrap();
+        assert_eq!(66_150, frames.get());
+        assert_eq!(delta, TimeDelta::try_from(frames)?);
+
+        // 1 frame at 44.1 kHz is 22_675.736... ns
+        let frame = TimeDelta::try_from(Frames::<AUDIO_CD>::new(1))?;
+        assert_eq!(Some(22_675), frame.num_nanoseconds());
+
+        assert_eq!(
+            Err(SignedDurationError::Negative),
+            Frames::<AUDIO_CD>::try_from(-delta)
+        );
+        assert_eq!(
+            Err(SignedDurationError::Overflow),
+            Frames::<AUDIO_CD>::try_from(TimeDelta::MAX)
+        );
+        assert!(TimeDelta::try_from(Frames::<AUDIO_CD>::new(usize::MAX)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_delta_signed() -> Result<(), OverflowError> {
+        for millis in [-1_500, 0, 1_500] {
+            let delta = TimeDelta::milliseconds(millis);
+            let frames = FramesDelta::<AUDIO_CD>::try_from(delta)?;
+            assert_eq!(millis as isize * 441 / 10, frames.get());
+            assert_eq!(delta, TimeDelta::try_from(frames)?);
+        }
+
+        // partial frames are truncated towards zero
+        let frames = FramesDelta::<AUDIO_CD>::try_from(TimeDelta::nanoseconds(-45_000))?;
+        assert_eq!(-1, frames.get());
+
+        assert!(FramesDelta::<AUDIO_CD>::try_from(TimeDelta::MIN).is_err());
+        assert!(TimeDelta::try_from(FramesDelta::<AUDIO_CD>::new(isize::MIN)).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+//! Iterators over fixed-size, whole-frame chunks of interleaved byte buffers,
+//! e.g. for feeding an encoder or packetizer with a fixed period.
+
+use core::{
+    iter::FusedIterator,
+    slice::{Chunks, ChunksMut},
+};
+
+use crate::{convert::frames_to_bytes, Frames, System};
+
+/// The length in bytes of `chunk` frames, saturating on overflow (in which case
+/// any buffer fits in a single chunk).
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+#[inline]
+#[track_caller]
+fn chunk_len<const SYS: System>(chunk: Frames<SYS>) -> usize {
+    assert!(chunk.get() != 0, "chunk size must be non-zero");
+    frames_to_bytes(chunk).map_or(usize::MAX, |bytes| bytes.get())
+}
+
+/// The length of the longest prefix of a `len` bytes long buffer that is a
+/// whole number of frames.
+#[inline]
+const fn aligned_len<const SYS: System>(len: usize) -> usize {
+    len - len % SYS.frame_size().get() as usize
+}
+
+#[inline]
+const fn frames_of<const SYS: System>(chunk: &[u8]) -> Frames<SYS> {
+    Frames::new(chunk.len() / SYS.frame_size().get() as usize)
+}
+
+/// Splits `buf` into chunks of `chunk` frames each.
+///
+/// Every chunk except possibly the last is exactly `chunk` frames long, and the
+/// last one is truncated to a whole number of frames. The trailing bytes that
+/// don't make up a whole frame are available through
+/// [`remainder`](FrameChunks::remainder).
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+#[inline]
+#[track_caller]
+pub fn chunks_frames<const SYS: System>(buf: &[u8], chunk: Frames<SYS>) -> FrameChunks<'_, SYS> {
+    let (aligned, remainder) = buf.split_at(aligned_len::<SYS>(buf.len()));
+    FrameChunks {
+        chunks: aligned.chunks(chunk_len(chunk)),
+        remainder,
+    }
+}
+
+/// Like [`chunks_frames`], but yields mutable chunks.
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+#[inline]
+#[track_caller]
+pub fn chunks_frames_mut<const SYS: System>(
+    buf: &mut [u8],
+    chunk: Frames<SYS>,
+) -> FrameChunksMut<'_, SYS> {
+    let (aligned, remainder) = buf.split_at_mut(aligned_len::<SYS>(buf.len()));
+    FrameChunksMut {
+        chunks: aligned.chunks_mut(chunk_len(chunk)),
+        remainder,
+    }
+}
+
+/// Like [`chunks_frames`], but consumes `buf`, yielding owned chunks.
+///
+/// The trailing partial frame can be taken back out of the iterator using
+/// [`into_remainder`](IntoFrameChunks::into_remainder), which reuses `buf`'s
+/// allocation, e.g. to prepend it to the next buffer.
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+#[cfg(feature = "std")]
+#[inline]
+#[track_caller]
+pub fn into_chunks_frames<const SYS: System>(
+    buf: Vec<u8>,
+    chunk: Frames<SYS>,
+) -> IntoFrameChunks<SYS> {
+    IntoFrameChunks {
+        chunk_len: chunk_len(chunk),
+        pos: 0,
+        aligned: aligned_len::<SYS>(buf.len()),
+        buf,
+    }
+}
+
+/// Iterator returned by [`chunks_frames`].
+#[derive(Debug, Clone)]
+pub struct FrameChunks<'a, const SYS: System> {
+    chunks: Chunks<'a, u8>,
+    remainder: &'a [u8],
+}
+
+impl<'a, const SYS: System> FrameChunks<'a, SYS> {
+    /// The trailing bytes of the buffer that don't make up a whole frame.
+    #[inline]
+    pub const fn remainder(&self) -> &'a [u8] {
+        self.remainder
+    }
+}
+
+impl<'a, const SYS: System> Iterator for FrameChunks<'a, SYS> {
+    type Item = (&'a [u8], Frames<SYS>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|chunk| (chunk, frames_of(chunk)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<const SYS: System> DoubleEndedIterator for FrameChunks<'_, SYS> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks
+            .next_back()
+            .map(|chunk| (chunk, frames_of(chunk)))
+    }
+}
+
+impl<const SYS: System> ExactSizeIterator for FrameChunks<'_, SYS> {}
+
+impl<const SYS: System> FusedIterator for FrameChunks<'_, SYS> {}
+
+/// Iterator returned by [`chunks_frames_mut`].
+#[derive(Debug)]
+pub struct FrameChunksMut<'a, const SYS: System> {
+    chunks: ChunksMut<'a, u8>,
+    remainder: &'a mut [u8],
+}
+
+impl<'a, const SYS: System> FrameChunksMut<'a, SYS> {
+    /// The trailing bytes of the buffer that don't make up a whole frame.
+    #[inline]
+    pub fn remainder(&self) -> &[u8] {
+        self.remainder
+    }
+
+    /// Like [`remainder`](FrameChunksMut::remainder), but consumes the
+    /// iterator, returning the trailing bytes with its full lifetime.
+    #[inline]
+    pub fn into_remainder(self) -> &'a mut [u8] {
+        self.remainder
+    }
+}
+
+impl<'a, const SYS: System> Iterator for FrameChunksMut<'a, SYS> {
+    type Item = (&'a mut [u8], Frames<SYS>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|chunk| {
+            let frames = frames_of(chunk);
+            (chunk, frames)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<const SYS: System> DoubleEndedIterator for FrameChunksMut<'_, SYS> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back().map(|chunk| {
+            let frames = frames_of(chunk);
+            (chunk, frames)
+        })
+    }
+}
+
+impl<const SYS: System> ExactSizeIterator for FrameChunksMut<'_, SYS> {}
+
+impl<const SYS: System> FusedIterator for FrameChunksMut<'_, SYS> {}
+
+/// Iterator returned by [`into_chunks_frames`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct IntoFrameChunks<const SYS: System> {
+    buf: Vec<u8>,
+    chunk_len: usize,
+    /// Start of the next chunk.
+    pos: usize,
+    /// End of the last whole frame in `buf`.
+    aligned: usize,
+}
+
+#[cfg(feature = "std")]
+impl<const SYS: System> IntoFrameChunks<SYS> {
+    /// The trailing bytes of the buffer that don't make up a whole frame.
+    #[inline]
+    pub fn remainder(&self) -> &[u8] {
+        &self.buf[self.aligned..]
+    }
+
+    /// Consumes the iterator, returning the trailing bytes of the buffer that
+    /// don't make up a whole frame. Chunks that weren't yielded yet are
+    /// discarded.
+    #[inline]
+    pub fn into_remainder(self) -> Vec<u8> {
+        let mut buf = self.buf;
+        buf.drain(..self.aligned);
+        buf
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SYS: System> Iterator for IntoFrameChunks<SYS> {
+    type Item = (Vec<u8>, Frames<SYS>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.aligned {
+            return None;
+        }
+
+        let end = self.aligned.min(self.pos.saturating_add(self.chunk_len));
+        let chunk = self.buf[self.pos..end].to_vec();
+        self.pos = end;
+
+        let frames = frames_of(&chunk);
+        Some((chunk, frames))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.aligned - self.pos).div_ceil(self.chunk_len);
+        (len, Some(len))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SYS: System> ExactSizeIterator for IntoFrameChunks<SYS> {}
+
+#[cfg(feature = "std")]
+impl<const SYS: System> FusedIterator for IntoFrameChunks<SYS> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::AUDIO_CD;
+
+    #[test]
+    fn test_tail() {
+        let buf: Vec<u8> = (0..43).collect();
+        let chunk = Frames::<AUDIO_CD>::new(4);
+
+        let mut chunks = chunks_frames(&buf, chunk);
+        assert_eq!(3, chunks.len());
+        assert_eq!(&[40, 41, 42], chunks.remainder());
+        assert_eq!(Some((&buf[..16], Frames::new(4))), chunks.next());
+        assert_eq!(Some((&buf[16..32], Frames::new(4))), chunks.next());
+        assert_eq!(Some((&buf[32..40], Frames::new(2))), chunks.next());
+        assert_eq!(None, chunks.next());
+
+        let mut buf_mut = buf.clone();
+        let mut chunks = chunks_frames_mut(&mut buf_mut, chunk);
+        for (chunk, frames) in &mut chunks {
+            chunk.fill(frames.get() as u8);
+        }
+        chunks.into_remainder().fill(0xff);
+        assert_eq!(&[4; 32], &buf_mut[..32]);
+        assert_eq!(&[2; 8], &buf_mut[32..40]);
+        assert_eq!(&[0xff; 3], &buf_mut[40..]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_chunks() {
+        let buf: Vec<u8> = (0..43).collect();
+        let chunk = Frames::<AUDIO_CD>::new(4);
+
+        let mut chunks = into_chunks_frames(buf.clone(), chunk);
+        assert_eq!(3, chunks.len());
+        assert_eq!(Some((buf[..16].to_vec(), Frames::new(4))), chunks.next());
+        assert_eq!(2, chunks.len());
+        assert_eq!(&[40, 41, 42], chunks.remainder());
+        assert_eq!(vec![40, 41, 42], chunks.into_remainder());
+
+        let chunks: Vec<_> = into_chunks_frames(buf, chunk)
+            .map(|(_, f)| f.get())
+            .collect();
+        assert_eq!(vec![4, 4, 2], chunks);
+
+        let chunks = into_chunks_frames(vec![0; 3_528], Frames::<AUDIO_CD>::new(441));
+        assert!(chunks.remainder().is_empty());
+        assert_eq!(2, chunks.count());
+    }
+
+    #[test]
+    fn test_exact_multiple() {
+        let buf = [0; 3_528];
+        let chunk = Frames::<AUDIO_CD>::new(441);
+
+        let chunks = chunks_frames(&buf, chunk);
+        assert!(chunks.remainder().is_empty());
+        assert!(chunks
+            .clone()
+            .all(|(c, frames)| c.len() == 1_764 && frames == chunk));
+        assert_eq!(2, chunks.count());
+
+        assert_eq!(2, chunks_frames_mut(&mut [0; 3_528], chunk).count());
+
+        assert_eq!(0, chunks_frames(&[0; 3], chunk).count());
+        assert_eq!(
+            1,
+            chunks_frames(&buf, Frames::<AUDIO_CD>::new(usize::MAX)).count()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_chunk() {
+        chunks_frames(&[0; 4], Frames::<AUDIO_CD>::new(0));
+    }
+}
@@ -0,0 +1,161 @@
+//! Packet sizes of common audio codecs.
+//!
+//! Codecs encode audio in packets spanning a fixed number of frames, e.g.:
+//! ```
+//! # use audio_time::{codec, AUDIO_CD};
+//! #
+//! let frames = codec::aac_lc::<AUDIO_CD>();
+//! assert_eq!(1_024, frames.get());
+//! assert_eq!(4_096, codec::pcm_bytes_per_packet(frames).get());
+//! ```
+
+use core::time::Duration;
+
+use crate::{Bytes, Frames, NotIntegralError, OverflowError, System};
+
+/// Frames per packet of AAC-LC.
+#[inline]
+pub const fn aac_lc<const SYS: System>() -> Frames<SYS> {
+    Frames::new(1_024)
+}
+
+/// Frames per packet of HE-AAC (at the output sample rate, i.e. after spectral
+/// band replication).
+#[inline]
+pub const fn he_aac<const SYS: System>() -> Frames<SYS> {
+    Frames::new(2_048)
+}
+
+/// Frames per packet of MPEG-1 Layer III (MP3).
+///
+/// MPEG-2/2.5 Layer III streams (sample rates of 24 kHz and below) use half as
+/// many frames per packet, see [`mp3_lsf`].
+#[inline]
+pub const fn mp3<const SYS: System>() -> Frames<SYS> {
+    Frames::new(1_152)
+}
+
+/// Frames per packet of MPEG-2/2.5 Layer III (low sampling frequency MP3).
+#[inline]
+pub const fn mp3_lsf<const SYS: System>() -> Frames<SYS> {
+    Frames::new(576)
+}
+
+/// The frame durations supported by Opus.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum OpusFrameDuration {
+    /// 2.5 milliseconds
+    Ms2_5,
+    Ms5,
+    Ms10,
+    Ms20,
+    Ms40,
+    Ms60,
+}
+
+impl OpusFrameDuration {
+    #[inline]
+    pub const fn duration(self) -> Duration {
+        Duration::from_micros(self.tenths_of_millis() as u64 * 100)
+    }
+
+    const fn tenths_of_millis(self) -> u32 {
+        match self {
+            Self::Ms2_5 => 25,
+            Self::Ms5 => 50,
+            Self::Ms10 => 100,
+            Self::Ms20 => 200,
+            Self::Ms40 => 400,
+            Self::Ms60 => 600,
+        }
+    }
+}
+
+/// Frames per packet of Opus, for the given frame duration.
+///
+/// Fails if the duration does not span a whole number of frames in `SYS` (e.g.
+/// 2.5 milliseconds at 44.1 kHz).
+#[inline]
+pub const fn opus<const SYS: System>(
+    duration: OpusFrameDuration,
+) -> Result<Frames<SYS>, NotIntegralError> {
+    let n = SYS.sample_rate.get().get() as u64 * duration.tenths_of_millis() as u64;
+
+    if n % 10_000 == 0 {
+        Ok(Frames::new((n / 10_000) as usize))
+    } else {
+        Err(NotIntegralError(()))
+    }
+}
+
+/// The number of whole packets of `frames_per_packet` frames needed to encode
+/// `dur`, and the number of frames left over after the last whole packet.
+///
+/// # Panics
+///
+/// Panics if `frames_per_packet` is zero.
+#[inline]
+#[track_caller]
+pub fn packets_for_duration<const SYS: System>(
+    dur: Duration,
+    frames_per_packet: Frames<SYS>,
+) -> Result<(usize, Frames<SYS>), OverflowError> {
+    let frames =
+        Frames::<SYS>::from_nanos_u64(dur.as_nanos().try_into().map_err(|_| OverflowError(()))?)?;
+
+    Ok((
+        frames.get() / frames_per_packet.get(),
+        Frames::new(frames.get() % frames_per_packet.get()),
+    ))
+}
+
+/// The number of bytes of (decoded) PCM audio in a single packet of
+/// `frames_per_packet` frames.
+///
+/// Equivalent to `frames_per_packet.into_bytes()`.
+#[inline]
+#[track_caller]
+pub const fn pcm_bytes_per_packet<const SYS: System>(frames_per_packet: Frames<SYS>) -> Bytes<SYS> {
+    frames_per_packet.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_aac_mp3() -> Result<(), OverflowError> {
+        assert_eq!(4_096, pcm_bytes_per_packet(aac_lc::<AUDIO_CD>()).get());
+        assert_eq!(4_608, pcm_bytes_per_packet(mp3::<AUDIO_CD>()).get());
+
+        assert_eq!(
+            (43, Frames::new(68)),
+            packets_for_duration(Duration::from_secs(1), aac_lc::<AUDIO_CD>())?
+        );
+        assert_eq!(
+            (38, Frames::new(324)),
+            packets_for_duration(Duration::from_secs(1), mp3::<AUDIO_CD>())?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_opus() -> Result<(), NotIntegralError> {
+        const SYS: System = system!(48_000, Stereo, f32);
+
+        assert_eq!(120, opus::<SYS>(OpusFrameDuration::Ms2_5)?.get());
+        assert_eq!(960, opus::<SYS>(OpusFrameDuration::Ms20)?.get());
+        assert_eq!(2_880, opus::<SYS>(OpusFrameDuration::Ms60)?.get());
+        assert_eq!(
+            Duration::from_micros(2_500),
+            OpusFrameDuration::Ms2_5.duration()
+        );
+
+        assert!(opus::<AUDIO_CD>(OpusFrameDuration::Ms2_5).is_err());
+        assert_eq!(882, opus::<AUDIO_CD>(OpusFrameDuration::Ms20)?.get());
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 //! Implementations of the [`From`] and [`TryFrom`] traits for converting
-//! between [`Samples`], [`Bytes`], and [`Duration`]s.
+//! between [`Samples`], [`Bytes`], [`Bits`], and [`Duration`]s, and functions
+//! for converting whole slices of them at once.
 
-use std::time::Duration;
+use core::time::Duration;
 
-pub(crate) use self::{frames::*, samples::*};
-use crate::{frames::Frames, Bytes, OverflowError, Samples, System};
+pub(crate) use self::{bits::*, frames::*, samples::*};
+use crate::{frames::Frames, Bits, Bytes, OverflowError, Samples, System};
 
 mod frames {
     use super::*;
@@ -200,6 +201,91 @@ mod samples {
     }
 }
 
+mod bits {
+    use super::*;
+
+    //
+    // Bits <-> Samples
+    //
+
+    pub(crate) const fn bits_to_samples<const SYS: System>(value: Bits<SYS>) -> Samples<SYS> {
+        Samples::new(value.get() / SYS.bits_per_sample().get() as usize).unwrap()
+    }
+
+    pub(crate) const fn samples_to_bits<const SYS: System>(
+        value: Samples<SYS>,
+    ) -> Result<Bits<SYS>, OverflowError> {
+        let bits = value
+            .get()
+            .checked_mul(SYS.bits_per_sample().get() as usize);
+
+        match bits {
+            Some(n) => Ok(Bits::new(n).unwrap()),
+            None => Err(OverflowError(())),
+        }
+    }
+
+    impl<const SYS: System> From<Bits<SYS>> for Samples<SYS> {
+        #[inline]
+        fn from(value: Bits<SYS>) -> Self {
+            bits_to_samples(value)
+        }
+    }
+
+    impl<const SYS: System> TryFrom<Samples<SYS>> for Bits<SYS> {
+        type Error = OverflowError;
+
+        #[inline]
+        fn try_from(value: Samples<SYS>) -> Result<Self, Self::Error> {
+            samples_to_bits(value)
+        }
+    }
+
+    //
+    // Bits <-> Bytes (via Samples)
+    //
+
+    impl<const SYS: System> TryFrom<Bytes<SYS>> for Bits<SYS> {
+        type Error = OverflowError;
+
+        #[inline]
+        fn try_from(value: Bytes<SYS>) -> Result<Self, Self::Error> {
+            Samples::from(value).try_into()
+        }
+    }
+
+    impl<const SYS: System> TryFrom<Bits<SYS>> for Bytes<SYS> {
+        type Error = OverflowError;
+
+        #[inline]
+        fn try_from(value: Bits<SYS>) -> Result<Self, Self::Error> {
+            Samples::from(value).try_into()
+        }
+    }
+
+    //
+    // Bits <-> Duration (via Samples)
+    //
+
+    impl<const SYS: System> TryFrom<Duration> for Bits<SYS> {
+        type Error = OverflowError;
+
+        #[inline]
+        fn try_from(value: Duration) -> Result<Self, Self::Error> {
+            Samples::<SYS>::try_from(value)?.try_into()
+        }
+    }
+
+    impl<const SYS: System> TryFrom<Bits<SYS>> for Duration {
+        type Error = OverflowError;
+
+        #[inline]
+        fn try_from(value: Bits<SYS>) -> Result<Self, Self::Error> {
+            Samples::from(value).try_into()
+        }
+    }
+}
+
 //
 // Bytes <-> Duration (via Samples)
 //
@@ -221,3 +307,164 @@ impl<const SYS: System> TryFrom<Bytes<SYS>> for Duration {
         Samples::<SYS>::from(value).try_into()
     }
 }
+
+//
+// Slices
+//
+
+/// Error returned when converting a slice of spans, if one of them overflows.
+#[derive(thiserror::Error, Debug)]
+#[error("Overflow error converting element at index {index}")]
+pub struct SliceOverflowError {
+    index: usize,
+}
+
+impl SliceOverflowError {
+    /// The index of the first element that failed to convert.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Clear `output` and fill it with the conversions of all elements of `input`.
+#[cfg(feature = "std")]
+#[inline]
+fn convert_slice<T: Copy, U>(
+    input: &[T],
+    output: &mut Vec<U>,
+    convert: impl Fn(T) -> Result<U, OverflowError>,
+) -> Result<(), SliceOverflowError> {
+    output.clear();
+    output.reserve(input.len());
+
+    for (index, &value) in input.iter().enumerate() {
+        output.push(convert(value).map_err(|_| SliceOverflowError { index })?);
+    }
+
+    Ok(())
+}
+
+/// Convert each of the given frames into a [`Duration`], replacing the contents
+/// of `output`.
+///
+/// On failure, `output` holds the conversions of all elements preceding the
+/// failing one.
+#[cfg(feature = "std")]
+pub fn frames_to_durations_slice<const SYS: System>(
+    input: &[Frames<SYS>],
+    output: &mut Vec<Duration>,
+) -> Result<(), SliceOverflowError> {
+    convert_slice(input, output, frames_to_duration)
+}
+
+/// Convert each of the given frames into [`Bytes`], replacing the contents of
+/// `output`.
+///
+/// On failure, `output` holds the conversions of all elements preceding the
+/// failing one.
+#[cfg(feature = "std")]
+pub fn frames_to_bytes_slice<const SYS: System>(
+    input: &[Frames<SYS>],
+    output: &mut Vec<Bytes<SYS>>,
+) -> Result<(), SliceOverflowError> {
+    convert_slice(input, output, frames_to_bytes)
+}
+
+/// Convert each of the given frames into [`Samples`], replacing the contents of
+/// `output`.
+///
+/// On failure, `output` holds the conversions of all elements preceding the
+/// failing one.
+#[cfg(feature = "std")]
+pub fn frames_to_samples_slice<const SYS: System>(
+    input: &[Frames<SYS>],
+    output: &mut Vec<Samples<SYS>>,
+) -> Result<(), SliceOverflowError> {
+    convert_slice(input, output, frames_to_samples)
+}
+
+/// Convert each of the given samples into a [`Duration`], replacing the
+/// contents of `output`.
+///
+/// On failure, `output` holds the conversions of all elements preceding the
+/// failing one.
+#[cfg(feature = "std")]
+pub fn samples_to_durations_slice<const SYS: System>(
+    input: &[Samples<SYS>],
+    output: &mut Vec<Duration>,
+) -> Result<(), SliceOverflowError> {
+    convert_slice(input, output, |samples| samples.try_into())
+}
+
+/// Convert each of the given bytes into a [`Duration`], replacing the contents
+/// of `output`.
+///
+/// On failure, `output` holds the conversions of all elements preceding the
+/// failing one.
+#[cfg(feature = "std")]
+pub fn bytes_to_durations_slice<const SYS: System>(
+    input: &[Bytes<SYS>],
+    output: &mut Vec<Duration>,
+) -> Result<(), SliceOverflowError> {
+    convert_slice(input, output, |bytes| bytes.try_into())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_slices() -> Result<(), SliceOverflowError> {
+        let frames: Vec<_> = (0..10_000)
+            .map(|n| Frames::<AUDIO_CD>::new(n * 7_919))
+            .collect();
+
+        let mut durations = vec![];
+        frames_to_durations_slice(&frames, &mut durations)?;
+        let mut bytes = vec![];
+        frames_to_bytes_slice(&frames, &mut bytes)?;
+        let mut samples = vec![];
+        frames_to_samples_slice(&frames, &mut samples)?;
+        assert_eq!(frames.len(), durations.len());
+
+        for (i, frames) in frames.iter().enumerate() {
+            assert_eq!(frames.into_duration(), durations[i]);
+            assert_eq!(frames.into_bytes(), bytes[i]);
+            assert_eq!(frames.into_samples(), samples[i]);
+        }
+
+        let mut from_samples = vec![];
+        samples_to_durations_slice(&samples, &mut from_samples)?;
+        let mut from_bytes = vec![];
+        bytes_to_durations_slice(&bytes, &mut from_bytes)?;
+        assert_eq!(durations, from_samples);
+        assert_eq!(durations, from_bytes);
+
+        // empty slices clear the output
+        frames_to_durations_slice::<AUDIO_CD>(&[], &mut durations)?;
+        assert!(durations.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_overflow() {
+        const SYS: System = system!(8_000, Mono, i16);
+
+        let frames = [0, 1, usize::MAX, 2, usize::MAX].map(Frames::<SYS>::new);
+        let mut durations = vec![];
+        let err = frames_to_durations_slice(&frames, &mut durations).unwrap_err();
+        assert_eq!(2, err.index());
+        assert_eq!(2, durations.len());
+
+        let mut bytes = vec![];
+        assert_eq!(
+            2,
+            frames_to_bytes_slice(&frames, &mut bytes)
+                .unwrap_err()
+                .index()
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Conversions between [`DynSystem`]s and CoreAudio stream descriptions
+//! (`AudioStreamBasicDescription`).
+//!
+//! [`Asbd`] mirrors the C struct's layout, so no CoreAudio bindings are
+//! needed. Only interleaved linear PCM is supported.
+
+use crate::{
+    stream_format::channel_layout_of, DynSampleType, DynSystem, Endianness, SampleKind, SampleRate,
+    StreamParamsError, System,
+};
+
+/// `kAudioFormatLinearPCM` (`'lpcm'`).
+pub const AUDIO_FORMAT_LINEAR_PCM: u32 = u32::from_be_bytes(*b"lpcm");
+
+pub const AUDIO_FORMAT_FLAG_IS_FLOAT: u32 = 1 << 0;
+pub const AUDIO_FORMAT_FLAG_IS_BIG_ENDIAN: u32 = 1 << 1;
+pub const AUDIO_FORMAT_FLAG_IS_SIGNED_INTEGER: u32 = 1 << 2;
+/// Samples use all the bits of their containers.
+pub const AUDIO_FORMAT_FLAG_IS_PACKED: u32 = 1 << 3;
+/// Samples narrower than their containers are in the high bits.
+pub const AUDIO_FORMAT_FLAG_IS_ALIGNED_HIGH: u32 = 1 << 4;
+/// Each channel is in a separate buffer.
+pub const AUDIO_FORMAT_FLAG_IS_NON_INTERLEAVED: u32 = 1 << 5;
+pub const AUDIO_FORMAT_FLAG_IS_NON_MIXABLE: u32 = 1 << 6;
+
+/// An `AudioStreamBasicDescription`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct Asbd {
+    pub sample_rate: f64,
+    /// One of the `kAudioFormat*` IDs, e.g. [`AUDIO_FORMAT_LINEAR_PCM`].
+    pub format_id: u32,
+    /// A combination of the `AUDIO_FORMAT_FLAG_*` flags.
+    pub format_flags: u32,
+    pub bytes_per_packet: u32,
+    pub frames_per_packet: u32,
+    pub bytes_per_frame: u32,
+    pub channels_per_frame: u32,
+    pub bits_per_channel: u32,
+    pub reserved: u32,
+}
+
+/// Error returned from [`DynSystem::from_asbd`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+pub enum UnsupportedAsbdError {
+    #[error("Format {0:#010x} is not linear PCM")]
+    NotLinearPcm(u32),
+    #[error("Non-interleaved formats are not supported")]
+    NonInterleaved,
+    #[error("Sample rate of {0} Hz is not a whole number of hertz")]
+    NonIntegralRate(f64),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+    #[error("Linear PCM must have one frame per packet of {bytes_per_frame} bytes")]
+    InvalidPacket {
+        bytes_per_frame: u32,
+        bytes_per_packet: u32,
+        frames_per_packet: u32,
+    },
+    #[error(
+        "{bytes_per_frame} bytes per frame is inconsistent with {channels} channels of \
+         {bits_per_channel} bits"
+    )]
+    InconsistentFrameSize {
+        bytes_per_frame: u32,
+        channels: u32,
+        bits_per_channel: u32,
+    },
+    #[error("Unsupported {bits_per_channel}-bit sample format (flags {format_flags:#x})")]
+    UnsupportedSampleFormat {
+        format_flags: u32,
+        bits_per_channel: u32,
+    },
+}
+
+impl DynSystem {
+    /// The system of a stream with an interleaved linear PCM description.
+    ///
+    /// The sample rate is snapped to a whole number of hertz within
+    /// [`SampleRate::DEFAULT_HZ_TOLERANCE`], and the layout is derived from
+    /// the number of channels alone (see
+    /// [`ChannelLayout::from_channels`](crate::ChannelLayout::from_channels)).
+    ///
+    /// `mBitsPerChannel` becomes the sample type's
+    /// [bit depth](crate::SampleType::bit_depth), whether samples narrower
+    /// than their containers are in the low or
+    /// [high](AUDIO_FORMAT_FLAG_IS_ALIGNED_HIGH) bits, as the sample type
+    /// doesn't record their alignment. Describe them again with
+    /// [`System::to_asbd`] or [`System::to_asbd_aligned_high`] respectively.
+    pub const fn from_asbd(asbd: &Asbd) -> Result<Self, UnsupportedAsbdError> {
+        if asbd.format_id != AUDIO_FORMAT_LINEAR_PCM {
+            return Err(UnsupportedAsbdError::NotLinearPcm(asbd.format_id));
+        }
+        let flags = asbd.format_flags;
+        if flags & AUDIO_FORMAT_FLAG_IS_NON_INTERLEAVED != 0 {
+            return Err(UnsupportedAsbdError::NonInterleaved);
+        }
+
+        let Some(sample_rate) = snap_rate(asbd.sample_rate) else {
+            return Err(UnsupportedAsbdError::NonIntegralRate(asbd.sample_rate));
+        };
+        let channels = asbd.channels_per_frame;
+        let channel_layout = match channel_layout_of(channels) {
+            Ok(layout) => layout,
+            Err(err) => return Err(UnsupportedAsbdError::Params(err)),
+        };
+
+        if asbd.frames_per_packet != 1 || asbd.bytes_per_packet != asbd.bytes_per_frame {
+            return Err(UnsupportedAsbdError::InvalidPacket {
+                bytes_per_frame: asbd.bytes_per_frame,
+                bytes_per_packet: asbd.bytes_per_packet,
+                frames_per_packet: asbd.frames_per_packet,
+            });
+        }
+
+        // the container size is implied by the frame size, and must hold the
+        // sample's bits exactly if packed
+        let bits = asbd.bits_per_channel;
+        let byte_depth = asbd.bytes_per_frame / channels;
+        let packed = flags & AUDIO_FORMAT_FLAG_IS_PACKED != 0;
+        let container_bits = byte_depth as u64 * 8;
+        if byte_depth * channels != asbd.bytes_per_frame
+            || container_bits < bits as u64
+            || (packed && container_bits != bits as u64)
+        {
+            return Err(UnsupportedAsbdError::InconsistentFrameSize {
+                bytes_per_frame: asbd.bytes_per_frame,
+                channels,
+                bits_per_channel: bits,
+            });
+        }
+
+        let kind = if flags & AUDIO_FORMAT_FLAG_IS_FLOAT != 0 {
+            SampleKind::Float
+        } else if flags & AUDIO_FORMAT_FLAG_IS_SIGNED_INTEGER != 0 {
+            SampleKind::SignedInt
+        } else {
+            SampleKind::UnsignedInt
+        };
+        let endianness = if byte_depth == 1 {
+            Endianness::Native
+        } else if flags & AUDIO_FORMAT_FLAG_IS_BIG_ENDIAN != 0 {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        };
+
+        let sample_type = match DynSampleType::new(kind, byte_depth as u8, bits as u8) {
+            Some(sample_type) if byte_depth <= 8 => {
+                sample_type.with_endianness(endianness).to_sample_type()
+            }
+            _ => None,
+        };
+        let Some(sample_type) = sample_type else {
+            return Err(UnsupportedAsbdError::UnsupportedSampleFormat {
+                format_flags: flags,
+                bits_per_channel: bits,
+            });
+        };
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+/// The nearest whole number of hertz to `hz`, if within
+/// [`SampleRate::DEFAULT_HZ_TOLERANCE`] of it.
+const fn snap_rate(hz: f64) -> Option<SampleRate> {
+    // `!(hz < ...)` also rejects NaN
+    if !(hz >= 0.5 && hz < u32::MAX as f64) {
+        return None;
+    }
+
+    let snapped = (hz + 0.5) as u32;
+    if (hz - snapped as f64).abs() > SampleRate::DEFAULT_HZ_TOLERANCE as f64 {
+        return None;
+    }
+    SampleRate::from_hz(snapped)
+}
+
+impl System {
+    /// The description of an interleaved linear PCM stream in this system.
+    ///
+    /// Native-endian sample types are described in the target's byte order,
+    /// and samples narrower than their containers are in the low bits (see
+    /// [`to_asbd_aligned_high`](System::to_asbd_aligned_high)). Sample types
+    /// without a [kind](crate::SampleType::kind) are described as unsigned
+    /// integers.
+    pub const fn to_asbd(&self) -> Asbd {
+        let sample_type = self.sample_type.with_resolved_endianness();
+        let byte_depth = sample_type.byte_depth().get() as u32;
+        let bits = sample_type.bit_depth().get() as u32;
+
+        let mut format_flags = match sample_type.kind() {
+            Some(SampleKind::Float) => AUDIO_FORMAT_FLAG_IS_FLOAT,
+            Some(SampleKind::SignedInt) => AUDIO_FORMAT_FLAG_IS_SIGNED_INTEGER,
+            Some(SampleKind::UnsignedInt) | None => 0,
+        };
+        if matches!(sample_type.endianness(), Endianness::Big) {
+            format_flags |= AUDIO_FORMAT_FLAG_IS_BIG_ENDIAN;
+        }
+        if bits == byte_depth * 8 {
+            format_flags |= AUDIO_FORMAT_FLAG_IS_PACKED;
+        }
+
+        let bytes_per_frame = self.frame_size().get() as u32;
+        Asbd {
+            sample_rate: self.sample_rate.get().get() as f64,
+            format_id: AUDIO_FORMAT_LINEAR_PCM,
+            format_flags,
+            bytes_per_packet: bytes_per_frame,
+            frames_per_packet: 1,
+            bytes_per_frame,
+            channels_per_frame: self.channel_layout.channels().get() as u32,
+            bits_per_channel: bits,
+            reserved: 0,
+        }
+    }
+
+    /// Equivalent to [`to_asbd`](System::to_asbd), with samples narrower than
+    /// their containers in the high bits.
+    pub const fn to_asbd_aligned_high(&self) -> Asbd {
+        let mut asbd = self.to_asbd();
+        if asbd.format_flags & AUDIO_FORMAT_FLAG_IS_PACKED == 0 {
+            asbd.format_flags |= AUDIO_FORMAT_FLAG_IS_ALIGNED_HIGH;
+        }
+        asbd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, ChannelLayout, SampleType, I24};
+
+    const F32_STEREO: Asbd = Asbd {
+        sample_rate: 48_000.,
+        format_id: AUDIO_FORMAT_LINEAR_PCM,
+        format_flags: AUDIO_FORMAT_FLAG_IS_FLOAT | AUDIO_FORMAT_FLAG_IS_PACKED,
+        bytes_per_packet: 8,
+        frames_per_packet: 1,
+        bytes_per_frame: 8,
+        channels_per_frame: 2,
+        bits_per_channel: 32,
+        reserved: 0,
+    };
+
+    #[test]
+    fn test_round_trip() {
+        let system = DynSystem::from_asbd(&F32_STEREO).unwrap();
+        assert_eq!(SampleType::new_le::<f32>(), system.sample_type);
+        assert_eq!(ChannelLayout::Stereo, system.channel_layout);
+        assert_eq!(
+            F32_STEREO,
+            system.to_system().to_asbd(),
+            "{:?}",
+            system.to_system()
+        );
+
+        for sys in [
+            system!(44_100, Stereo, i16),
+            system!(8_000, Mono, u8),
+            system!(96_000, Stereo, I24),
+            system!(192_000, Stereo, f64),
+        ] {
+            let asbd = sys.to_asbd();
+            let system = DynSystem::from_asbd(&asbd).unwrap();
+            assert_eq!(asbd, system.to_system().to_asbd(), "{sys:?}");
+            assert_eq!(sys.frame_size(), system.to_system().frame_size());
+        }
+
+        let asbd = system!(48_000, Stereo, i16).to_asbd();
+        assert_eq!(
+            cfg!(target_endian = "big"),
+            asbd.format_flags & AUDIO_FORMAT_FLAG_IS_BIG_ENDIAN != 0
+        );
+    }
+
+    #[test]
+    fn test_from_asbd() {
+        let asbd = Asbd {
+            sample_rate: 47_999.999_9,
+            ..F32_STEREO
+        };
+        assert_eq!(
+            Ok(DynSystem::from_asbd(&F32_STEREO).unwrap()),
+            DynSystem::from_asbd(&asbd)
+        );
+
+        // 24 bits in 32-bit containers
+        let asbd = Asbd {
+            format_flags: AUDIO_FORMAT_FLAG_IS_SIGNED_INTEGER | AUDIO_FORMAT_FLAG_IS_BIG_ENDIAN,
+            bits_per_channel: 24,
+            ..F32_STEREO
+        };
+        let system = DynSystem::from_asbd(&asbd).unwrap();
+        assert_eq!(Endianness::Big, system.sample_type.endianness());
+        assert_eq!(4, system.sample_type.byte_depth().get());
+        assert_eq!(24, system.sample_type.bit_depth().get());
+        assert_eq!(asbd, system.to_system().to_asbd());
+
+        // ...in the high bits
+        let aligned_high = Asbd {
+            format_flags: asbd.format_flags | AUDIO_FORMAT_FLAG_IS_ALIGNED_HIGH,
+            ..asbd
+        };
+        assert_eq!(Ok(system), DynSystem::from_asbd(&aligned_high));
+        assert_eq!(aligned_high, system.to_system().to_asbd_aligned_high());
+
+        // packed samples have no alignment
+        assert_eq!(
+            F32_STEREO,
+            DynSystem::from_asbd(&F32_STEREO)
+                .unwrap()
+                .to_system()
+                .to_asbd_aligned_high()
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let asbd = Asbd {
+            format_flags: F32_STEREO.format_flags | AUDIO_FORMAT_FLAG_IS_NON_INTERLEAVED,
+            bytes_per_packet: 4,
+            bytes_per_frame: 4,
+            ..F32_STEREO
+        };
+        assert_eq!(
+            Err(UnsupportedAsbdError::NonInterleaved),
+            DynSystem::from_asbd(&asbd)
+        );
+
+        let aac = u32::from_be_bytes(*b"aac ");
+        let asbd = Asbd {
+            format_id: aac,
+            ..F32_STEREO
+        };
+        assert_eq!(
+            Err(UnsupportedAsbdError::NotLinearPcm(aac)),
+            DynSystem::from_asbd(&asbd)
+        );
+
+        for sample_rate in [44_100.5, 0., -48_000., f64::NAN] {
+            let asbd = Asbd {
+                sample_rate,
+                ..F32_STEREO
+            };
+            assert!(matches!(
+                DynSystem::from_asbd(&asbd),
+                Err(UnsupportedAsbdError::NonIntegralRate(_))
+            ));
+        }
+
+        let asbd = Asbd {
+            channels_per_frame: 0,
+            ..F32_STEREO
+        };
+        assert_eq!(
+            Err(UnsupportedAsbdError::Params(
+                StreamParamsError::UnsupportedChannels(0)
+            )),
+            DynSystem::from_asbd(&asbd)
+        );
+
+        // mBytesPerFrame must match the channels and bit depth
+        for (bytes_per_frame, bits_per_channel) in [(6, 32), (7, 32), (16, 32)] {
+            let asbd = Asbd {
+                bytes_per_frame,
+                bytes_per_packet: bytes_per_frame,
+                bits_per_channel,
+                ..F32_STEREO
+            };
+            assert_eq!(
+                Err(UnsupportedAsbdError::InconsistentFrameSize {
+                    bytes_per_frame,
+                    channels: 2,
+                    bits_per_channel,
+                }),
+                DynSystem::from_asbd(&asbd)
+            );
+        }
+
+        let asbd = Asbd {
+            bytes_per_packet: 0,
+            frames_per_packet: 1_024,
+            ..F32_STEREO
+        };
+        assert!(matches!(
+            DynSystem::from_asbd(&asbd),
+            Err(UnsupportedAsbdError::InvalidPacket { .. })
+        ));
+
+        // consistent, but there are no 24-bit floats
+        let asbd = Asbd {
+            format_flags: AUDIO_FORMAT_FLAG_IS_FLOAT | AUDIO_FORMAT_FLAG_IS_PACKED,
+            bytes_per_packet: 6,
+            bytes_per_frame: 6,
+            bits_per_channel: 24,
+            ..F32_STEREO
+        };
+        assert_eq!(
+            Err(UnsupportedAsbdError::UnsupportedSampleFormat {
+                format_flags: asbd.format_flags,
+                bits_per_channel: 24,
+            }),
+            DynSystem::from_asbd(&asbd)
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! [`Read`] and [`Write`] adapters that keep track of how much audio passed
+//! through them.
+
+use std::{
+    io::{self, BufRead, IoSlice, IoSliceMut, Read, Write},
+    time::Duration,
+};
+
+use crate::System;
+
+/// Wraps a reader of raw audio in `SYS`, counting the bytes read through it.
+///
+/// Reads are passed through unchanged, and may end mid-frame: the partial
+/// frame is completed by later reads.
+#[derive(Debug, Default)]
+pub struct CountingReader<R, const SYS: System> {
+    inner: R,
+    bytes: u64,
+}
+
+impl<R, const SYS: System> CountingReader<R, SYS> {
+    #[inline]
+    pub const fn new(inner: R) -> Self {
+        Self { inner, bytes: 0 }
+    }
+
+    #[inline]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Reading from the inner reader directly is not counted.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// The total number of bytes read so far.
+    ///
+    /// Not named `bytes`, since calls to it would resolve to [`Read::bytes`].
+    #[inline]
+    pub const fn bytes_read(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The number of whole frames read so far.
+    #[inline]
+    pub const fn whole_frames(&self) -> u64 {
+        self.bytes / SYS.frame_size().get() as u64
+    }
+
+    /// The duration of the whole frames read so far, truncated to whole
+    /// nanoseconds.
+    #[inline]
+    pub const fn elapsed(&self) -> Duration {
+        SYS.duration_for_bytes(self.bytes)
+    }
+
+    /// The number of bytes read of the current, incomplete frame.
+    #[inline]
+    pub const fn partial_frame_bytes(&self) -> usize {
+        (self.bytes % SYS.frame_size().get() as u64) as usize
+    }
+
+    #[inline]
+    pub(crate) fn count(&mut self, n: usize) {
+        self.bytes += n as u64;
+    }
+}
+
+impl<R: Read, const SYS: System> Read for CountingReader<R, SYS> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count(n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let n = self.inner.read_vectored(bufs)?;
+        self.count(n);
+        Ok(n)
+    }
+}
+
+/// Bytes are counted once they are [consumed](BufRead::consume).
+impl<R: BufRead, const SYS: System> BufRead for CountingReader<R, SYS> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.count(amt);
+    }
+}
+
+/// Wraps a writer of raw audio in `SYS`, counting the bytes written through
+/// it.
+///
+/// Optionally, the writer can be [limited](CountingWriter::limit) to a maximum
+/// duration, e.g. for capping the length of a recording.
+#[derive(Debug, Default)]
+pub struct CountingWriter<W, const SYS: System> {
+    inner: W,
+    bytes: u64,
+    limit: Option<u64>,
+}
+
+impl<W, const SYS: System> CountingWriter<W, SYS> {
+    #[inline]
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            bytes: 0,
+            limit: None,
+        }
+    }
+
+    /// Stop accepting bytes once `max` worth of audio was written, truncated to
+    /// a whole number of frames.
+    ///
+    /// Writes that would cross the limit are cut short at it, and once it is
+    /// reached, writes of non-empty buffers return `Ok(0)` (which
+    /// [`Write::write_all`] reports as an [`io::ErrorKind::WriteZero`]
+    /// error).
+    #[inline]
+    pub fn limit(self, max: Duration) -> Self {
+        Self {
+            limit: Some(SYS.bytes_for(max).unwrap_or(u64::MAX)),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writing to the inner writer directly is not counted.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// The total number of bytes written so far.
+    #[inline]
+    pub const fn bytes_written(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The number of whole frames written so far.
+    #[inline]
+    pub const fn whole_frames(&self) -> u64 {
+        self.bytes / SYS.frame_size().get() as u64
+    }
+
+    /// The duration of the whole frames written so far, truncated to whole
+    /// nanoseconds.
+    #[inline]
+    pub const fn elapsed(&self) -> Duration {
+        SYS.duration_for_bytes(self.bytes)
+    }
+
+    /// The number of bytes written of the current, incomplete frame.
+    #[inline]
+    pub const fn partial_frame_bytes(&self) -> usize {
+        (self.bytes % SYS.frame_size().get() as u64) as usize
+    }
+
+    /// The number of bytes that can still be written before reaching the
+    /// [limit](CountingWriter::limit), or `None` if there is none.
+    #[inline]
+    pub const fn remaining(&self) -> Option<u64> {
+        match self.limit {
+            Some(limit) => Some(limit.saturating_sub(self.bytes)),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn is_limited(&self) -> bool {
+        self.limit.is_some()
+    }
+
+    /// The part of `buf` that can be written without crossing the limit.
+    #[inline]
+    pub(crate) fn truncate<'a>(&self, buf: &'a [u8]) -> &'a [u8] {
+        match self.remaining() {
+            Some(remaining) => &buf[..buf.len().min(remaining.try_into().unwrap_or(usize::MAX))],
+            None => buf,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn count(&mut self, n: usize) {
+        self.bytes += n as u64;
+    }
+}
+
+impl<W: Write, const SYS: System> Write for CountingWriter<W, SYS> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let buf = self.truncate(buf);
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let n = self.inner.write(buf)?;
+        self.count(n);
+        Ok(n)
+    }
+
+    /// When [limited](CountingWriter::limit), only the first non-empty buffer
+    /// is written.
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        if self.is_limited() {
+            let buf = bufs.iter().find(|buf| !buf.is_empty());
+            return self.write(buf.map_or(&[], |buf| buf));
+        }
+
+        let n = self.inner.write_vectored(bufs)?;
+        self.count(n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_uneven_reads() -> io::Result<()> {
+        let mut reader = CountingReader::<_, AUDIO_CD>::new(Cursor::new(vec![0; 176_400]));
+
+        let mut buf = [0; 1_009];
+        reader.read_exact(&mut buf[..3])?;
+        assert_eq!(0, reader.whole_frames());
+        assert_eq!(3, reader.partial_frame_bytes());
+        assert_eq!(Duration::ZERO, reader.elapsed());
+
+        reader.read_exact(&mut buf[..6])?;
+        assert_eq!(2, reader.whole_frames());
+        assert_eq!(1, reader.partial_frame_bytes());
+
+        let mut sizes = [7, 13, 1_009, 2].into_iter().cycle();
+        while reader.read(&mut buf[..sizes.next().unwrap()])? != 0 {}
+
+        assert_eq!(176_400, reader.bytes_read());
+        assert_eq!(44_100, reader.whole_frames());
+        assert_eq!(0, reader.partial_frame_bytes());
+        assert_eq!(Duration::from_secs(1), reader.elapsed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_buf_read() -> io::Result<()> {
+        const SYS: System = system!(48_000, Stereo, crate::I24);
+
+        let mut reader = CountingReader::<_, SYS>::new(Cursor::new(vec![0; 1_000]));
+        assert_eq!(1_000, reader.fill_buf()?.len());
+        assert_eq!(0, reader.bytes_read());
+
+        reader.consume(7);
+        assert_eq!(1, reader.whole_frames());
+        assert_eq!(1, reader.partial_frame_bytes());
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest)?;
+        assert_eq!(993, rest.len());
+        assert_eq!(1_000, reader.bytes_read());
+        assert_eq!(166, reader.whole_frames());
+        assert_eq!(4, reader.partial_frame_bytes());
+        assert_eq!(SYS.duration_for_bytes(996), reader.elapsed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_writer() -> io::Result<()> {
+        let mut writer = CountingWriter::<_, AUDIO_CD>::new(Vec::new());
+
+        let data = [0; 176_400];
+        let mut written = 0;
+        for size in [3, 7, 13, 1_009].into_iter().cycle() {
+            let end = (written + size).min(data.len());
+            writer.write_all(&data[written..end])?;
+            written = end;
+            if written == data.len() {
+                break;
+            }
+            assert_eq!(written % 4, writer.partial_frame_bytes());
+        }
+        assert_eq!(
+            8,
+            writer.write_vectored(&[IoSlice::new(&[0; 3]), IoSlice::new(&[0; 5])])?
+        );
+        writer.flush()?;
+
+        assert_eq!(176_408, writer.bytes_written());
+        assert_eq!(44_102, writer.whole_frames());
+        assert_eq!(None, writer.remaining());
+        assert_eq!(
+            Duration::from_secs(1) + AUDIO_CD.duration_for_bytes(8),
+            writer.elapsed()
+        );
+        assert_eq!(176_408, writer.into_inner().len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit() -> io::Result<()> {
+        // 1 ms is 44.1 frames, cut off at 44
+        let mut writer =
+            CountingWriter::<_, AUDIO_CD>::new(Vec::new()).limit(Duration::from_millis(1));
+        assert_eq!(Some(176), writer.remaining());
+
+        writer.write_all(&[1; 101])?;
+        assert_eq!(75, writer.write(&[2; 101])?);
+        assert_eq!(0, writer.write(&[3; 1])?);
+        assert_eq!(
+            io::ErrorKind::WriteZero,
+            writer.write_all(&[3; 1]).unwrap_err().kind()
+        );
+        assert_eq!(0, writer.write_vectored(&[IoSlice::new(&[3; 4])])?);
+
+        assert_eq!(Some(0), writer.remaining());
+        assert_eq!(44, writer.whole_frames());
+        assert_eq!(0, writer.partial_frame_bytes());
+        assert_eq!(176, writer.get_ref().len());
+
+        let mut writer = CountingWriter::<_, AUDIO_CD>::new(Vec::new()).limit(Duration::MAX);
+        assert_eq!(
+            4,
+            writer.write_vectored(&[
+                IoSlice::new(&[]),
+                IoSlice::new(&[0; 4]),
+                IoSlice::new(&[0; 4]),
+            ])?
+        );
+        assert_eq!(4, writer.bytes_written());
+        assert_eq!(Some(u64::MAX - 4), writer.remaining());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+//! Conversions between [`SampleType`]s and [`System`]s, and [`cpal`]'s sample
+//! formats and stream configs.
+
+use ::cpal::{
+    ChannelCount, SampleFormat, StreamConfig, SupportedStreamConfig, SupportedStreamConfigRange,
+};
+
+use crate::{
+    stream_format::{channel_layout_of, sample_rate_of},
+    DynSystem, Endianness, SampleType, StreamParamsError, System,
+};
+
+/// Error returned when a `cpal` stream config can't be described by a
+/// [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpalConfigError {
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+    #[error("Unsupported sample format `{0}`")]
+    UnsupportedFormat(SampleFormat),
+}
+
+impl SampleType {
+    /// The sample type matching a `cpal` sample format, or `None` for formats
+    /// this crate has no counterpart for.
+    #[inline]
+    pub const fn from_cpal(fmt: SampleFormat) -> Option<Self> {
+        Some(match fmt {
+            SampleFormat::I8 => Self::new::<i8>(),
+            SampleFormat::I16 => Self::new::<i16>(),
+            SampleFormat::I32 => Self::new::<i32>(),
+            SampleFormat::I64 => Self::new::<i64>(),
+            SampleFormat::U8 => Self::new::<u8>(),
+            SampleFormat::U16 => Self::new::<u16>(),
+            SampleFormat::U32 => Self::new::<u32>(),
+            SampleFormat::U64 => Self::new::<u64>(),
+            SampleFormat::F32 => Self::new::<f32>(),
+            SampleFormat::F64 => Self::new::<f64>(),
+            _ => return None,
+        })
+    }
+
+    /// The `cpal` sample format matching this sample type, or `None` if `cpal`
+    /// has no counterpart (e.g. for packed 24-bit samples, or sample types with
+    /// a narrowed [bit depth](SampleType::bit_depth)).
+    #[inline]
+    pub const fn to_cpal(&self) -> Option<SampleFormat> {
+        const FORMATS: [SampleFormat; 10] = [
+            SampleFormat::I8,
+            SampleFormat::I16,
+            SampleFormat::I32,
+            SampleFormat::I64,
+            SampleFormat::U8,
+            SampleFormat::U16,
+            SampleFormat::U32,
+            SampleFormat::U64,
+            SampleFormat::F32,
+            SampleFormat::F64,
+        ];
+
+        let mut i = 0;
+        while i < FORMATS.len() {
+            if self.matches_cpal(FORMATS[i]) {
+                return Some(FORMATS[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether this sample type is the one described by the `cpal` sample
+    /// format `fmt`.
+    ///
+    /// `cpal` samples are always in the target's byte order, so sample types
+    /// with an explicit [endianness](SampleType::endianness) match if it is
+    /// the target's.
+    #[inline]
+    pub const fn matches_cpal(&self, fmt: SampleFormat) -> bool {
+        if self.is_big_endian() != cfg!(target_endian = "big") {
+            return false;
+        }
+
+        match Self::from_cpal(fmt) {
+            Some(sample_type) => sample_type.const_eq(&self.with_endianness(Endianness::Native)),
+            None => false,
+        }
+    }
+}
+
+impl System {
+    /// Whether a `cpal` stream opened with `config` and sample format `fmt`
+    /// carries audio in this system.
+    ///
+    /// The config's buffer size is ignored.
+    #[inline]
+    pub const fn matches_cpal(&self, config: &StreamConfig, fmt: SampleFormat) -> bool {
+        self.sample_rate.get().get() == config.sample_rate.0
+            && self.channel_layout.channels().get() as ChannelCount == config.channels
+            && self.sample_type.matches_cpal(fmt)
+    }
+
+    /// The best of the supported configs in `ranges` (e.g. as returned by
+    /// `cpal`'s `Device::supported_output_configs`) for playing audio in this
+    /// system, or `None` if none of them have a matching channel count and
+    /// sample format.
+    ///
+    /// A config supporting this system's sample rate is preferred, otherwise
+    /// the config is given the supported rate nearest to it (ties going to
+    /// the higher rate), in which case the audio will need to be resampled.
+    pub fn best_cpal_config(
+        &self,
+        ranges: impl IntoIterator<Item = SupportedStreamConfigRange>,
+    ) -> Option<SupportedStreamConfig> {
+        let rate = self.sample_rate.get().get();
+
+        ranges
+            .into_iter()
+            .filter(|range| {
+                range.channels() == self.channel_layout.channels().get() as ChannelCount
+                    && self.sample_type.matches_cpal(range.sample_format())
+            })
+            .map(|range| {
+                let nearest = rate.clamp(range.min_sample_rate().0, range.max_sample_rate().0);
+                range.with_sample_rate(::cpal::SampleRate(nearest))
+            })
+            .min_by_key(|config| {
+                let nearest = config.sample_rate().0;
+                (nearest.abs_diff(rate), nearest < rate)
+            })
+    }
+}
+
+impl DynSystem {
+    /// The system of a `cpal` stream opened with `config` and sample format
+    /// `fmt`.
+    pub fn from_cpal(config: &StreamConfig, fmt: SampleFormat) -> Result<Self, CpalConfigError> {
+        let sample_rate = sample_rate_of(config.sample_rate.0)?;
+        let channel_layout = channel_layout_of(config.channels.into())?;
+        let sample_type =
+            SampleType::from_cpal(fmt).ok_or(CpalConfigError::UnsupportedFormat(fmt))?;
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::cpal::{BufferSize, SupportedBufferSize};
+
+    use super::*;
+    use crate::{system, AUDIO_CD, I24};
+
+    #[test]
+    fn test_round_trip() {
+        for (fmt, sample_type) in [
+            (SampleFormat::I8, SampleType::new::<i8>()),
+            (SampleFormat::I16, SampleType::new::<i16>()),
+            (SampleFormat::I32, SampleType::new::<i32>()),
+            (SampleFormat::I64, SampleType::new::<i64>()),
+            (SampleFormat::U8, SampleType::new::<u8>()),
+            (SampleFormat::U16, SampleType::new::<u16>()),
+            (SampleFormat::U32, SampleType::new::<u32>()),
+            (SampleFormat::U64, SampleType::new::<u64>()),
+            (SampleFormat::F32, SampleType::new::<f32>()),
+            (SampleFormat::F64, SampleType::new::<f64>()),
+        ] {
+            assert_eq!(Some(sample_type), SampleType::from_cpal(fmt));
+            assert_eq!(Some(fmt), sample_type.to_cpal());
+            assert!(sample_type.matches_cpal(fmt));
+            assert_eq!(fmt.sample_size(), sample_type.byte_depth().get() as usize);
+        }
+    }
+
+    #[test]
+    fn test_unsupported() {
+        for sample_type in [
+            SampleType::new::<I24>(),
+            SampleType::new::<i128>(),
+            SampleType::new::<[u8; 2]>(),
+            SampleType::with_valid_bits::<i32>(24).unwrap(),
+        ] {
+            assert_eq!(None, sample_type.to_cpal(), "{sample_type}");
+        }
+
+        assert!(!SampleType::new::<i16>().matches_cpal(SampleFormat::U16));
+        assert!(!SampleType::new::<f32>().matches_cpal(SampleFormat::I32));
+
+        let (native, foreign) = if cfg!(target_endian = "big") {
+            (SampleType::new_be::<i16>(), SampleType::new_le::<i16>())
+        } else {
+            (SampleType::new_le::<i16>(), SampleType::new_be::<i16>())
+        };
+        assert_eq!(Some(SampleFormat::I16), native.to_cpal());
+        assert_eq!(None, foreign.to_cpal());
+    }
+
+    fn config(channels: ChannelCount, rate: u32) -> StreamConfig {
+        StreamConfig {
+            channels,
+            sample_rate: ::cpal::SampleRate(rate),
+            buffer_size: BufferSize::Default,
+        }
+    }
+
+    fn range(
+        channels: ChannelCount,
+        min: u32,
+        max: u32,
+        fmt: SampleFormat,
+    ) -> SupportedStreamConfigRange {
+        SupportedStreamConfigRange::new(
+            channels,
+            ::cpal::SampleRate(min),
+            ::cpal::SampleRate(max),
+            SupportedBufferSize::Unknown,
+            fmt,
+        )
+    }
+
+    #[test]
+    fn test_stream_config() {
+        let cd = config(2, 44_100);
+        assert!(AUDIO_CD.matches_cpal(&cd, SampleFormat::I16));
+        assert!(!AUDIO_CD.matches_cpal(&cd, SampleFormat::F32));
+        assert!(!AUDIO_CD.matches_cpal(&config(1, 44_100), SampleFormat::I16));
+        assert!(!AUDIO_CD.matches_cpal(&config(2, 48_000), SampleFormat::I16));
+        assert!(!AUDIO_CD.matches_cpal(
+            &StreamConfig {
+                buffer_size: BufferSize::Fixed(512),
+                ..cd.clone()
+            },
+            SampleFormat::U16
+        ));
+
+        let system = DynSystem::from_cpal(&cd, SampleFormat::I16).unwrap();
+        assert!(system.is::<AUDIO_CD>());
+        assert!(DynSystem::from_cpal(&config(255, 48_000), SampleFormat::F32).is_ok());
+
+        assert_eq!(
+            Err(CpalConfigError::Params(
+                StreamParamsError::UnsupportedChannels(256)
+            )),
+            DynSystem::from_cpal(&config(256, 48_000), SampleFormat::F32)
+        );
+        assert_eq!(
+            Err(CpalConfigError::Params(
+                StreamParamsError::UnsupportedChannels(0)
+            )),
+            DynSystem::from_cpal(&config(0, 48_000), SampleFormat::F32)
+        );
+        assert_eq!(
+            Err(CpalConfigError::Params(StreamParamsError::ZeroRate)),
+            DynSystem::from_cpal(&config(2, 0), SampleFormat::F32)
+        );
+    }
+
+    #[test]
+    fn test_best_config() {
+        const SYS: System = system!(48_000, Stereo, f32);
+
+        let ranges = [
+            range(2, 8_000, 44_100, SampleFormat::F32),
+            range(2, 8_000, 192_000, SampleFormat::I16),
+            range(1, 8_000, 192_000, SampleFormat::F32),
+            range(2, 96_000, 192_000, SampleFormat::F32),
+        ];
+        assert_eq!(
+            None,
+            SYS.best_cpal_config(ranges.iter().copied().skip(1).take(2))
+        );
+
+        // 44.1 kHz is nearer than 96 kHz
+        let best = SYS.best_cpal_config(ranges).unwrap();
+        assert_eq!(44_100, best.sample_rate().0);
+        assert_eq!(SampleFormat::F32, best.sample_format());
+
+        // an exact match wins
+        let best = SYS
+            .best_cpal_config(ranges.into_iter().chain([range(
+                2,
+                44_100,
+                48_000,
+                SampleFormat::F32,
+            )]))
+            .unwrap();
+        assert!(SYS.matches_cpal(&best.config(), best.sample_format()));
+
+        // ties go to the higher rate
+        let best = SYS
+            .best_cpal_config([
+                range(2, 8_000, 47_000, SampleFormat::F32),
+                range(2, 49_000, 96_000, SampleFormat::F32),
+            ])
+            .unwrap();
+        assert_eq!(49_000, best.sample_rate().0);
+    }
+}
@@ -0,0 +1,584 @@
+//! Conversion of spans between two different [`System`]s.
+
+use core::{cmp::Ordering, time::Duration};
+
+use crate::{
+    convert::{frames_to_bytes, frames_to_samples},
+    sample_rate::{duration_from_nanos, NANOS_PER_SEC},
+    Bytes, Frames, OverflowError, Rounding, Samples, System,
+};
+
+impl<const A: System> Frames<A> {
+    /// Convert these frames into the number of frames spanning the same
+    /// duration in system `B`, i.e. `self * B.sample_rate / A.sample_rate`,
+    /// rounded in the direction of `rounding`.
+    ///
+    /// The conversion is exact (and `rounding` is irrelevant) if both systems
+    /// have the same sample rate.
+    #[inline]
+    pub const fn convert_to<const B: System>(
+        self,
+        rounding: Rounding,
+    ) -> Result<Frames<B>, OverflowError> {
+        let (from, to) = (
+            A.sample_rate.get().get() as u128,
+            B.sample_rate.get().get() as u128,
+        );
+
+        if from == to {
+            return Ok(Frames::new(self.get()));
+        }
+
+        let frames = rounding.div(self.get() as u128 * to, from);
+
+        if frames <= usize::MAX as u128 {
+            Ok(Frames::new(frames as usize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+}
+
+impl<const A: System> Samples<A> {
+    /// Convert these samples into the number of samples spanning the same
+    /// duration in system `B`.
+    ///
+    /// See [`Frames::convert_to`].
+    #[inline]
+    pub const fn convert_to<const B: System>(
+        self,
+        rounding: Rounding,
+    ) -> Result<Samples<B>, OverflowError> {
+        match self.into_frames().convert_to::<B>(rounding) {
+            Ok(frames) => frames_to_samples(frames),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const A: System> Bytes<A> {
+    /// Convert these bytes into the number of bytes spanning the same duration
+    /// in system `B`.
+    ///
+    /// See [`Frames::convert_to`].
+    #[inline]
+    pub const fn convert_to<const B: System>(
+        self,
+        rounding: Rounding,
+    ) -> Result<Bytes<B>, OverflowError> {
+        match Frames::from_bytes(self).convert_to::<B>(rounding) {
+            Ok(frames) => frames_to_bytes(frames),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Conversion of a span from system `FROM`, preserving its duration.
+///
+/// Implemented for [`Frames`], [`Samples`] and [`Bytes`] of any system, using
+/// their respective `convert_to` methods (e.g. [`Frames::convert_to`]). Useful
+/// for writing code that is generic over the source system:
+/// ```
+/// # #![feature(adt_const_params, const_option)]
+/// # #![allow(incomplete_features)]
+/// use audio_time::{system, Frames, FromSystem, OverflowError, Rounding, System, AUDIO_CD};
+///
+/// const PIPELINE: System = system!(48_000, Stereo, f32);
+///
+/// fn enqueue<const S: System>(span: Frames<S>) -> Result<Frames<PIPELINE>, OverflowError>
+/// where
+///     Frames<PIPELINE>: FromSystem<S, Source = Frames<S>>,
+/// {
+///     Frames::from_system(span, Rounding::Nearest)
+/// }
+///
+/// assert_eq!(48_000, enqueue(Frames::<AUDIO_CD>::new(44_100))?.get());
+/// # Ok::<_, OverflowError>(())
+/// ```
+pub trait FromSystem<const FROM: System>: Sized {
+    /// The type of span being converted.
+    type Source;
+
+    fn from_system(source: Self::Source, rounding: Rounding) -> Result<Self, OverflowError>;
+}
+
+/// The reciprocal of [`FromSystem`], implemented for every [`Frames`],
+/// [`Samples`] and [`Bytes`].
+pub trait IntoSystem<T> {
+    fn into_system(self, rounding: Rounding) -> Result<T, OverflowError>;
+}
+
+macro_rules! impl_from_system {
+    ($name:ident) => {
+        impl<const FROM: System, const TO: System> FromSystem<FROM> for $name<TO> {
+            type Source = $name<FROM>;
+
+            #[inline]
+            fn from_system(
+                source: Self::Source,
+                rounding: Rounding,
+            ) -> Result<Self, OverflowError> {
+                source.convert_to(rounding)
+            }
+        }
+
+        impl<const FROM: System, T> IntoSystem<T> for $name<FROM>
+        where
+            T: FromSystem<FROM, Source = Self>,
+        {
+            #[inline]
+            fn into_system(self, rounding: Rounding) -> Result<T, OverflowError> {
+                T::from_system(self, rounding)
+            }
+        }
+    };
+}
+
+impl_from_system!(Frames);
+impl_from_system!(Samples);
+impl_from_system!(Bytes);
+
+/// Error returned when converting a span between two systems that differ in
+/// more than the conversion allows.
+#[derive(thiserror::Error, Debug)]
+pub enum CrossSystemError {
+    #[error("Systems are incompatible for this conversion")]
+    Incompatible,
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+impl<const A: System> Frames<A> {
+    /// Convert these frames into system `B`, which may only differ from `A` in
+    /// its channel layout (e.g. when upmixing mono audio to stereo).
+    ///
+    /// Frames (and so, durations) map 1:1 between such systems.
+    #[inline]
+    pub const fn remix<const B: System>(self) -> Result<Frames<B>, CrossSystemError> {
+        if !(A.same_rate(&B) && A.same_sample_type(&B)) {
+            return Err(CrossSystemError::Incompatible);
+        }
+
+        Ok(Frames::new(self.get()))
+    }
+}
+
+impl<const A: System> Samples<A> {
+    /// Convert these samples into system `B`, which may only differ from `A` in
+    /// its channel layout (e.g. when upmixing mono audio to stereo).
+    ///
+    /// The number of samples is scaled by the ratio between the systems'
+    /// channel counts, e.g. doubled when going from mono to stereo.
+    #[inline]
+    pub const fn remix<const B: System>(self) -> Result<Samples<B>, CrossSystemError> {
+        match self.into_frames().remix::<B>() {
+            Ok(frames) => match frames_to_samples(frames) {
+                Ok(samples) => Ok(samples),
+                Err(err) => Err(CrossSystemError::Overflow(err)),
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const A: System> Bytes<A> {
+    /// Convert these bytes into system `B`, which may only differ from `A` in
+    /// its channel layout (e.g. when upmixing mono audio to stereo).
+    ///
+    /// The number of bytes is scaled by the ratio between the systems' channel
+    /// counts, e.g. doubled when going from mono to stereo.
+    #[inline]
+    pub const fn remix<const B: System>(self) -> Result<Bytes<B>, CrossSystemError> {
+        match Frames::from_bytes(self).remix::<B>() {
+            Ok(frames) => match frames_to_bytes(frames) {
+                Ok(bytes) => Ok(bytes),
+                Err(err) => Err(CrossSystemError::Overflow(err)),
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const A: System> Bytes<A> {
+    /// Convert these bytes into system `B`, which may only differ from `A` in
+    /// its sample type (e.g. when converting `i16` samples to `f32`).
+    ///
+    /// The number of bytes is scaled by the ratio between the systems' sample
+    /// byte depths. See also [`System::bytes_for_frames`].
+    #[inline]
+    pub const fn rescale_sample_type<const B: System>(self) -> Result<Bytes<B>, CrossSystemError> {
+        if !(A.same_rate(&B) && A.same_layout(&B)) {
+            return Err(CrossSystemError::Incompatible);
+        }
+
+        match frames_to_bytes(Frames::<B>::new(Frames::from_bytes(self).get())) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) => Err(CrossSystemError::Overflow(err)),
+        }
+    }
+}
+
+impl System {
+    /// The number of bytes needed to represent the given number of frames
+    /// (from any system) in this system.
+    ///
+    /// Only the number of frames is taken into account, so the result spans
+    /// a different duration if the systems' sample rates differ.
+    #[inline]
+    pub const fn bytes_for_frames<const A: System>(
+        &self,
+        frames: Frames<A>,
+    ) -> Result<usize, OverflowError> {
+        match frames.get().checked_mul(self.frame_size().get() as usize) {
+            Some(bytes) => Ok(bytes),
+            None => Err(OverflowError(())),
+        }
+    }
+}
+
+/// Compare two positions (or spans) in different systems, returning which one
+/// is further along, and by how much.
+///
+/// The difference is computed exactly and only then truncated to whole
+/// nanoseconds.
+pub const fn drift_between<const A: System, const B: System>(
+    a: Frames<A>,
+    b: Frames<B>,
+) -> Result<(Ordering, Duration), OverflowError> {
+    let (rate_a, rate_b) = (
+        A.sample_rate.get().get() as u128,
+        B.sample_rate.get().get() as u128,
+    );
+
+    // a / rate_a - b / rate_b == (a * rate_b - b * rate_a) / (rate_a * rate_b)
+    let (a, b) = (a.get() as u128 * rate_b, b.get() as u128 * rate_a);
+    let (ordering, diff) = if a >= b {
+        (
+            if a == b {
+                Ordering::Equal
+            } else {
+                Ordering::Greater
+            },
+            a - b,
+        )
+    } else {
+        (Ordering::Less, b - a)
+    };
+
+    let nanos = match diff.checked_mul(NANOS_PER_SEC as u128) {
+        Some(n) => n / (rate_a * rate_b),
+        None => return Err(OverflowError(())),
+    };
+
+    match duration_from_nanos(nanos) {
+        Some(dur) => Ok((ordering, dur)),
+        None => Err(OverflowError(())),
+    }
+}
+
+/// Whether two positions (or spans) in different systems are at most
+/// `tolerance` apart.
+///
+/// See [`drift_between`].
+pub const fn drift_within<const A: System, const B: System>(
+    a: Frames<A>,
+    b: Frames<B>,
+    tolerance: Duration,
+) -> bool {
+    match drift_between(a, b) {
+        Ok((_, drift)) => drift.as_nanos() <= tolerance.as_nanos(),
+        Err(_) => false,
+    }
+}
+
+/// Error returned when reinterpreting a span in a system with an incompatible
+/// frame layout.
+#[derive(thiserror::Error, Debug)]
+#[error("Systems have incompatible frame layouts")]
+pub struct ReinterpretError(());
+
+impl<const A: System> Frames<A> {
+    /// Relabel these frames as belonging to system `B`, keeping the number of
+    /// frames unchanged.
+    ///
+    /// Note that this changes the duration of the span if the systems' sample
+    /// rates differ. See [`convert_to`](Frames::convert_to) for a conversion
+    /// that preserves the duration instead.
+    #[inline]
+    pub const fn reinterpret<const B: System>(self) -> Frames<B> {
+        Frames::new(self.get())
+    }
+}
+
+impl<const A: System> Samples<A> {
+    /// Relabel these samples as belonging to system `B`, keeping the number of
+    /// samples unchanged.
+    ///
+    /// Fails if the systems have a different number of channels. Note that this
+    /// changes the duration of the span if the systems' sample rates differ.
+    #[inline]
+    pub const fn reinterpret<const B: System>(self) -> Result<Samples<B>, ReinterpretError> {
+        if A.channel_layout.channels().get() != B.channel_layout.channels().get() {
+            return Err(ReinterpretError(()));
+        }
+
+        Ok(Samples::new(self.get()).unwrap())
+    }
+}
+
+impl<const A: System> Bytes<A> {
+    /// Relabel these bytes as belonging to system `B`, keeping the number of
+    /// bytes unchanged.
+    ///
+    /// Fails if the systems have different [frame sizes](System::frame_size).
+    /// Note that this changes the duration of the span if the systems' sample
+    /// rates differ.
+    #[inline]
+    pub const fn reinterpret<const B: System>(self) -> Result<Bytes<B>, ReinterpretError> {
+        if !A.same_frame_size(&B) {
+            return Err(ReinterpretError(()));
+        }
+
+        Ok(Bytes::new(self.get()).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    const DAT: System = system!(48_000, Stereo, f32);
+
+    #[test]
+    fn test_lossy_round_trip() -> Result<(), OverflowError> {
+        for n in (0..10 * 44_100).step_by(37) {
+            let frames = Frames::<AUDIO_CD>::new(n);
+            for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+                let round_trip = frames
+                    .convert_to::<DAT>(rounding)?
+                    .convert_to::<AUDIO_CD>(rounding)?;
+                assert!(round_trip.get().abs_diff(n) <= 1);
+            }
+        }
+
+        assert_eq!(
+            Frames::new(48_000),
+            Frames::<AUDIO_CD>::new(44_100).convert_to::<DAT>(Rounding::Down)?
+        );
+        assert_eq!(
+            Frames::new(1),
+            Frames::<AUDIO_CD>::new(1).convert_to::<DAT>(Rounding::Down)?
+        );
+        assert_eq!(
+            Frames::new(2),
+            Frames::<AUDIO_CD>::new(1).convert_to::<DAT>(Rounding::Up)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact() -> Result<(), OverflowError> {
+        const SYS: System = system!(24_000, Mono, i16);
+
+        let frames = Frames::<SYS>::new(12_345);
+        assert_eq!(
+            Frames::new(24_690),
+            frames.convert_to::<DAT>(Rounding::Down)?
+        );
+        assert_eq!(
+            Samples::new(49_380).unwrap(),
+            frames.into_samples().convert_to::<DAT>(Rounding::Down)?
+        );
+        assert_eq!(
+            Bytes::new(197_520).unwrap(),
+            frames.into_bytes().convert_to::<DAT>(Rounding::Down)?
+        );
+
+        // same sample rate
+        assert_eq!(
+            Bytes::new(200).unwrap(),
+            Bytes::<AUDIO_CD>::new(100)
+                .unwrap()
+                .convert_to::<{ system!(44_100, Mono, f64) }>(Rounding::Up)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rescale_sample_type() -> Result<(), CrossSystemError> {
+        const FLOAT_CD: System = system!(44_100, Stereo, f32);
+
+        let second = Bytes::<AUDIO_CD>::new(176_400).unwrap();
+        let rescaled = second.rescale_sample_type::<FLOAT_CD>()?;
+        assert_eq!(352_800, rescaled.get());
+        assert_eq!(second.into_duration(), rescaled.into_duration());
+        assert_eq!(second, rescaled.rescale_sample_type::<AUDIO_CD>()?);
+
+        assert_eq!(
+            Ok(352_800),
+            FLOAT_CD
+                .bytes_for_frames(Frames::<AUDIO_CD>::new(44_100))
+                .map_err(|_| ())
+        );
+
+        assert!(matches!(
+            second.rescale_sample_type::<DAT>(),
+            Err(CrossSystemError::Incompatible)
+        ));
+        assert!(matches!(
+            Bytes::<AUDIO_CD>::new(usize::MAX / 4 * 4)
+                .unwrap()
+                .rescale_sample_type::<FLOAT_CD>(),
+            Err(CrossSystemError::Overflow(_))
+        ));
+        assert!(FLOAT_CD
+            .bytes_for_frames(Frames::<AUDIO_CD>::new(usize::MAX))
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_system() -> Result<(), OverflowError> {
+        fn check<const A: System, const B: System>(n: usize) -> Result<(), OverflowError> {
+            let frames = Frames::<A>::new(n);
+            for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+                assert_eq!(
+                    frames.convert_to::<B>(rounding)?,
+                    Frames::<B>::from_system(frames, rounding)?
+                );
+                assert_eq!(
+                    frames.into_samples().convert_to::<B>(rounding)?,
+                    frames.into_samples().into_system(rounding)?
+                );
+                assert_eq!(
+                    frames.into_bytes().convert_to::<B>(rounding)?,
+                    Bytes::<B>::from_system(frames.into_bytes(), rounding)?
+                );
+            }
+            Ok(())
+        }
+
+        for n in [0, 1, 1_000, 44_101, 123_456_789] {
+            check::<AUDIO_CD, DAT>(n)?;
+            check::<DAT, { system!(96_000, Mono, i16) }>(n)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drift() -> Result<(), OverflowError> {
+        let audio = Frames::<DAT>::new(48_000 + 3);
+        let video = Frames::<AUDIO_CD>::new(44_100);
+
+        assert_eq!(
+            (Ordering::Greater, Duration::from_nanos(62_500)),
+            drift_between(audio, video)?
+        );
+        assert_eq!(
+            (Ordering::Less, Duration::from_nanos(62_500)),
+            drift_between(video, audio)?
+        );
+        assert_eq!(
+            (Ordering::Equal, Duration::ZERO),
+            drift_between(Frames::<DAT>::new(48_000), video)?
+        );
+
+        assert!(drift_within(audio, video, Duration::from_micros(100)));
+        assert!(drift_within(audio, video, Duration::from_nanos(62_500)));
+        assert!(!drift_within(audio, video, Duration::from_micros(50)));
+
+        // even the largest positions don't overflow
+        assert_eq!(
+            Duration::from_secs(u64::MAX),
+            drift_between(
+                Frames::<{ system!(1, Mono, u8) }>::new(usize::MAX),
+                Frames::<DAT>::new(0)
+            )?
+            .1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reinterpret() {
+        const CUSTOM: System = system!(48_000, Stereo, u16);
+        const MONO: System = system!(44_100, Mono, i32);
+
+        let bytes = Bytes::<AUDIO_CD>::new(4_000).unwrap();
+        assert_eq!(4_000, bytes.reinterpret::<CUSTOM>().unwrap().get());
+        // same frame size, different layout
+        assert_eq!(4_000, bytes.reinterpret::<MONO>().unwrap().get());
+        assert!(bytes.reinterpret::<DAT>().is_err());
+
+        let samples = bytes.into_samples();
+        assert_eq!(2_000, samples.reinterpret::<DAT>().unwrap().get());
+        assert!(samples.reinterpret::<MONO>().is_err());
+
+        // the duration changes along with the sample rate
+        let frames = Frames::<AUDIO_CD>::new(44_100);
+        assert_eq!(44_100, frames.reinterpret::<CUSTOM>().get());
+        assert_ne!(
+            frames.into_duration(),
+            frames.reinterpret::<CUSTOM>().into_duration()
+        );
+    }
+
+    #[test]
+    fn test_remix() -> Result<(), CrossSystemError> {
+        const MONO_CD: System = system!(44_100, Mono, i16);
+
+        let frames = Frames::<MONO_CD>::new(44_100);
+        assert_eq!(frames.get(), frames.remix::<AUDIO_CD>()?.get());
+        assert_eq!(
+            frames.into_duration(),
+            frames.remix::<AUDIO_CD>()?.into_duration()
+        );
+
+        let samples = frames.into_samples();
+        assert_eq!(88_200, samples.remix::<AUDIO_CD>()?.get());
+        assert_eq!(
+            samples.into_duration(),
+            samples.remix::<AUDIO_CD>()?.into_duration()
+        );
+
+        let bytes = Bytes::<AUDIO_CD>::new(176_400).unwrap();
+        assert_eq!(88_200, bytes.remix::<MONO_CD>()?.get());
+        assert_eq!(bytes, bytes.remix::<MONO_CD>()?.remix::<AUDIO_CD>()?);
+
+        // different rate or sample type
+        assert!(matches!(
+            bytes.remix::<{ system!(48_000, Mono, i16) }>(),
+            Err(CrossSystemError::Incompatible)
+        ));
+        assert!(matches!(
+            bytes.remix::<{ system!(44_100, Mono, u16) }>(),
+            Err(CrossSystemError::Incompatible)
+        ));
+
+        assert!(matches!(
+            Samples::<MONO_CD>::new(usize::MAX)
+                .unwrap()
+                .remix::<AUDIO_CD>(),
+            Err(CrossSystemError::Overflow(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(Frames::<AUDIO_CD>::new(usize::MAX)
+            .convert_to::<DAT>(Rounding::Down)
+            .is_err());
+        assert!(Frames::<AUDIO_CD>::new(usize::MAX / 2)
+            .convert_to::<{ system!(44_100, Stereo, f64) }>(Rounding::Down)
+            .and_then(Bytes::try_from)
+            .is_err());
+    }
+}
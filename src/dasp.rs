@@ -0,0 +1,96 @@
+//! Interop with the [`dasp`](https://docs.rs/dasp) family of crates.
+
+use core::num::NonZeroU8;
+
+use dasp_frame::Frame;
+
+use crate::{ChannelLayout, SampleRate, SampleType, System};
+
+impl ChannelLayout {
+    /// The layout with as many channels as the `dasp` frame type `F` (see
+    /// [`from_channels`](ChannelLayout::from_channels)), or `None` if `F` has
+    /// no channels or more than `u8::MAX`.
+    #[inline]
+    pub const fn of_frame<F: Frame>() -> Option<Self> {
+        if F::CHANNELS > u8::MAX as usize {
+            return None;
+        }
+
+        match NonZeroU8::new(F::CHANNELS as u8) {
+            Some(channels) => Some(Self::from_channels(channels)),
+            None => None,
+        }
+    }
+}
+
+impl SampleType {
+    /// The sample type of the `dasp` sample `S`.
+    ///
+    /// `dasp` samples that don't also implement [`audio_core::Sample`] (e.g.
+    /// its packed 24-bit types) are not supported, and fail to compile.
+    #[inline]
+    pub const fn of_dasp_sample<S: dasp_sample::Sample + audio_core::Sample + 'static>() -> Self {
+        Self::new::<S>()
+    }
+}
+
+impl System {
+    /// The system describing frames of the `dasp` frame type `F`, sampled at
+    /// `sample_rate`, or `None` if `F`'s number of channels is not supported
+    /// (see [`ChannelLayout::of_frame`]).
+    ///
+    /// ```
+    /// # use audio_time::{system, sample_rate, System};
+    /// #
+    /// assert_eq!(
+    ///     Some(system!(48_000, Stereo, f32)),
+    ///     System::of_dasp_frame::<[f32; 2]>(sample_rate!(48_000))
+    /// );
+    /// ```
+    #[inline]
+    pub const fn of_dasp_frame<F>(sample_rate: SampleRate) -> Option<Self>
+    where
+        F: Frame,
+        F::Sample: audio_core::Sample + 'static,
+    {
+        match ChannelLayout::of_frame::<F>() {
+            Some(channel_layout) => Some(Self {
+                sample_rate,
+                channel_layout,
+                sample_type: SampleType::of_dasp_sample::<F::Sample>(),
+            }),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sample_rate, system};
+
+    #[test]
+    fn test_of_dasp_frame() {
+        assert_eq!(
+            Some(system!(48_000, Stereo, f32)),
+            System::of_dasp_frame::<[f32; 2]>(sample_rate!(48_000))
+        );
+        assert_eq!(
+            Some(system!(8_000, Mono, i16)),
+            System::of_dasp_frame::<[i16; 1]>(sample_rate!(8_000))
+        );
+        assert_eq!(
+            Some(system!(48_000, 6, u8)),
+            System::of_dasp_frame::<[u8; 6]>(sample_rate!(48_000))
+        );
+        assert_eq!(
+            Some(ChannelLayout::from_channels(NonZeroU8::new(32).unwrap())),
+            ChannelLayout::of_frame::<[f32; 32]>()
+        );
+
+        assert_eq!(
+            SampleType::new::<i32>(),
+            SampleType::of_dasp_sample::<i32>()
+        );
+    }
+}
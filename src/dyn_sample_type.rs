@@ -0,0 +1,210 @@
+use core::{fmt, num::NonZeroU8};
+
+use crate::{Endianness, SampleKind, SampleType};
+
+/// A runtime description of a sample format, e.g. as parsed from a file
+/// header, that isn't necessarily backed by a Rust type.
+///
+/// Can be compared against const [`SampleType`]s using
+/// [`matches`](DynSampleType::matches), and converted into one using
+/// [`to_sample_type`](DynSampleType::to_sample_type) when a built-in sample
+/// type of the same format exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynSampleType {
+    kind: SampleKind,
+    byte_depth: NonZeroU8,
+    bit_depth: NonZeroU8,
+    endianness: Endianness,
+}
+
+impl DynSampleType {
+    /// A sample format of the given kind, stored in containers of `byte_depth`
+    /// bytes, of which `bit_depth` bits are meaningful.
+    ///
+    /// Returns `None` if either depth is zero, or if `bit_depth` is wider than
+    /// the container.
+    #[inline]
+    pub const fn new(kind: SampleKind, byte_depth: u8, bit_depth: u8) -> Option<Self> {
+        let (Some(byte_depth), Some(bit_depth)) =
+            (NonZeroU8::new(byte_depth), NonZeroU8::new(bit_depth))
+        else {
+            return None;
+        };
+        if bit_depth.get() as u16 > byte_depth.get() as u16 * 8 {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            byte_depth,
+            bit_depth,
+            endianness: Endianness::Native,
+        })
+    }
+
+    /// The description of a const sample type, or `None` if it has no
+    /// [kind](SampleType::kind).
+    #[inline]
+    pub const fn from_sample_type(sample_type: SampleType) -> Option<Self> {
+        match sample_type.kind() {
+            Some(kind) => Some(Self {
+                kind,
+                byte_depth: sample_type.byte_depth(),
+                bit_depth: sample_type.bit_depth(),
+                endianness: sample_type.endianness(),
+            }),
+            None => None,
+        }
+    }
+
+    /// This sample format, stored in the given byte order.
+    #[inline]
+    pub const fn with_endianness(self, endianness: Endianness) -> Self {
+        Self { endianness, ..self }
+    }
+
+    #[inline]
+    pub const fn kind(&self) -> SampleKind {
+        self.kind
+    }
+
+    /// See [`SampleType::byte_depth`].
+    #[inline]
+    pub const fn byte_depth(&self) -> NonZeroU8 {
+        self.byte_depth
+    }
+
+    /// See [`SampleType::bit_depth`].
+    #[inline]
+    pub const fn bit_depth(&self) -> NonZeroU8 {
+        self.bit_depth
+    }
+
+    #[inline]
+    pub const fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Whether `sample_type` has this format, i.e. the same kind, depths and
+    /// endianness.
+    #[inline]
+    pub const fn matches(&self, sample_type: &SampleType) -> bool {
+        match sample_type.kind() {
+            Some(kind) => {
+                kind as u8 == self.kind as u8
+                    && sample_type.byte_depth().get() == self.byte_depth.get()
+                    && sample_type.bit_depth().get() == self.bit_depth.get()
+                    && sample_type.endianness() as u8 == self.endianness as u8
+            }
+            None => false,
+        }
+    }
+
+    /// The built-in [`SampleType`] with this format, if there is one.
+    #[inline]
+    pub const fn to_sample_type(&self) -> Option<SampleType> {
+        let Some(sample_type) = SampleType::from_kind(self.kind, self.byte_depth.get()) else {
+            return None;
+        };
+
+        match sample_type.with_bit_depth(self.bit_depth) {
+            Some(sample_type) => Some(sample_type.with_endianness(self.endianness)),
+            None => None,
+        }
+    }
+}
+
+impl TryFrom<SampleType> for DynSampleType {
+    type Error = SampleType;
+
+    /// Fails for sample types without a [kind](SampleType::kind), returning
+    /// them back.
+    #[inline]
+    fn try_from(value: SampleType) -> Result<Self, Self::Error> {
+        Self::from_sample_type(value).ok_or(value)
+    }
+}
+
+impl TryFrom<DynSampleType> for SampleType {
+    type Error = DynSampleType;
+
+    /// Fails for formats without a built-in sample type (see
+    /// [`DynSampleType::to_sample_type`]), returning them back.
+    #[inline]
+    fn try_from(value: DynSampleType) -> Result<Self, Self::Error> {
+        value.to_sample_type().ok_or(value)
+    }
+}
+
+/// Formatted like [`SampleType`]'s `Display` implementation when a built-in
+/// sample type of this format exists, e.g. `i32/24`, or as the kind followed by
+/// the container's width otherwise, e.g. `int40`.
+impl fmt::Display for DynSampleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_sample_type() {
+            Some(sample_type) => fmt::Display::fmt(&sample_type, f),
+            None => write!(
+                f,
+                "{}{}",
+                self.kind.name(),
+                self.byte_depth.get() as u16 * 8
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        const S24_32: SampleType = SampleType::with_valid_bits::<i32>(24).unwrap();
+
+        for (dyn_type, sample_type) in [
+            (
+                DynSampleType::new(SampleKind::SignedInt, 2, 16).unwrap(),
+                SampleType::new::<i16>(),
+            ),
+            (
+                DynSampleType::new(SampleKind::SignedInt, 4, 24).unwrap(),
+                S24_32,
+            ),
+            (
+                DynSampleType::new(SampleKind::Float, 4, 32).unwrap(),
+                SampleType::new::<f32>(),
+            ),
+        ] {
+            assert!(dyn_type.matches(&sample_type), "{dyn_type}");
+            assert_eq!(Some(sample_type), dyn_type.to_sample_type());
+            assert_eq!(Ok(dyn_type), DynSampleType::try_from(sample_type));
+            assert_eq!(sample_type.to_string(), dyn_type.to_string());
+        }
+
+        let s16 = DynSampleType::new(SampleKind::SignedInt, 2, 16).unwrap();
+        assert_eq!(2, s16.byte_depth().get());
+        assert!(!s16.matches(&SampleType::new::<u16>()));
+        assert!(!s16.matches(&SampleType::new::<[u8; 2]>()));
+        assert!(!s16.matches(&SampleType::new_be::<i16>()));
+        assert!(s16
+            .with_endianness(Endianness::Big)
+            .matches(&SampleType::new_be::<i16>()));
+        assert!(!DynSampleType::new(SampleKind::SignedInt, 4, 32)
+            .unwrap()
+            .matches(&S24_32));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert!(DynSampleType::new(SampleKind::SignedInt, 0, 8).is_none());
+        assert!(DynSampleType::new(SampleKind::SignedInt, 2, 0).is_none());
+        assert!(DynSampleType::new(SampleKind::SignedInt, 2, 17).is_none());
+
+        let int40 = DynSampleType::new(SampleKind::SignedInt, 5, 40).unwrap();
+        assert_eq!(None, int40.to_sample_type());
+        assert_eq!(Err(int40), SampleType::try_from(int40));
+        assert_eq!("int40", int40.to_string());
+
+        assert!(DynSampleType::try_from(SampleType::new::<[u8; 3]>()).is_err());
+    }
+}
@@ -0,0 +1,790 @@
+//! A runtime counterpart to the const-generic API, for when the encoding
+//! system is only known at runtime (e.g. after parsing a file header).
+
+use core::{fmt, time::Duration};
+#[cfg(feature = "std")]
+use core::{num::NonZeroU32, str::FromStr};
+
+#[cfg(feature = "std")]
+use crate::Endianness;
+use crate::{
+    frames::Frames, presets, Bytes, ChannelLayout, OverflowError, SampleRate, SampleType, Samples,
+    System,
+};
+
+/// The runtime equivalent of [`System`].
+///
+/// Can be converted to and from a [`System`] at no cost, but unlike a
+/// `System`, spans measured in a `DynSystem` ([`DynFrames`], [`DynSamples`]
+/// and [`DynBytes`]) carry it as a value instead of a const generic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynSystem {
+    pub sample_rate: SampleRate,
+    pub channel_layout: ChannelLayout,
+    pub sample_type: SampleType,
+}
+
+impl DynSystem {
+    #[inline]
+    pub const fn from_system(system: System) -> Self {
+        Self {
+            sample_rate: system.sample_rate,
+            channel_layout: system.channel_layout,
+            sample_type: system.sample_type,
+        }
+    }
+
+    #[inline]
+    pub const fn to_system(self) -> System {
+        System {
+            sample_rate: self.sample_rate,
+            channel_layout: self.channel_layout,
+            sample_type: self.sample_type,
+        }
+    }
+
+    /// See [`System::frame_size`].
+    #[inline]
+    #[track_caller]
+    pub const fn frame_size(&self) -> usize {
+        self.to_system().frame_size().get() as usize
+    }
+
+    /// The number of channels in this system, widened to a `usize`.
+    #[inline]
+    const fn channels(&self) -> usize {
+        self.channel_layout.channels().get() as usize
+    }
+
+    /// Whether this system is equal to the compile-time system `SYS`.
+    #[inline]
+    pub const fn is<const SYS: System>(&self) -> bool {
+        let system = self.to_system();
+        system.same_rate(&SYS) && system.same_layout(&SYS) && system.same_sample_type(&SYS)
+    }
+}
+
+/// Sample format names, as used by ffmpeg (`-f`/`-sample_fmt`), along with
+/// their corresponding sample types, in native byte order. Multi-byte formats
+/// may be suffixed with `le` or `be` for an explicit
+/// [endianness](crate::Endianness), e.g. `s16le`.
+#[cfg(any(feature = "arbitrary", feature = "std"))]
+pub(crate) const SAMPLE_FORMATS: &[(&str, SampleType)] = &[
+    ("u8", SampleType::new::<u8>()),
+    ("s8", SampleType::new::<i8>()),
+    ("s16", SampleType::new::<i16>()),
+    ("u16", SampleType::new::<u16>()),
+    ("s24", SampleType::new::<crate::I24>()),
+    ("u24", SampleType::new::<crate::U24>()),
+    ("s32", SampleType::new::<i32>()),
+    ("u32", SampleType::new::<u32>()),
+    ("s64", SampleType::new::<i64>()),
+    ("f32", SampleType::new::<f32>()),
+    ("f64", SampleType::new::<f64>()),
+];
+
+/// Alternative names for some of the [`SAMPLE_FORMATS`], which don't take an
+/// endianness suffix.
+#[cfg(feature = "std")]
+const SAMPLE_FORMAT_ALIASES: &[(&str, SampleType)] = &[
+    ("flt", SampleType::new::<f32>()),
+    ("dbl", SampleType::new::<f64>()),
+];
+
+/// Look up a sample format by name (case-insensitively), see
+/// [`SAMPLE_FORMATS`].
+#[cfg(feature = "std")]
+fn parse_sample_format(name: &str) -> Option<SampleType> {
+    let find = |formats: &[(&str, SampleType)], name: &str| {
+        formats
+            .iter()
+            .find(|(format, _)| format.eq_ignore_ascii_case(name))
+            .map(|&(_, sample_type)| sample_type)
+    };
+
+    if let Some(sample_type) =
+        find(SAMPLE_FORMATS, name).or_else(|| find(SAMPLE_FORMAT_ALIASES, name))
+    {
+        return Some(sample_type);
+    }
+
+    let (name, suffix) = name.split_at_checked(name.len().checked_sub(2)?)?;
+    let endianness = if suffix.eq_ignore_ascii_case("le") {
+        Endianness::Little
+    } else if suffix.eq_ignore_ascii_case("be") {
+        Endianness::Big
+    } else {
+        return None;
+    };
+
+    find(SAMPLE_FORMATS, name)
+        .filter(|sample_type| sample_type.byte_depth().get() > 1)
+        .map(|sample_type| sample_type.with_endianness(endianness))
+}
+
+/// The name of `sample_type`'s format, with an endianness suffix if it is
+/// explicit (and meaningful), see [`SAMPLE_FORMATS`].
+#[cfg(feature = "std")]
+fn sample_format_name(sample_type: SampleType) -> Option<String> {
+    let native = sample_type.with_endianness(Endianness::Native);
+    let (format, _) = SAMPLE_FORMATS.iter().find(|(_, t)| *t == native)?;
+
+    let suffix = match sample_type.endianness() {
+        _ if sample_type.byte_depth().get() == 1 => "",
+        Endianness::Native => "",
+        Endianness::Little => "le",
+        Endianness::Big => "be",
+    };
+    Some(format!("{format}{suffix}"))
+}
+
+/// Error returned when parsing a [`DynSystem`] from a format string.
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ParseSystemError {
+    #[error("Expected a format string of the form `<format>:<rate>:<channels>`")]
+    InvalidSyntax,
+    #[error("Unknown sample format `{0}`")]
+    UnknownFormat(String),
+    #[error("Invalid sample rate `{0}`")]
+    InvalidRate(String),
+    #[error("Sample rate must be non-zero")]
+    ZeroRate,
+    #[error("Unsupported number of channels `{0}`")]
+    UnsupportedChannels(String),
+}
+
+/// Parses an ffmpeg-style format string of the form
+/// `<format>:<rate>:<channels>`, e.g. `s16le:44100:2` or `f32:48000:1`.
+///
+/// Formats with an `le` or `be` suffix have an explicit
+/// [endianness](crate::Endianness), while ones without it (e.g. `s16`) are in
+/// native byte order.
+///
+/// The channels may also be given as a layout name (e.g. `5.1` or
+/// `ambisonics1`, see [`ChannelLayout`]'s `FromStr` implementation), while a
+/// number of channels is interpreted using
+/// [`from_channels`](ChannelLayout::from_channels).
+///
+/// ```
+/// # use audio_time::{DynSystem, Endianness, AUDIO_CD};
+/// #
+/// let system: DynSystem = "s16:44100:2".parse().unwrap();
+/// assert!(system.is::<AUDIO_CD>());
+/// assert_eq!(Some("AUDIO_CD"), system.preset_name());
+///
+/// let system: DynSystem = "s16be:44100:2".parse().unwrap();
+/// assert_eq!(Endianness::Big, system.sample_type.endianness());
+/// ```
+#[cfg(feature = "std")]
+impl FromStr for DynSystem {
+    type Err = ParseSystemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(format), Some(rate), Some(channels), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseSystemError::InvalidSyntax);
+        };
+
+        let sample_type = parse_sample_format(format)
+            .ok_or_else(|| ParseSystemError::UnknownFormat(format.to_owned()))?;
+        let sample_rate = rate
+            .parse::<u32>()
+            .map_err(|_| ParseSystemError::InvalidRate(rate.to_owned()))?;
+        let sample_rate = NonZeroU32::new(sample_rate).ok_or(ParseSystemError::ZeroRate)?;
+        let channel_layout = ChannelLayout::parse(channels)
+            .ok_or_else(|| ParseSystemError::UnsupportedChannels(channels.to_owned()))?;
+
+        Ok(Self {
+            sample_rate: SampleRate::new(sample_rate),
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+impl DynSystem {
+    /// The inverse of [`DynSystem::from_str`], using the canonical name for the
+    /// sample format, suffixed by its explicit endianness if any, e.g.
+    /// `s16:44100:2` or `s16le:44100:2`.
+    ///
+    /// The layout is given as its number of channels if that is parsed back
+    /// into the same layout, and by name otherwise (e.g. `f32:48000:5.1`).
+    ///
+    /// Returns `None` if the sample type has no ffmpeg-style name (e.g. if its
+    /// [bit depth](SampleType::with_bit_depth) was narrowed).
+    #[cfg(feature = "std")]
+    pub fn to_format_string(&self) -> Option<String> {
+        let format = sample_format_name(self.sample_type)?;
+
+        let channels = self.channel_layout.channels();
+        Some(
+            if self.channel_layout == ChannelLayout::from_channels(channels) {
+                format!("{format}:{}:{channels}", self.sample_rate)
+            } else {
+                format!("{format}:{}:{}", self.sample_rate, self.channel_layout)
+            },
+        )
+    }
+
+    /// The name of the [preset](crate::presets) equal to this system, if any.
+    pub fn preset_name(&self) -> Option<&'static str> {
+        presets::ALL
+            .iter()
+            .find(|(_, system)| *self == DynSystem::from(*system))
+            .map(|&(name, _)| name)
+    }
+}
+
+/// Same as [`System`]'s `Display` implementation.
+impl fmt::Display for DynSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_system(), f)
+    }
+}
+
+impl From<System> for DynSystem {
+    #[inline]
+    fn from(value: System) -> Self {
+        Self::from_system(value)
+    }
+}
+
+impl From<DynSystem> for System {
+    #[inline]
+    fn from(value: DynSystem) -> Self {
+        value.to_system()
+    }
+}
+
+/// Error returned from operations on spans measured in [`DynSystem`]s.
+#[derive(thiserror::Error, Debug)]
+pub enum DynSystemError {
+    #[error("Spans are measured in different systems")]
+    Mismatch,
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+macro_rules! impl_dyn_span {
+    ($name:ident, $static:ident) => {
+        impl $name {
+            #[inline]
+            pub const fn get(&self) -> usize {
+                self.n
+            }
+
+            #[inline]
+            pub const fn system(&self) -> DynSystem {
+                self.system
+            }
+
+            /// Add two spans, failing if they are measured in different
+            /// systems, or on overflow.
+            #[inline]
+            pub fn checked_add(self, rhs: Self) -> Result<Self, DynSystemError> {
+                if self.system != rhs.system {
+                    return Err(DynSystemError::Mismatch);
+                }
+
+                let n = self.n.checked_add(rhs.n).ok_or(OverflowError(()))?;
+                Ok(Self { n, ..self })
+            }
+
+            /// Subtract `rhs` from this span, failing if they are measured in
+            /// different systems, or on underflow.
+            #[inline]
+            pub fn checked_sub(self, rhs: Self) -> Result<Self, DynSystemError> {
+                if self.system != rhs.system {
+                    return Err(DynSystemError::Mismatch);
+                }
+
+                let n = self.n.checked_sub(rhs.n).ok_or(OverflowError(()))?;
+                Ok(Self { n, ..self })
+            }
+
+            /// Convert into a span measured in the compile-time system `SYS`,
+            /// if it equals this span's system.
+            #[inline]
+            pub const fn to_static<const SYS: System>(
+                self,
+            ) -> Result<$static<SYS>, DynSystemError> {
+                if !self.system.is::<SYS>() {
+                    return Err(DynSystemError::Mismatch);
+                }
+
+                // divisibility was already checked against an equal system
+                Ok(new_static!($static, self.n))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.n, f)
+            }
+        }
+
+        impl<const SYS: System> From<$static<SYS>> for $name {
+            #[inline]
+            fn from(value: $static<SYS>) -> Self {
+                Self {
+                    n: value.get(),
+                    system: SYS.into(),
+                }
+            }
+        }
+
+        impl<const SYS: System> TryFrom<$name> for $static<SYS> {
+            type Error = DynSystemError;
+
+            #[inline]
+            fn try_from(value: $name) -> Result<Self, Self::Error> {
+                value.to_static()
+            }
+        }
+    };
+}
+
+macro_rules! new_static {
+    (Frames, $n:expr) => {
+        Frames::new($n)
+    };
+    ($static:ident, $n:expr) => {
+        $static::new($n).unwrap()
+    };
+}
+
+/// The runtime equivalent of [`Frames`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynFrames {
+    n: usize,
+    system: DynSystem,
+}
+
+impl_dyn_span!(DynFrames, Frames);
+
+impl DynFrames {
+    #[inline]
+    pub const fn new(n: usize, system: DynSystem) -> Self {
+        Self { n, system }
+    }
+
+    /// See [`Frames::into_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_duration(self) -> Duration {
+        match self.try_into_duration() {
+            Ok(dur) => dur,
+            Err(_) => {
+                panic!("Overflowed trying to convert frames to duration")
+            }
+        }
+    }
+
+    /// Fallible version of [`into_duration`](DynFrames::into_duration).
+    #[inline]
+    pub const fn try_into_duration(self) -> Result<Duration, OverflowError> {
+        let sample_rate = self.system.sample_rate.get().get() as u64;
+
+        match self.n.checked_mul(1_000) {
+            Some(n) => Ok(Duration::from_millis(n as u64 / sample_rate)),
+            None => Err(OverflowError(())),
+        }
+    }
+
+    /// See [`Frames::from_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn from_duration(dur: Duration, system: DynSystem) -> Self {
+        match Self::try_from_duration(dur, system) {
+            Ok(frames) => frames,
+            Err(_) => {
+                panic!("Overflowed trying to convert duration to frames")
+            }
+        }
+    }
+
+    /// Fallible version of [`from_duration`](DynFrames::from_duration).
+    #[inline]
+    pub const fn try_from_duration(
+        dur: Duration,
+        system: DynSystem,
+    ) -> Result<Self, OverflowError> {
+        let sample_rate = system.sample_rate.get().get() as u128;
+
+        match dur.as_millis().checked_mul(sample_rate) {
+            Some(n) if n / 1_000 <= usize::MAX as u128 => {
+                Ok(Self::new((n / 1_000) as usize, system))
+            }
+            _ => Err(OverflowError(())),
+        }
+    }
+
+    /// See [`Frames::into_samples`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_samples(self) -> DynSamples {
+        match self.try_into_samples() {
+            Ok(samples) => samples,
+            Err(_) => {
+                panic!("Overflowed trying to convert frames to samples")
+            }
+        }
+    }
+
+    /// Fallible version of [`into_samples`](DynFrames::into_samples).
+    #[inline]
+    pub const fn try_into_samples(self) -> Result<DynSamples, OverflowError> {
+        match self.n.checked_mul(self.system.channels()) {
+            Some(n) => Ok(DynSamples {
+                n,
+                system: self.system,
+            }),
+            None => Err(OverflowError(())),
+        }
+    }
+
+    /// See [`Frames::from_samples`].
+    #[inline]
+    pub const fn from_samples(samples: DynSamples) -> Self {
+        Self::new(samples.n / samples.system.channels(), samples.system)
+    }
+
+    /// See [`Frames::into_bytes`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_bytes(self) -> DynBytes {
+        match self.try_into_bytes() {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                panic!("Overflowed trying to convert frames to bytes")
+            }
+        }
+    }
+
+    /// Fallible version of [`into_bytes`](DynFrames::into_bytes).
+    #[inline]
+    pub const fn try_into_bytes(self) -> Result<DynBytes, OverflowError> {
+        match self.n.checked_mul(self.system.frame_size()) {
+            Some(n) => Ok(DynBytes {
+                n,
+                system: self.system,
+            }),
+            None => Err(OverflowError(())),
+        }
+    }
+
+    /// See [`Frames::from_bytes`].
+    #[inline]
+    pub const fn from_bytes(bytes: DynBytes) -> Self {
+        Self::new(bytes.n / bytes.system.frame_size(), bytes.system)
+    }
+}
+
+/// The runtime equivalent of [`Samples`].
+///
+/// The `usize` contained in this struct is invariantly held to be divisible
+/// (without remainder) by the number of channels in its system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynSamples {
+    n: usize,
+    system: DynSystem,
+}
+
+impl_dyn_span!(DynSamples, Samples);
+
+impl DynSamples {
+    /// Create a `DynSamples` if the given value is divisible by the number of
+    /// channels in `system`.
+    #[inline]
+    pub const fn new(n: usize, system: DynSystem) -> Option<Self> {
+        if n % system.channels() == 0 {
+            Some(Self { n, system })
+        } else {
+            None
+        }
+    }
+
+    /// See [`Samples::into_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_duration(self) -> Duration {
+        self.into_frames().into_duration()
+    }
+
+    /// See [`Samples::from_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn from_duration(dur: Duration, system: DynSystem) -> Self {
+        DynFrames::from_duration(dur, system).into_samples()
+    }
+
+    /// See [`Samples::into_frames`].
+    #[inline]
+    pub const fn into_frames(self) -> DynFrames {
+        DynFrames::from_samples(self)
+    }
+
+    /// See [`Samples::into_bytes`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_bytes(self) -> DynBytes {
+        self.into_frames().into_bytes()
+    }
+}
+
+/// The runtime equivalent of [`Bytes`].
+///
+/// The `usize` contained in this struct is invariantly held to be divisible
+/// (without remainder) by the frame size of its system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynBytes {
+    n: usize,
+    system: DynSystem,
+}
+
+impl_dyn_span!(DynBytes, Bytes);
+
+impl DynBytes {
+    /// Create a `DynBytes` if the given value is divisible by
+    /// [`system.frame_size()`](DynSystem::frame_size).
+    #[inline]
+    pub const fn new(n: usize, system: DynSystem) -> Option<Self> {
+        if n % system.frame_size() == 0 {
+            Some(Self { n, system })
+        } else {
+            None
+        }
+    }
+
+    /// See [`Bytes::into_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn into_duration(self) -> Duration {
+        self.into_frames().into_duration()
+    }
+
+    /// See [`Bytes::from_duration`].
+    #[inline]
+    #[track_caller]
+    pub const fn from_duration(dur: Duration, system: DynSystem) -> Self {
+        DynFrames::from_duration(dur, system).into_bytes()
+    }
+
+    /// Equivalent to `DynFrames::from_bytes(self)`.
+    #[inline]
+    pub const fn into_frames(self) -> DynFrames {
+        DynFrames::from_bytes(self)
+    }
+
+    /// See [`Bytes::into_samples`].
+    #[inline]
+    pub const fn into_samples(self) -> DynSamples {
+        self.into_frames().into_samples()
+    }
+}
+
+impl From<DynSamples> for DynFrames {
+    #[inline]
+    fn from(value: DynSamples) -> Self {
+        Self::from_samples(value)
+    }
+}
+
+impl From<DynBytes> for DynFrames {
+    #[inline]
+    fn from(value: DynBytes) -> Self {
+        Self::from_bytes(value)
+    }
+}
+
+impl TryFrom<DynFrames> for DynSamples {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: DynFrames) -> Result<Self, Self::Error> {
+        value.try_into_samples()
+    }
+}
+
+impl TryFrom<DynFrames> for DynBytes {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: DynFrames) -> Result<Self, Self::Error> {
+        value.try_into_bytes()
+    }
+}
+
+impl TryFrom<DynFrames> for Duration {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: DynFrames) -> Result<Self, Self::Error> {
+        value.try_into_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::{NonZeroU32, NonZeroU8};
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    /// Build a system from values read from a (pretend) WAV header, if it
+    /// describes a supported format.
+    fn from_header(rate: u32, channels: u8, float: bool, bits: u8) -> Option<DynSystem> {
+        Some(DynSystem {
+            sample_rate: SampleRate::new(NonZeroU32::new(rate)?),
+            channel_layout: ChannelLayout::from_channels(NonZeroU8::new(channels)?),
+            sample_type: match (float, bits) {
+                (false, 8) => SampleType::new::<u8>(),
+                (false, 16) => SampleType::new::<i16>(),
+                (false, 24) => SampleType::new::<crate::I24>(),
+                (false, 32) => SampleType::new::<i32>(),
+                (true, 32) => SampleType::new::<f32>(),
+                (true, 64) => SampleType::new::<f64>(),
+                _ => return None,
+            },
+        })
+    }
+
+    #[test]
+    fn test_parity() -> Result<(), DynSystemError> {
+        let system = from_header(44_100, 2, false, 16).unwrap();
+        assert!(from_header(44_100, 2, true, 16).is_none());
+        assert!(from_header(0, 2, false, 16).is_none());
+        assert_eq!(DynSystem::from(AUDIO_CD), system);
+        assert!(system.is::<AUDIO_CD>());
+        assert_eq!(AUDIO_CD.to_string(), system.to_string());
+
+        for dur in [0, 1, 999, 1_000, 3_600_000].map(Duration::from_millis) {
+            let frames = DynFrames::from_duration(dur, system);
+            let expected = Frames::<AUDIO_CD>::from_duration(dur);
+            assert_eq!(expected, frames.to_static()?);
+            assert_eq!(expected.into_duration(), frames.into_duration());
+            assert_eq!(expected.into_samples().get(), frames.into_samples().get());
+            assert_eq!(expected.into_bytes().get(), frames.into_bytes().get());
+            assert_eq!(
+                Bytes::<AUDIO_CD>::from_duration(dur),
+                DynBytes::from_duration(dur, system).try_into()?
+            );
+        }
+
+        assert!(DynBytes::new(3, system).is_none());
+        assert!(DynSamples::new(3, system).is_none());
+        assert!(DynFrames::new(usize::MAX, system).try_into_bytes().is_err());
+        assert!(DynFrames::try_from_duration(Duration::MAX, system).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let cd = DynFrames::from(Frames::<AUDIO_CD>::new(100));
+        let other = DynFrames::new(100, from_header(48_000, 2, true, 32).unwrap());
+
+        assert_eq!(200, cd.checked_add(cd).unwrap().get());
+        assert!(matches!(
+            cd.checked_add(other),
+            Err(DynSystemError::Mismatch)
+        ));
+        assert!(matches!(
+            cd.checked_sub(cd.checked_add(cd).unwrap()),
+            Err(DynSystemError::Overflow(_))
+        ));
+        assert!(matches!(
+            other.to_static::<AUDIO_CD>(),
+            Err(DynSystemError::Mismatch)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_format_string() {
+        for (s, canonical) in [
+            ("s16le:44100:2", "s16le:44100:2"),
+            ("S16BE:44100:2", "s16be:44100:2"),
+            ("s16:44100:2", "s16:44100:2"),
+            ("f32:48000:1", "f32:48000:1"),
+            ("f32be:48000:1", "f32be:48000:1"),
+            ("U8:8000:1", "u8:8000:1"),
+            ("dbl:96000:2", "f64:96000:2"),
+            ("s32:48000:6", "s32:48000:6"),
+            ("s24le:48000:2", "s24le:48000:2"),
+            ("s24:96000:2", "s24:96000:2"),
+            ("u24be:48000:1", "u24be:48000:1"),
+            ("f32:48000:5.1", "f32:48000:5.1"),
+            ("f32:48000:6ch", "f32:48000:6"),
+            ("f32:48000:Ambisonics1", "f32:48000:ambisonics1"),
+        ] {
+            let system: DynSystem = s.parse().unwrap();
+            assert_eq!(Some(canonical), system.to_format_string().as_deref());
+            assert_eq!(Ok(system), canonical.parse());
+        }
+
+        let format_string = |sample_type| {
+            DynSystem::from(AUDIO_CD.with_sample_type(sample_type)).to_format_string()
+        };
+        assert_eq!(
+            Some("s16le:44100:2"),
+            format_string(SampleType::new_le::<i16>()).as_deref()
+        );
+        assert_eq!(
+            Some("s16be:44100:2"),
+            format_string(SampleType::new_be::<i16>()).as_deref()
+        );
+        assert_eq!(
+            Some("u8:44100:2"),
+            format_string(SampleType::new_be::<u8>()).as_deref()
+        );
+        assert_eq!(
+            None,
+            format_string(SampleType::with_valid_bits::<i32>(24).unwrap())
+        );
+
+        assert_eq!(
+            Some("STUDIO_48K_F32"),
+            "flt:48000:2".parse::<DynSystem>().unwrap().preset_name()
+        );
+        assert_eq!(
+            None,
+            "flt:48000:1".parse::<DynSystem>().unwrap().preset_name()
+        );
+
+        for (s, err) in [
+            ("s16le:44100", ParseSystemError::InvalidSyntax),
+            ("s16le:44100:2:1", ParseSystemError::InvalidSyntax),
+            ("s20:44100:2", ParseSystemError::UnknownFormat("s20".into())),
+            (
+                "u8le:8000:1",
+                ParseSystemError::UnknownFormat("u8le".into()),
+            ),
+            (
+                "fltle:8000:1",
+                ParseSystemError::UnknownFormat("fltle".into()),
+            ),
+            ("le:8000:1", ParseSystemError::UnknownFormat("le".into())),
+            (
+                "s16me:8000:1",
+                ParseSystemError::UnknownFormat("s16me".into()),
+            ),
+            ("s16:44.1k:2", ParseSystemError::InvalidRate("44.1k".into())),
+            ("s16:0:2", ParseSystemError::ZeroRate),
+            (
+                "s16:44100:0",
+                ParseSystemError::UnsupportedChannels("0".into()),
+            ),
+            (
+                "s16:44100:256",
+                ParseSystemError::UnsupportedChannels("256".into()),
+            ),
+        ] {
+            assert_eq!(Err(err), s.parse::<DynSystem>());
+        }
+    }
+}
@@ -0,0 +1,432 @@
+//! A C interface to the conversion math, for sharing a single implementation
+//! with C and C++ code (see `include/audio_time.h`).
+//!
+//! Systems are described at runtime by an [`AudioTimeSystem`], and all spans
+//! are passed as `uint64_t`s. Every function returns an [`AudioTimeStatus`],
+//! and only writes its result through `out` on success. None of them panic.
+//!
+//! Conversions into frames (and bytes or samples) truncate any partial frame,
+//! while conversions into nanoseconds truncate any partial nanosecond, the same
+//! as [`Frames::from_nanos_u64`](crate::Frames::from_nanos_u64) and
+//! [`Frames::to_nanos_u64`](crate::Frames::to_nanos_u64).
+
+use core::time::Duration;
+
+use crate::{SampleRate, System};
+
+/// The parts of a [`System`] that the conversions depend on.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(C)]
+pub struct AudioTimeSystem {
+    /// Frames per second.
+    pub sample_rate: u32,
+    /// Samples per frame.
+    pub channels: u8,
+    /// Bytes per sample.
+    pub byte_depth: u8,
+}
+
+impl From<System> for AudioTimeSystem {
+    #[inline]
+    fn from(value: System) -> Self {
+        Self {
+            sample_rate: value.sample_rate.get().get(),
+            channels: value.channel_layout.channels().get(),
+            byte_depth: value.sample_type.byte_depth().get(),
+        }
+    }
+}
+
+/// The result of a conversion.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(C)]
+pub enum AudioTimeStatus {
+    /// The conversion succeeded, and its result was written to `out`.
+    Ok = 0,
+    /// `out` is null.
+    NullPointer = 1,
+    /// The system's sample rate, number of channels or byte depth is zero.
+    InvalidSystem = 2,
+    /// The result does not fit in a `uint64_t`.
+    Overflow = 3,
+    /// The number of samples or bytes is not a whole number of frames.
+    Misaligned = 4,
+}
+
+/// An [`AudioTimeSystem`] with all of its fields checked to be non-zero.
+struct Valid {
+    rate: SampleRate,
+    channels: u64,
+    frame_size: u64,
+}
+
+impl AudioTimeSystem {
+    #[inline]
+    fn validate(self) -> Result<Valid, AudioTimeStatus> {
+        let rate = SampleRate::from_hz(self.sample_rate);
+        let (Some(rate), 1.., 1..) = (rate, self.channels, self.byte_depth) else {
+            return Err(AudioTimeStatus::InvalidSystem);
+        };
+
+        Ok(Valid {
+            rate,
+            channels: self.channels as u64,
+            frame_size: self.channels as u64 * self.byte_depth as u64,
+        })
+    }
+}
+
+impl Valid {
+    #[inline]
+    fn frames_to_nanos(&self, frames: u64) -> Result<u64, AudioTimeStatus> {
+        let nanos = self.rate.duration_of(frames).as_nanos();
+        u64::try_from(nanos).map_err(|_| AudioTimeStatus::Overflow)
+    }
+
+    #[inline]
+    fn nanos_to_frames(&self, nanos: u64) -> Result<u64, AudioTimeStatus> {
+        let frames = self.rate.periods_in(Duration::from_nanos(nanos));
+        u64::try_from(frames).map_err(|_| AudioTimeStatus::Overflow)
+    }
+}
+
+/// The number of samples or bytes in `frames`.
+#[inline]
+fn from_frames(frames: u64, per_frame: u64) -> Result<u64, AudioTimeStatus> {
+    frames
+        .checked_mul(per_frame)
+        .ok_or(AudioTimeStatus::Overflow)
+}
+
+/// The number of frames in `n` samples or bytes.
+#[inline]
+fn into_frames(n: u64, per_frame: u64) -> Result<u64, AudioTimeStatus> {
+    match n % per_frame {
+        0 => Ok(n / per_frame),
+        _ => Err(AudioTimeStatus::Misaligned),
+    }
+}
+
+/// Validates `sys`, and writes the result of `convert` to `out`.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[inline]
+unsafe fn convert(
+    sys: AudioTimeSystem,
+    out: *mut u64,
+    convert: impl FnOnce(Valid) -> Result<u64, AudioTimeStatus>,
+) -> AudioTimeStatus {
+    if out.is_null() {
+        return AudioTimeStatus::NullPointer;
+    }
+
+    match sys.validate().and_then(convert) {
+        Ok(result) => {
+            out.write(result);
+            AudioTimeStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// The duration of `frames`, in nanoseconds.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_frames_to_nanos(
+    sys: AudioTimeSystem,
+    frames: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| sys.frames_to_nanos(frames))
+}
+
+/// The number of whole frames in `nanos` nanoseconds.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_nanos_to_frames(
+    sys: AudioTimeSystem,
+    nanos: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| sys.nanos_to_frames(nanos))
+}
+
+/// The number of bytes in `frames`.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_frames_to_bytes(
+    sys: AudioTimeSystem,
+    frames: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| from_frames(frames, sys.frame_size))
+}
+
+/// The number of frames in `bytes`, which must be a whole number of frames.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_bytes_to_frames(
+    sys: AudioTimeSystem,
+    bytes: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| into_frames(bytes, sys.frame_size))
+}
+
+/// The number of samples in `frames`.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_frames_to_samples(
+    sys: AudioTimeSystem,
+    frames: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| from_frames(frames, sys.channels))
+}
+
+/// The number of frames in `samples`, which must be a whole number of frames.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_samples_to_frames(
+    sys: AudioTimeSystem,
+    samples: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| into_frames(samples, sys.channels))
+}
+
+/// The duration of `bytes`, which must be a whole number of frames, in
+/// nanoseconds.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_bytes_to_nanos(
+    sys: AudioTimeSystem,
+    bytes: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| {
+        sys.frames_to_nanos(into_frames(bytes, sys.frame_size)?)
+    })
+}
+
+/// The number of bytes in the whole frames in `nanos` nanoseconds.
+///
+/// # Safety
+///
+/// `out` must be null, or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn audio_time_nanos_to_bytes(
+    sys: AudioTimeSystem,
+    nanos: u64,
+    out: *mut u64,
+) -> AudioTimeStatus {
+    convert(sys, out, |sys| {
+        from_frames(sys.nanos_to_frames(nanos)?, sys.frame_size)
+    })
+}
+
+// the layout `include/audio_time.h` expects
+const _: () = {
+    use core::mem::{align_of, offset_of, size_of};
+
+    assert!(size_of::<AudioTimeSystem>() == 8);
+    assert!(align_of::<AudioTimeSystem>() == 4);
+    assert!(offset_of!(AudioTimeSystem, sample_rate) == 0);
+    assert!(offset_of!(AudioTimeSystem, channels) == 4);
+    assert!(offset_of!(AudioTimeSystem, byte_depth) == 5);
+    assert!(size_of::<AudioTimeStatus>() == size_of::<core::ffi::c_int>());
+};
+
+#[cfg(test)]
+mod tests {
+    use core::{ptr, time::Duration};
+
+    use super::*;
+    use crate::{presets::DVD_AUDIO_96K_24, system, Bytes, Frames, Samples, AUDIO_CD};
+
+    fn call(
+        f: unsafe extern "C" fn(AudioTimeSystem, u64, *mut u64) -> AudioTimeStatus,
+        sys: System,
+        n: u64,
+    ) -> Result<u64, AudioTimeStatus> {
+        let mut out = u64::MAX;
+        match unsafe { f(sys.into(), n, &mut out) } {
+            AudioTimeStatus::Ok => Ok(out),
+            status => {
+                assert_eq!(u64::MAX, out);
+                Err(status)
+            }
+        }
+    }
+
+    #[test]
+    fn test_typed_parity() {
+        for n in [0, 1, 441, 44_100, 1_234_567, 1 << 40] {
+            let frames = Frames::<AUDIO_CD>::new(n);
+            let n = n as u64;
+
+            assert_eq!(
+                frames.to_nanos_u64().ok(),
+                call(audio_time_frames_to_nanos, AUDIO_CD, n).ok()
+            );
+            assert_eq!(
+                Frames::<AUDIO_CD>::from_nanos_u64(n).unwrap().get() as u64,
+                call(audio_time_nanos_to_frames, AUDIO_CD, n).unwrap()
+            );
+            assert_eq!(
+                frames.into_bytes().get() as u64,
+                call(audio_time_frames_to_bytes, AUDIO_CD, n).unwrap()
+            );
+            assert_eq!(
+                frames.into_samples().get() as u64,
+                call(audio_time_frames_to_samples, AUDIO_CD, n).unwrap()
+            );
+            assert_eq!(
+                Frames::from(Bytes::<AUDIO_CD>::new(n as usize * 4).unwrap()).get() as u64,
+                call(audio_time_bytes_to_frames, AUDIO_CD, n * 4).unwrap()
+            );
+            assert_eq!(
+                Frames::from(Samples::<AUDIO_CD>::new(n as usize * 2).unwrap()).get() as u64,
+                call(audio_time_samples_to_frames, AUDIO_CD, n * 2).unwrap()
+            );
+        }
+
+        let dur = Duration::from_millis(1_500);
+        let bytes = Frames::<DVD_AUDIO_96K_24>::from_duration(dur).into_bytes();
+        assert_eq!(
+            bytes.get() as u64,
+            call(
+                audio_time_nanos_to_bytes,
+                DVD_AUDIO_96K_24,
+                dur.as_nanos() as u64
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            dur.as_nanos() as u64,
+            call(
+                audio_time_bytes_to_nanos,
+                DVD_AUDIO_96K_24,
+                bytes.get() as u64
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(AudioTimeStatus::Misaligned),
+            call(audio_time_bytes_to_frames, AUDIO_CD, 6)
+        );
+        assert_eq!(
+            Err(AudioTimeStatus::Misaligned),
+            call(audio_time_samples_to_frames, AUDIO_CD, 3)
+        );
+        assert_eq!(
+            Err(AudioTimeStatus::Misaligned),
+            call(audio_time_bytes_to_nanos, AUDIO_CD, 2)
+        );
+        assert_eq!(
+            Err(AudioTimeStatus::Overflow),
+            call(audio_time_frames_to_bytes, AUDIO_CD, u64::MAX / 2)
+        );
+        assert_eq!(
+            Err(AudioTimeStatus::Overflow),
+            call(
+                audio_time_frames_to_nanos,
+                system!(8_000, Mono, u8),
+                u64::MAX
+            )
+        );
+        assert_eq!(
+            Err(AudioTimeStatus::Overflow),
+            call(
+                audio_time_nanos_to_frames,
+                system!(4_000_000_000, Mono, u8),
+                u64::MAX
+            )
+        );
+
+        for sys in [
+            AudioTimeSystem {
+                sample_rate: 0,
+                ..AUDIO_CD.into()
+            },
+            AudioTimeSystem {
+                channels: 0,
+                ..AUDIO_CD.into()
+            },
+            AudioTimeSystem {
+                byte_depth: 0,
+                ..AUDIO_CD.into()
+            },
+        ] {
+            let mut out = 0;
+            assert_eq!(AudioTimeStatus::InvalidSystem, unsafe {
+                audio_time_frames_to_bytes(sys, 1, &mut out)
+            });
+        }
+
+        assert_eq!(AudioTimeStatus::NullPointer, unsafe {
+            audio_time_frames_to_bytes(AUDIO_CD.into(), 1, ptr::null_mut())
+        });
+    }
+
+    #[test]
+    fn test_header() {
+        let header = include_str!("../include/audio_time.h");
+
+        for decl in [
+            "uint32_t sample_rate;\n    uint8_t channels;\n    uint8_t byte_depth;",
+            "AUDIO_TIME_OK = 0,",
+            "AUDIO_TIME_NULL_POINTER = 1,",
+            "AUDIO_TIME_INVALID_SYSTEM = 2,",
+            "AUDIO_TIME_OVERFLOW = 3,",
+            "AUDIO_TIME_MISALIGNED = 4,",
+        ] {
+            assert!(header.contains(decl), "{decl}");
+        }
+
+        for name in [
+            "frames_to_nanos",
+            "nanos_to_frames",
+            "frames_to_bytes",
+            "bytes_to_frames",
+            "frames_to_samples",
+            "samples_to_frames",
+            "bytes_to_nanos",
+            "nanos_to_bytes",
+        ] {
+            let (from, _) = name.split_once("_to_").unwrap();
+            let decl = format!(
+                "AudioTimeStatus audio_time_{name}(AudioTimeSystem sys, uint64_t {from}, \
+                 uint64_t *out);"
+            );
+            assert!(header.contains(&decl), "{decl}");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Support for the [`fixed`] crate's fixed-point sample types, e.g. Q15
+//! ([`I1F15`](fixed::types::I1F15)) or Q31 ([`I1F31`](fixed::types::I1F31)).
+//!
+//! `fixed`'s types don't implement [`audio_core::Sample`], and the orphan rule
+//! prevents this crate from doing so, so their [`SampleType`]s are created
+//! using [`SampleType::of_fixed`] instead of [`SampleType::new`].
+
+use fixed::traits::Fixed;
+
+use crate::{SampleKind, SampleType};
+
+impl SampleType {
+    /// The sample type of the fixed-point type `F`.
+    ///
+    /// It has the same byte depth as `F`'s underlying integer, and is
+    /// [signed](SampleType::is_signed) if `F` is, but is distinct from sample
+    /// types of any other type, and [named](SampleType::name) differently from
+    /// the integer (e.g. `fixed_i16`). Like other sample types that aren't
+    /// built-in, it is [tagged](crate::System::to_tag) with a kind of 0.
+    ///
+    /// ```
+    /// # use audio_time::SampleType;
+    /// use fixed::types::I1F15;
+    ///
+    /// const Q15: SampleType = SampleType::of_fixed::<I1F15>();
+    /// assert_eq!(2, Q15.byte_depth().get());
+    /// assert_ne!(SampleType::new::<i16>(), Q15);
+    /// ```
+    #[inline]
+    pub const fn of_fixed<F: Fixed + 'static>() -> Self {
+        let kind = if F::IS_SIGNED {
+            SampleKind::SignedInt
+        } else {
+            SampleKind::UnsignedInt
+        };
+
+        Self::of_type::<F>().with_kind(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fixed::types::{I1F15, I1F31, U0F16};
+
+    use super::*;
+    use crate::{system, Bytes, Frames, Samples, System};
+
+    #[test]
+    fn test_fixed() {
+        const Q15: SampleType = SampleType::of_fixed::<I1F15>();
+        const Q31: SampleType = SampleType::of_fixed::<I1F31>();
+
+        assert_eq!(2, Q15.byte_depth().get());
+        assert_eq!(4, Q31.byte_depth().get());
+        assert_eq!(Some(SampleKind::SignedInt), Q15.kind());
+        assert_eq!(
+            Some(SampleKind::UnsignedInt),
+            SampleType::of_fixed::<U0F16>().kind()
+        );
+
+        assert_ne!(SampleType::new::<i16>(), Q15);
+        assert_ne!(SampleType::new::<i32>(), Q31);
+        assert_ne!(SampleType::of_fixed::<U0F16>(), Q15);
+        assert_eq!(SampleType::of_fixed::<I1F15>(), Q15);
+
+        assert_eq!("fixed_i16", Q15.name());
+        assert_eq!(
+            "fixed_i32be",
+            Q31.with_endianness(crate::Endianness::Big).to_string()
+        );
+        assert_eq!("fixed_u16", SampleType::of_fixed::<U0F16>().to_string());
+        assert_eq!("i16", SampleType::new::<i16>().name());
+        assert_eq!("i128", SampleType::new::<i128>().name());
+    }
+
+    #[test]
+    fn test_tag() {
+        const SYS: System =
+            system!(16_000, Mono, i16).with_sample_type(SampleType::of_fixed::<I1F15>());
+
+        assert_ne!(system!(16_000, Mono, i16).to_tag(), SYS.to_tag());
+        assert_eq!(0, SYS.to_tag() >> 48 & 0xf);
+        assert_eq!(
+            Err(crate::TagError::UnknownKind(0)),
+            System::from_tag(SYS.to_tag())
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const SYS: System =
+            system!(16_000, Mono, i16).with_sample_type(SampleType::of_fixed::<I1F15>());
+
+        let frames = Frames::<SYS>::from_duration(Duration::from_millis(250));
+        assert_eq!(4_000, frames.get());
+
+        let samples = frames.into_samples();
+        assert_eq!(4_000, samples.get());
+        let bytes = samples.into_bytes();
+        assert_eq!(8_000, bytes.get());
+
+        assert_eq!(samples, Samples::<SYS>::from(bytes));
+        assert_eq!(frames, Frames::<SYS>::from(bytes));
+        assert_eq!(Duration::from_millis(250), bytes.into_duration());
+        assert!(Bytes::<SYS>::new(8_001).is_none());
+    }
+}
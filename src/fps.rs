@@ -0,0 +1,226 @@
+use core::{fmt, num::NonZeroU32};
+
+use crate::{
+    rounding::{gcd, Rounding},
+    Frames, NotIntegralError, OverflowError, System,
+};
+
+/// A video frame rate, in frames per second, stored as an exact ratio so that
+/// NTSC rates (e.g. 30000/1001) are represented without error.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fps {
+    num: NonZeroU32,
+    den: NonZeroU32,
+}
+
+impl Fps {
+    pub const FILM: Self = Self::from_ratio(24, 1).unwrap();
+    pub const PAL: Self = Self::from_ratio(25, 1).unwrap();
+    pub const FPS_30: Self = Self::from_ratio(30, 1).unwrap();
+    /// 23.976 fps
+    pub const NTSC_FILM: Self = Self::from_ratio(24_000, 1_001).unwrap();
+    /// 29.97 fps
+    pub const NTSC: Self = Self::from_ratio(30_000, 1_001).unwrap();
+    /// 59.94 fps
+    pub const NTSC_DOUBLE: Self = Self::from_ratio(60_000, 1_001).unwrap();
+
+    /// Create an `Fps` of `num / den` frames per second, or `None` if either is
+    /// zero.
+    #[inline]
+    pub const fn from_ratio(num: u32, den: u32) -> Option<Self> {
+        if num == 0 || den == 0 {
+            return None;
+        }
+
+        let gcd = gcd(num as u128, den as u128) as u32;
+
+        match (NonZeroU32::new(num / gcd), NonZeroU32::new(den / gcd)) {
+            (Some(num), Some(den)) => Some(Self { num, den }),
+            _ => None,
+        }
+    }
+
+    /// The frame rate as a `(numerator, denominator)` pair in lowest terms.
+    #[inline]
+    pub const fn ratio(&self) -> (NonZeroU32, NonZeroU32) {
+        (self.num, self.den)
+    }
+}
+
+impl fmt::Display for Fps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den.get() == 1 {
+            write!(f, "{} fps", self.num)
+        } else {
+            let fps = self.num.get() as f64 / self.den.get() as f64;
+            write!(f, "{fps:.3} fps")
+        }
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The number of audio frames spanned by a single video frame, if it is a
+    /// whole number (e.g. 1920 at 48 kHz and 25 fps).
+    ///
+    /// See [`snap_to_video_frame`](Frames::snap_to_video_frame) for rates
+    /// where it isn't (e.g. 1601.6 at 48 kHz and 29.97 fps).
+    #[inline]
+    pub const fn per_video_frame(fps: Fps) -> Result<Self, NotIntegralError> {
+        let num = SYS.sample_rate.get().get() as u128 * fps.den.get() as u128;
+        let den = fps.num.get() as u128;
+
+        if num % den == 0 && num / den <= usize::MAX as u128 {
+            Ok(Self::new((num / den) as usize))
+        } else {
+            Err(NotIntegralError(()))
+        }
+    }
+
+    /// Snap this position to a video frame boundary, choosing the boundary in
+    /// the direction of `rounding`.
+    ///
+    /// Boundaries are computed directly from the video frame's index using
+    /// exact rational math, so no error accumulates over long streams. When a
+    /// boundary falls between two audio frames, it is placed at the nearest
+    /// one (so e.g. at 48 kHz and 29.97 fps, the boundaries are at 0, 1602,
+    /// 3203, 4805, 6406, 8008, ...).
+    pub const fn snap_to_video_frame(
+        self,
+        fps: Fps,
+        rounding: Rounding,
+    ) -> Result<Self, OverflowError> {
+        let position = self.get() as u128;
+
+        // the boundary of video frame `i` is at `round(i * rate / fps)`, so the
+        // last boundary at or before `position` belongs to the largest `i` for
+        // which `i * rate / fps < position + 1/2`
+        let index = match (2 * position + 1).checked_mul(fps.num.get() as u128) {
+            Some(n) => (n - 1) / (2 * SYS.sample_rate.get().get() as u128 * fps.den.get() as u128),
+            None => return Err(OverflowError(())),
+        };
+
+        let before = match video_frame_boundary::<SYS>(fps, index) {
+            Some(before) => before,
+            None => return Err(OverflowError(())),
+        };
+
+        let snapped = if before == position || matches!(rounding, Rounding::Down) {
+            before
+        } else {
+            let after = match video_frame_boundary::<SYS>(fps, index + 1) {
+                Some(after) => after,
+                None => return Err(OverflowError(())),
+            };
+
+            match rounding {
+                Rounding::Nearest if position - before < after - position => before,
+                _ => after,
+            }
+        };
+
+        if snapped <= usize::MAX as u128 {
+            Ok(Self::new(snapped as usize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+}
+
+/// The audio frame nearest to the start of video frame number `index`.
+const fn video_frame_boundary<const SYS: System>(fps: Fps, index: u128) -> Option<u128> {
+    let rate = SYS.sample_rate.get().get() as u128;
+
+    match index.checked_mul(rate * fps.den.get() as u128) {
+        Some(n) => Some(Rounding::Nearest.div(n, fps.num.get() as u128)),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    const SYS: System = system!(48_000, Stereo, i16);
+
+    #[test]
+    fn test_per_video_frame() {
+        assert_eq!(
+            1_920,
+            Frames::<SYS>::per_video_frame(Fps::PAL).unwrap().get()
+        );
+        assert_eq!(
+            2_000,
+            Frames::<SYS>::per_video_frame(Fps::FILM).unwrap().get()
+        );
+        assert!(Frames::<SYS>::per_video_frame(Fps::NTSC).is_err());
+        assert!(Fps::from_ratio(0, 1).is_none());
+        assert_eq!(Some(Fps::NTSC), Fps::from_ratio(60_000, 2_002));
+    }
+
+    #[test]
+    fn test_snap_exact() -> Result<(), OverflowError> {
+        let snap = |n, rounding| Frames::<SYS>::new(n).snap_to_video_frame(Fps::PAL, rounding);
+
+        assert_eq!(Frames::new(1_920), snap(1_920, Rounding::Down)?);
+        assert_eq!(Frames::new(1_920), snap(1_921, Rounding::Down)?);
+        assert_eq!(Frames::new(3_840), snap(1_921, Rounding::Up)?);
+        assert_eq!(Frames::new(1_920), snap(2_879, Rounding::Nearest)?);
+        assert_eq!(Frames::new(3_840), snap(2_880, Rounding::Nearest)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_ntsc() -> Result<(), OverflowError> {
+        let snap = |n, rounding| Frames::<SYS>::new(n).snap_to_video_frame(Fps::NTSC, rounding);
+
+        // exact boundaries at 0, 1601.6, 3203.2, 4804.8, ...
+        assert_eq!(Frames::new(1_602), snap(1_700, Rounding::Down)?);
+        assert_eq!(Frames::new(3_203), snap(1_700, Rounding::Up)?);
+        assert_eq!(Frames::new(3_203), snap(3_203, Rounding::Down)?);
+        assert_eq!(Frames::new(3_203), snap(3_203, Rounding::Up)?);
+        assert_eq!(Frames::new(3_203), snap(4_003, Rounding::Nearest)?);
+        assert_eq!(Frames::new(4_805), snap(4_004, Rounding::Nearest)?);
+
+        // after exactly five video frames, boundaries line up again
+        assert_eq!(Frames::new(8_008), snap(8_008, Rounding::Down)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_drift() -> Result<(), OverflowError> {
+        let (num, den) = Fps::NTSC.ratio();
+        let video_frames = 3_600 * num.get() as usize / den.get() as usize;
+
+        let mut boundary = Frames::<SYS>::new(0);
+        for index in 0..video_frames {
+            let exact = index as f64 * 1_601.6;
+            assert!((boundary.get() as f64 - exact).abs() <= 0.5);
+
+            // every position before the next boundary snaps back to this one
+            let next = Frames::<SYS>::new(boundary.get() + 1_602)
+                .snap_to_video_frame(Fps::NTSC, Rounding::Down)?;
+            assert_eq!(
+                boundary,
+                Frames::new(next.get() - 1).snap_to_video_frame(Fps::NTSC, Rounding::Down)?
+            );
+            assert_eq!(
+                next,
+                Frames::new(boundary.get() + 1).snap_to_video_frame(Fps::NTSC, Rounding::Up)?
+            );
+
+            boundary = next;
+        }
+
+        // an hour of NTSC video is 107 892 video frames and change
+        assert_eq!(
+            Frames::new((107_892. * 1_601.6f64).round() as usize),
+            boundary.snap_to_video_frame(Fps::NTSC, Rounding::Down)?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use core::{ops::Range, time::Duration};
+
+use crate::{Bytes, Frames, FramesDelta, OverflowError, System};
+
+mod sealed {
+    use crate::{Frames, System};
+
+    /// A half-open range of frames, `start..end`, e.g. a clip on a timeline or
+    /// a loop region.
+    ///
+    /// The range is invariantly held to have `start <= end`.
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct FrameRange<const SYS: System> {
+        start: Frames<SYS>,
+        end: Frames<SYS>,
+    }
+
+    impl<const SYS: System> FrameRange<SYS> {
+        /// Create a `FrameRange` if `start <= end`.
+        #[inline]
+        pub const fn new(start: Frames<SYS>, end: Frames<SYS>) -> Option<Self> {
+            if start.get() <= end.get() {
+                Some(Self { start, end })
+            } else {
+                None
+            }
+        }
+
+        /// The first frame in the range.
+        #[inline]
+        pub const fn start(&self) -> Frames<SYS> {
+            self.start
+        }
+
+        /// The frame right after the last one in the range.
+        #[inline]
+        pub const fn end(&self) -> Frames<SYS> {
+            self.end
+        }
+    }
+}
+
+pub use self::sealed::FrameRange;
+
+impl<const SYS: System> FrameRange<SYS> {
+    /// Create a `FrameRange` of `len` frames beginning at `start`, or `None` if
+    /// its end would overflow.
+    #[inline]
+    pub const fn from_start_len(start: Frames<SYS>, len: Frames<SYS>) -> Option<Self> {
+        match start.get().checked_add(len.get()) {
+            Some(end) => Self::new(start, Frames::new(end)),
+            None => None,
+        }
+    }
+
+    /// The number of frames in the range.
+    #[inline]
+    pub const fn len(&self) -> Frames<SYS> {
+        Frames::new(self.end().get() - self.start().get())
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.start().get() == self.end().get()
+    }
+
+    /// Whether `frame` lies within `start..end`.
+    #[inline]
+    pub const fn contains(&self, frame: Frames<SYS>) -> bool {
+        self.start().get() <= frame.get() && frame.get() < self.end().get()
+    }
+
+    /// The frames contained in both ranges, or `None` if the ranges do not
+    /// overlap.
+    ///
+    /// Since ranges are half-open, two ranges that merely touch (one's `end`
+    /// equals the other's `start`) do not overlap.
+    #[inline]
+    pub const fn intersect(&self, other: &Self) -> Option<Self> {
+        let start = max(self.start(), other.start());
+        let end = min(self.end(), other.end());
+
+        if start.get() < end.get() {
+            Self::new(start, end)
+        } else {
+            None
+        }
+    }
+
+    /// A single range covering both ranges, or `None` if there is a gap between
+    /// them.
+    ///
+    /// Ranges that touch or overlap are considered contiguous.
+    #[inline]
+    pub const fn union_if_contiguous(&self, other: &Self) -> Option<Self> {
+        if self.start().get() > other.end().get() || other.start().get() > self.end().get() {
+            return None;
+        }
+
+        Self::new(
+            min(self.start(), other.start()),
+            max(self.end(), other.end()),
+        )
+    }
+
+    /// Move both ends of the range by `delta`, or `None` if either end would be
+    /// negative or overflow.
+    #[inline]
+    pub const fn shift_by(&self, delta: FramesDelta<SYS>) -> Option<Self> {
+        match (
+            self.start().checked_add_delta(delta),
+            self.end().checked_add_delta(delta),
+        ) {
+            (Some(start), Some(end)) => Self::new(start, end),
+            _ => None,
+        }
+    }
+
+    /// Equivalent to `Range::<Duration>::try_from(range).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn into_duration_range(self) -> Range<Duration> {
+        self.start().into_duration()..self.end().into_duration()
+    }
+
+    /// Equivalent to `Range::<Bytes>::try_from(range).unwrap()`.
+    #[inline]
+    #[track_caller]
+    pub const fn into_bytes_range(self) -> Range<Bytes<SYS>> {
+        self.start().into_bytes()..self.end().into_bytes()
+    }
+}
+
+const fn min<const SYS: System>(a: Frames<SYS>, b: Frames<SYS>) -> Frames<SYS> {
+    if a.get() <= b.get() {
+        a
+    } else {
+        b
+    }
+}
+
+const fn max<const SYS: System>(a: Frames<SYS>, b: Frames<SYS>) -> Frames<SYS> {
+    if a.get() >= b.get() {
+        a
+    } else {
+        b
+    }
+}
+
+impl<const SYS: System> From<Range<Frames<SYS>>> for FrameRange<SYS> {
+    /// # Panics
+    ///
+    /// Panics if `range.start > range.end`.
+    #[inline]
+    #[track_caller]
+    fn from(value: Range<Frames<SYS>>) -> Self {
+        Self::new(value.start, value.end).expect("Range start is greater than its end")
+    }
+}
+
+impl<const SYS: System> From<FrameRange<SYS>> for Range<Frames<SYS>> {
+    #[inline]
+    fn from(value: FrameRange<SYS>) -> Self {
+        value.start()..value.end()
+    }
+}
+
+impl<const SYS: System> TryFrom<FrameRange<SYS>> for Range<Duration> {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: FrameRange<SYS>) -> Result<Self, Self::Error> {
+        Ok(value.start().try_into()?..value.end().try_into()?)
+    }
+}
+
+impl<const SYS: System> TryFrom<FrameRange<SYS>> for Range<Bytes<SYS>> {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: FrameRange<SYS>) -> Result<Self, Self::Error> {
+        Ok(value.start().try_into()?..value.end().try_into()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    fn range(start: usize, end: usize) -> FrameRange<AUDIO_CD> {
+        (Frames::new(start)..Frames::new(end)).into()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(FrameRange::<AUDIO_CD>::new(Frames::new(10), Frames::new(5)).is_none());
+
+        let empty = range(5, 5);
+        assert!(empty.is_empty());
+        assert!(!empty.contains(Frames::new(5)));
+
+        let r = range(5, 15);
+        assert_eq!(Frames::new(10), r.len());
+        assert!(r.contains(Frames::new(5)));
+        assert!(!r.contains(Frames::new(15)));
+        assert_eq!(
+            Some(r),
+            FrameRange::from_start_len(Frames::new(5), Frames::new(10))
+        );
+    }
+
+    #[test]
+    fn test_disjoint() {
+        let (a, b) = (range(0, 10), range(20, 30));
+        assert_eq!(None, a.intersect(&b));
+        assert_eq!(None, a.union_if_contiguous(&b));
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let (a, b) = (range(0, 20), range(10, 30));
+        assert_eq!(Some(range(10, 20)), a.intersect(&b));
+        assert_eq!(Some(range(10, 20)), b.intersect(&a));
+        assert_eq!(Some(range(0, 30)), a.union_if_contiguous(&b));
+
+        // containment
+        assert_eq!(Some(range(5, 10)), a.intersect(&range(5, 10)));
+        assert_eq!(Some(a), a.union_if_contiguous(&range(5, 10)));
+    }
+
+    #[test]
+    fn test_touching() {
+        let (a, b) = (range(0, 10), range(10, 20));
+        assert_eq!(None, a.intersect(&b));
+        assert_eq!(None, b.intersect(&a));
+        assert_eq!(Some(range(0, 20)), a.union_if_contiguous(&b));
+    }
+
+    #[test]
+    fn test_shift_by() {
+        let r = range(10, 20);
+        assert_eq!(Some(range(15, 25)), r.shift_by(FramesDelta::new(5)));
+        assert_eq!(Some(range(0, 10)), r.shift_by(FramesDelta::new(-10)));
+        assert_eq!(None, r.shift_by(FramesDelta::new(-11)));
+    }
+
+    #[test]
+    fn test_conversions() -> Result<(), OverflowError> {
+        let r = range(44_100, 88_200);
+        assert_eq!(
+            Duration::from_secs(1)..Duration::from_secs(2),
+            Range::<Duration>::try_from(r)?
+        );
+        assert_eq!(r.into_duration_range(), Range::<Duration>::try_from(r)?);
+
+        let bytes = r.into_bytes_range();
+        assert_eq!(176_400, bytes.start.get());
+        assert_eq!(352_800, bytes.end.get());
+
+        Ok(())
+    }
+}
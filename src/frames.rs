@@ -1,4 +1,4 @@
-use std::{
+use core::{
     ops::{Div, Mul},
     time::Duration,
 };
@@ -8,7 +8,7 @@ use crate::{
         bytes_to_frames, duration_to_frames, frames_to_bytes, frames_to_duration,
         frames_to_samples, samples_to_frames,
     },
-    impl_fmt, Bytes, Samples, System,
+    impl_fmt, Bytes, OverflowError, Samples, System,
 };
 
 mod sealed {
@@ -16,7 +16,6 @@ mod sealed {
 
     /// An audio time span, measured by the number of frames contained in it.
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct Frames<const SYS: System>(usize);
 
@@ -99,6 +98,45 @@ impl<const SYS: System> Frames<SYS> {
     pub const fn from_samples(samples: Samples<SYS>) -> Self {
         samples_to_frames(samples)
     }
+
+    /// Equivalent to
+    /// [`SYS.frames_for_latency(target)`](System::frames_for_latency).
+    #[inline]
+    pub const fn for_latency(target: Duration) -> Result<Self, OverflowError> {
+        match SYS.frames_for_latency(target) {
+            Ok(frames) => Ok(Self::new(frames)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Convert a number of nanoseconds (e.g. a GStreamer `ClockTime`) into
+    /// frames, truncating any partial frame.
+    #[inline]
+    pub const fn from_nanos_u64(nanos: u64) -> Result<Self, OverflowError> {
+        let frames = SYS.sample_rate.periods_in(Duration::from_nanos(nanos));
+
+        if frames <= usize::MAX as u128 {
+            Ok(Self::new(frames as usize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// Convert these frames into a number of nanoseconds (e.g. a GStreamer
+    /// `ClockTime`), truncating any partial nanosecond.
+    ///
+    /// Fails if the result does not fit in a `u64` (i.e. spans more than ~584
+    /// years).
+    #[inline]
+    pub const fn to_nanos_u64(&self) -> Result<u64, OverflowError> {
+        let nanos = SYS.sample_rate.duration_of(self.get() as u64).as_nanos();
+
+        if nanos <= u64::MAX as u128 {
+            Ok(nanos as u64)
+        } else {
+            Err(OverflowError(()))
+        }
+    }
 }
 
 impl<const SYS: System> From<usize> for Frames<SYS> {
@@ -0,0 +1,127 @@
+use core::{
+    ops::{Add, Neg, Sub},
+    time::Duration,
+};
+
+use crate::{impl_fmt, Frames, System};
+
+mod sealed {
+    use crate::System;
+
+    /// A signed difference between two positions in an audio stream, measured
+    /// in frames.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[repr(transparent)]
+    pub struct FramesDelta<const SYS: System>(isize);
+
+    impl<const SYS: System> FramesDelta<SYS> {
+        #[inline]
+        pub const fn new(n: isize) -> Self {
+            Self(n)
+        }
+
+        #[inline]
+        pub const fn get(&self) -> isize {
+            self.0
+        }
+    }
+}
+
+pub use self::sealed::FramesDelta;
+
+impl_fmt!(FramesDelta);
+
+impl<const SYS: System> FramesDelta<SYS> {
+    /// The (unsigned) number of frames spanned by this delta.
+    #[inline]
+    pub const fn abs(self) -> Frames<SYS> {
+        Frames::new(self.get().unsigned_abs())
+    }
+
+    #[inline]
+    pub const fn is_negative(self) -> bool {
+        self.get() < 0
+    }
+
+    /// The duration spanned by this delta, regardless of its sign.
+    ///
+    /// Equivalent to `delta.abs().into_duration()`.
+    #[inline]
+    #[track_caller]
+    pub const fn abs_duration(self) -> Duration {
+        self.abs().into_duration()
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The signed difference `self - earlier`, or `None` if it does not fit in
+    /// an `isize`.
+    #[inline]
+    pub const fn delta_since(self, earlier: Self) -> Option<FramesDelta<SYS>> {
+        let (diff, borrowed) = self.get().overflowing_sub(earlier.get());
+        let diff = diff as isize;
+
+        // the wrapped difference is correct as long as its sign agrees with the
+        // direction of the subtraction
+        if borrowed == (diff < 0) {
+            Some(FramesDelta::new(diff))
+        } else {
+            None
+        }
+    }
+
+    /// Move this position by `delta`, or `None` if the result would be negative
+    /// or overflow.
+    #[inline]
+    pub const fn checked_add_delta(self, delta: FramesDelta<SYS>) -> Option<Self> {
+        match self.get().checked_add_signed(delta.get()) {
+            Some(n) => Some(Self::new(n)),
+            None => None,
+        }
+    }
+}
+
+impl<const SYS: System> From<isize> for FramesDelta<SYS> {
+    #[inline]
+    fn from(value: isize) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const SYS: System> From<FramesDelta<SYS>> for isize {
+    #[inline]
+    fn from(value: FramesDelta<SYS>) -> Self {
+        value.get()
+    }
+}
+
+impl<const SYS: System> Neg for FramesDelta<SYS> {
+    type Output = Self;
+
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.get())
+    }
+}
+
+impl<const SYS: System> Add for FramesDelta<SYS> {
+    type Output = Self;
+
+    #[inline]
+    #[track_caller]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.get() + rhs.get())
+    }
+}
+
+impl<const SYS: System> Sub for FramesDelta<SYS> {
+    type Output = Self;
+
+    #[inline]
+    #[track_caller]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.get() - rhs.get())
+    }
+}
@@ -0,0 +1,66 @@
+//! Conversions between [`Frames`] and GStreamer's [`ClockTime`].
+
+use ::gstreamer::ClockTime;
+
+use crate::{Frames, OverflowError, System};
+
+/// Error returned when converting an `Option<ClockTime>` into [`Frames`].
+#[derive(thiserror::Error, Debug)]
+pub enum ClockTimeError {
+    #[error("Clock time is `ClockTime::NONE`")]
+    None,
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+impl<const SYS: System> TryFrom<ClockTime> for Frames<SYS> {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: ClockTime) -> Result<Self, Self::Error> {
+        Self::from_nanos_u64(value.nseconds())
+    }
+}
+
+impl<const SYS: System> TryFrom<Option<ClockTime>> for Frames<SYS> {
+    type Error = ClockTimeError;
+
+    #[inline]
+    fn try_from(value: Option<ClockTime>) -> Result<Self, Self::Error> {
+        Ok(value.ok_or(ClockTimeError::None)?.try_into()?)
+    }
+}
+
+impl<const SYS: System> TryFrom<Frames<SYS>> for ClockTime {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Frames<SYS>) -> Result<Self, Self::Error> {
+        match value.to_nanos_u64()? {
+            // `u64::MAX` is reserved for `ClockTime::NONE`
+            nanos if nanos <= ClockTime::MAX.nseconds() => Ok(ClockTime::from_nseconds(nanos)),
+            _ => Err(OverflowError(())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[test]
+    fn test_clock_time() -> Result<(), OverflowError> {
+        let pts = ClockTime::from_mseconds(1_500);
+        let frames = Frames::<AUDIO_CD>::try_from(pts)?;
+        assert_eq!(66_150, frames.get());
+        assert_eq!(pts, ClockTime::try_from(frames)?);
+
+        assert!(matches!(
+            Frames::<AUDIO_CD>::try_from(ClockTime::NONE),
+            Err(ClockTimeError::None)
+        ));
+
+        Ok(())
+    }
+}
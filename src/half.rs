@@ -0,0 +1,74 @@
+//! Support for [`half`]'s 16-bit floating point sample types.
+//!
+//! `half`'s types don't implement [`audio_core::Sample`], and the orphan rule
+//! prevents this crate from doing so, so their [`SampleType`]s are created
+//! using dedicated constructors instead of [`SampleType::new`].
+
+use crate::SampleType;
+
+impl SampleType {
+    /// The sample type of [`half::f16`] (IEEE 754 half precision) samples.
+    ///
+    /// ```
+    /// # use audio_time::{system, SampleType, System};
+    /// #
+    /// const SYS: System = system!(16_000, Mono, f32).with_sample_type(SampleType::f16());
+    /// assert_eq!(2, SYS.frame_size().get());
+    /// ```
+    #[inline]
+    pub const fn f16() -> Self {
+        Self::of_type::<half::f16>()
+    }
+
+    /// The sample type of [`half::bf16`] (bfloat16) samples.
+    #[inline]
+    pub const fn bf16() -> Self {
+        Self::of_type::<half::bf16>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{system, Bytes, Frames, SampleKind, System};
+
+    #[test]
+    fn test_half() {
+        const SYS: System = system!(16_000, Mono, f32).with_sample_type(SampleType::f16());
+
+        for sample_type in [SampleType::f16(), SampleType::bf16()] {
+            assert_eq!(2, sample_type.byte_depth().get());
+            assert_eq!(16, sample_type.bit_depth().get());
+            assert_eq!(Some(SampleKind::Float), sample_type.kind());
+            assert!(sample_type.is_float());
+            assert_ne!(SampleType::new::<i16>(), sample_type);
+            assert_ne!(SampleType::new::<u16>(), sample_type);
+        }
+        assert_ne!(SampleType::f16(), SampleType::bf16());
+        assert_eq!("f16", SampleType::f16().to_string());
+        assert_eq!("bf16", SampleType::bf16().to_string());
+
+        assert_eq!(2, SYS.frame_size().get());
+        assert_eq!(
+            32_000,
+            Frames::<SYS>::from_duration(Duration::from_secs(1))
+                .into_bytes()
+                .get()
+        );
+        assert!(Bytes::<SYS>::new(3).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            r#""f16""#,
+            serde_json::to_string(&SampleType::f16()).unwrap()
+        );
+        assert_eq!(SampleType::f16(), serde_json::from_str(r#""f16""#).unwrap());
+        // bf16 has the same kind and depth as f16, so it has no stable tag
+        assert!(serde_json::to_string(&SampleType::bf16()).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! Conversions between [`SampleType`]s and [`System`]s, and [`hound`]'s sample
+//! formats and WAV specs.
+
+use std::{io, time::Duration};
+
+use ::hound::{SampleFormat, WavReader, WavSpec};
+
+use crate::{
+    stream_format::{channel_layout_of, sample_rate_of},
+    DynSystem, Endianness, SampleRate, SampleType, StreamParamsError, System, I24,
+};
+
+/// Error returned when a `hound` WAV spec can't be described by a
+/// [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedSpecError {
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+    #[error("Unsupported sample format: {bits}-bit {format:?}")]
+    UnsupportedFormat { bits: u16, format: SampleFormat },
+}
+
+impl SampleType {
+    /// The sample type of a WAV stream with the given `hound` bits per sample
+    /// and sample format, or `None` if there is no matching sample type.
+    ///
+    /// 8-bit WAV samples are unsigned, so they map to `u8`. Although WAV
+    /// samples are little-endian, the returned sample types have
+    /// [native](crate::Endianness::Native) endianness, so they compare equal
+    /// to ones created using [`SampleType::new`].
+    #[inline]
+    pub const fn from_hound(bits: u16, fmt: SampleFormat) -> Option<Self> {
+        Some(match (bits, fmt) {
+            (8, SampleFormat::Int) => Self::new::<u8>(),
+            (16, SampleFormat::Int) => Self::new::<i16>(),
+            (24, SampleFormat::Int) => Self::new::<I24>(),
+            (32, SampleFormat::Int) => Self::new::<i32>(),
+            (32, SampleFormat::Float) => Self::new::<f32>(),
+            _ => return None,
+        })
+    }
+
+    /// The `hound` bits per sample and sample format for writing samples of
+    /// this type to a WAV file, or `None` if `hound` can't write them (e.g.
+    /// for 64-bit floats, or sample types with a narrowed
+    /// [bit depth](SampleType::bit_depth)).
+    ///
+    /// Both `u8` and `i8` are written as 8-bit WAV samples. Big-endian sample
+    /// types are not supported, since WAV samples are little-endian.
+    #[inline]
+    pub const fn to_hound(&self) -> Option<(u16, SampleFormat)> {
+        if self.is_big_endian() && self.byte_depth().get() > 1 {
+            return None;
+        }
+        if self.is::<i8>() && self.bit_depth().get() == 8 {
+            return Some((8, SampleFormat::Int));
+        }
+
+        let (bits, fmt) = if self.is_float() {
+            (32, SampleFormat::Float)
+        } else {
+            (self.bit_depth().get() as u16, SampleFormat::Int)
+        };
+
+        match Self::from_hound(bits, fmt) {
+            Some(sample_type)
+                if sample_type.const_eq(&self.with_endianness(Endianness::Native)) =>
+            {
+                Some((bits, fmt))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl System {
+    /// The `hound` spec for writing audio in this system to a WAV file, or
+    /// `None` if `hound` can't write its sample type (see
+    /// [`SampleType::to_hound`]).
+    #[inline]
+    pub const fn to_wav_spec(&self) -> Option<WavSpec> {
+        match self.sample_type.to_hound() {
+            Some((bits_per_sample, sample_format)) => Some(WavSpec {
+                channels: self.channel_layout.channels().get() as u16,
+                sample_rate: self.sample_rate.get().get(),
+                bits_per_sample,
+                sample_format,
+            }),
+            None => None,
+        }
+    }
+}
+
+impl DynSystem {
+    /// The system of a WAV file with the given `hound` spec.
+    ///
+    /// 24-bit samples map to the packed [`I24`], which is how `hound` writes
+    /// them.
+    pub fn from_wav_spec(spec: &WavSpec) -> Result<Self, UnsupportedSpecError> {
+        let sample_rate = sample_rate_of(spec.sample_rate)?;
+        let channel_layout = channel_layout_of(spec.channels.into())?;
+        let sample_type = SampleType::from_hound(spec.bits_per_sample, spec.sample_format).ok_or(
+            UnsupportedSpecError::UnsupportedFormat {
+                bits: spec.bits_per_sample,
+                format: spec.sample_format,
+            },
+        )?;
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+/// The duration of the audio in a WAV file, truncated to whole nanoseconds.
+///
+/// Computed exactly from the reader's [length](WavReader::len) (in samples)
+/// and spec, ignoring any trailing partial frame. Files declaring a zero sample
+/// rate, which can't be played, have a zero duration.
+pub fn duration_of_wav<R: io::Read>(reader: &WavReader<R>) -> Duration {
+    let spec = reader.spec();
+    let frames = (reader.len() / spec.channels.max(1) as u32) as u64;
+
+    SampleRate::from_hz(spec.sample_rate).map_or(Duration::ZERO, |rate| rate.duration_of(frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_round_trip() {
+        for (bits, fmt, sample_type) in [
+            (8, SampleFormat::Int, SampleType::new::<u8>()),
+            (16, SampleFormat::Int, SampleType::new::<i16>()),
+            (24, SampleFormat::Int, SampleType::new::<I24>()),
+            (32, SampleFormat::Int, SampleType::new::<i32>()),
+            (32, SampleFormat::Float, SampleType::new::<f32>()),
+        ] {
+            assert_eq!(Some(sample_type), SampleType::from_hound(bits, fmt));
+            assert_eq!(Some((bits, fmt)), sample_type.to_hound());
+        }
+
+        assert_eq!(
+            Some((8, SampleFormat::Int)),
+            SampleType::new::<i8>().to_hound()
+        );
+        assert_eq!(
+            Some((16, SampleFormat::Int)),
+            SampleType::new_le::<i16>().to_hound()
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        for (bits, fmt) in [
+            (64, SampleFormat::Float),
+            (64, SampleFormat::Int),
+            (16, SampleFormat::Float),
+            (12, SampleFormat::Int),
+            (0, SampleFormat::Int),
+        ] {
+            assert_eq!(None, SampleType::from_hound(bits, fmt));
+        }
+
+        for sample_type in [
+            SampleType::new::<f64>(),
+            SampleType::new::<i64>(),
+            SampleType::new::<u16>(),
+            SampleType::new::<[u8; 2]>(),
+            SampleType::with_valid_bits::<i32>(24).unwrap(),
+            SampleType::new_be::<i16>(),
+        ] {
+            assert_eq!(None, sample_type.to_hound(), "{sample_type}");
+        }
+    }
+
+    #[test]
+    fn test_wav_spec() {
+        for (spec, system) in [
+            (
+                WavSpec {
+                    channels: 2,
+                    sample_rate: 44_100,
+                    bits_per_sample: 16,
+                    sample_format: SampleFormat::Int,
+                },
+                AUDIO_CD,
+            ),
+            (
+                WavSpec {
+                    channels: 2,
+                    sample_rate: 96_000,
+                    bits_per_sample: 24,
+                    sample_format: SampleFormat::Int,
+                },
+                system!(96_000, Stereo, I24),
+            ),
+            (
+                WavSpec {
+                    channels: 1,
+                    sample_rate: 48_000,
+                    bits_per_sample: 32,
+                    sample_format: SampleFormat::Float,
+                },
+                system!(48_000, Mono, f32),
+            ),
+        ] {
+            assert_eq!(Ok(DynSystem::from(system)), DynSystem::from_wav_spec(&spec));
+            assert_eq!(Some(spec), system.to_wav_spec(), "{system}");
+        }
+
+        // 24 valid bits in a 32-bit container need `hound`'s `WavSpecEx`
+        assert_eq!(None, crate::presets::DVD_AUDIO_96K_24.to_wav_spec());
+        assert_eq!(None, system!(48_000, Stereo, f64).to_wav_spec());
+    }
+
+    #[test]
+    fn test_unsupported_spec() {
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 48_000,
+            bits_per_sample: 64,
+            sample_format: SampleFormat::Float,
+        };
+        assert_eq!(
+            Err(UnsupportedSpecError::UnsupportedFormat {
+                bits: 64,
+                format: SampleFormat::Float
+            }),
+            DynSystem::from_wav_spec(&spec)
+        );
+        assert_eq!(
+            Err(UnsupportedSpecError::Params(StreamParamsError::ZeroRate)),
+            DynSystem::from_wav_spec(&WavSpec {
+                sample_rate: 0,
+                ..spec
+            })
+        );
+        assert_eq!(
+            Err(UnsupportedSpecError::Params(
+                StreamParamsError::UnsupportedChannels(300)
+            )),
+            DynSystem::from_wav_spec(&WavSpec {
+                channels: 300,
+                ..spec
+            })
+        );
+    }
+
+    #[test]
+    fn test_duration_of_wav() {
+        let spec = AUDIO_CD.to_wav_spec().unwrap();
+        let mut wav = Cursor::new(Vec::new());
+        let mut writer = ::hound::WavWriter::new(&mut wav, spec).unwrap();
+        // 1.5 seconds, plus a frame
+        for _ in 0..(66_150 + 1) * 2 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let reader = WavReader::new(Cursor::new(wav.into_inner())).unwrap();
+        assert_eq!(132_302, reader.len());
+        assert_eq!(
+            Duration::from_millis(1_500) + Duration::from_nanos(22_675),
+            duration_of_wav(&reader)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Packed 24-bit sample types, for describing streams (e.g. WAV files, or ALSA
+//! `S24_3LE` devices) that store each sample in exactly 3 bytes.
+
+macro_rules! int24 {
+    ($(#[$attr:meta])* $name:ident, $int:ty, $min:expr, $max:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[repr(transparent)]
+        pub struct $name([u8; 3]);
+
+        impl $name {
+            pub const MIN: $int = $min;
+            pub const MAX: $int = $max;
+
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; 3]) -> Self {
+                Self(bytes)
+            }
+
+            #[inline]
+            pub const fn to_le_bytes(self) -> [u8; 3] {
+                self.0
+            }
+
+            /// Returns `None` if `value` is out of the 24-bit range.
+            #[inline]
+            pub const fn new(value: $int) -> Option<Self> {
+                if value < Self::MIN || value > Self::MAX {
+                    return None;
+                }
+
+                let [a, b, c, _] = value.to_le_bytes();
+                Some(Self([a, b, c]))
+            }
+        }
+
+        // SAFETY: all-zeros is the zero sample
+        unsafe impl audio_core::Sample for $name {
+            const ZERO: Self = Self([0; 3]);
+        }
+    };
+}
+
+int24!(
+    /// A signed 24-bit little-endian integer sample, packed into 3 bytes.
+    I24,
+    i32,
+    -(1 << 23),
+    (1 << 23) - 1
+);
+
+int24!(
+    /// An unsigned 24-bit little-endian integer sample, packed into 3 bytes.
+    U24,
+    u32,
+    0,
+    (1 << 24) - 1
+);
+
+impl I24 {
+    /// The sample's value, sign-extended.
+    #[inline]
+    pub const fn get(self) -> i32 {
+        let [a, b, c] = self.0;
+        i32::from_le_bytes([0, a, b, c]) >> 8
+    }
+}
+
+impl U24 {
+    #[inline]
+    pub const fn get(self) -> u32 {
+        let [a, b, c] = self.0;
+        u32::from_le_bytes([a, b, c, 0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{system, Bytes, Frames, MisalignedError, SampleType, System};
+
+    #[test]
+    fn test_values() {
+        assert_eq!(-1, I24::new(-1).unwrap().get());
+        assert_eq!([0xff; 3], I24::new(-1).unwrap().to_le_bytes());
+        assert_eq!(I24::MIN, I24::new(I24::MIN).unwrap().get());
+        assert_eq!(I24::MAX, I24::new(I24::MAX).unwrap().get());
+        assert!(I24::new(I24::MAX + 1).is_none());
+        assert!(I24::new(I24::MIN - 1).is_none());
+
+        assert_eq!(0x12_3456, U24::from_le_bytes([0x56, 0x34, 0x12]).get());
+        assert!(U24::new(1 << 24).is_none());
+    }
+
+    #[test]
+    fn test_packed_system() {
+        const SYS: System = system!(48_000, Stereo, I24);
+
+        assert_eq!(3, SampleType::new::<I24>().byte_depth().get());
+        assert_eq!(24, SampleType::new::<I24>().bit_depth().get());
+        assert_ne!(SampleType::new::<I24>(), SampleType::new::<[u8; 3]>());
+        assert_ne!(SampleType::new::<I24>(), SampleType::new::<U24>());
+        assert_eq!(6, SYS.frame_size().get());
+
+        let second = Frames::<SYS>::from_duration(Duration::from_secs(1));
+        assert_eq!(288_000, second.into_bytes().get());
+        assert_eq!(second, Frames::from(Bytes::<SYS>::new(288_000).unwrap()));
+
+        assert!(Bytes::<SYS>::new(7).is_none());
+        assert_eq!(
+            Err(MisalignedError { remainder: 1 }),
+            Frames::<SYS>::of_byte_slice(&[0; 7])
+        );
+        assert_eq!(Ok(Frames::new(2)), Frames::<SYS>::of_byte_slice(&[0; 12]));
+    }
+}
@@ -29,6 +29,7 @@
 //! assert_eq!(16_000, bytes.get());
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(incomplete_features, internal_features)]
 #![feature(
     adt_const_params,
@@ -45,32 +46,184 @@
 
 extern crate self as audio_time;
 
+pub mod alsa;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "audio-buf")]
+mod audio_buf;
+mod bits;
+mod buffer;
+mod byte_rate;
 mod bytes;
 mod channel_layout;
-mod convert;
+#[cfg(feature = "chrono")]
+mod chrono;
+mod chunks;
+pub mod codec;
+pub mod convert;
+pub mod coreaudio;
+#[cfg(feature = "std")]
+mod counting;
+#[cfg(feature = "cpal")]
+mod cpal;
+mod cross_system;
+#[cfg(feature = "dasp")]
+mod dasp;
+mod dyn_sample_type;
+mod dyn_system;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fixed")]
+mod fixed;
+mod fps;
+mod frame_range;
 mod frames;
+mod frames_delta;
+#[cfg(feature = "gstreamer")]
+mod gstreamer;
+#[cfg(feature = "half")]
+mod half;
+#[cfg(feature = "hound")]
+mod hound;
+mod int24;
 mod macros;
+mod note_value;
+pub mod opus;
+mod period;
+pub mod pipewire;
+pub mod presets;
+mod pts;
+pub mod pulse;
+mod rational_rate;
+mod reference_time;
+mod resample_counter;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rodio")]
+mod rodio;
+mod rounding;
+pub mod rtp;
 mod sample;
 mod sample_rate;
 mod samples;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "std")]
+mod seek;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod signed_duration;
+#[cfg(feature = "serde")]
+mod span_serde;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod stream_format;
+#[cfg(feature = "symphonia")]
+mod symphonia;
 mod system;
+mod tag;
+mod tempo;
+mod ticks;
+#[cfg(feature = "time")]
+mod time;
+mod time_base;
+mod time_components;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(feature = "std")]
+mod tool_args;
+pub mod wasapi;
+pub mod wav;
+mod web_audio;
 
 pub use ChannelLayout::{Mono, Stereo};
 
+#[cfg(feature = "audio-buf")]
+pub use crate::audio_buf::{
+    bytes_of_buf, frames_of_buf, validate_buf_duration, BufDurationError, ChannelMismatchError,
+};
+#[cfg(feature = "cpal")]
+pub use crate::cpal::CpalConfigError;
+#[cfg(feature = "gstreamer")]
+pub use crate::gstreamer::ClockTimeError;
+#[cfg(feature = "hound")]
+pub use crate::hound::{duration_of_wav, UnsupportedSpecError};
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::{ArchivedBytes, ArchivedFrames, ArchivedSampleRate, ArchivedSamples};
+#[cfg(feature = "rodio")]
+pub use crate::rodio::SourceTimeExt;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use crate::signed_duration::SignedDurationError;
+#[cfg(feature = "symphonia")]
+pub use crate::symphonia::{duration_of_audio_buffer, frames_of_audio_buffer, SignalSpecError};
+#[cfg(feature = "tokio")]
+pub use crate::tokio::{AsyncCountingReader, AsyncCountingWriter};
 pub use crate::{
-    bytes::Bytes,
-    channel_layout::ChannelLayout,
+    bits::{Bits, UnalignedBitsError},
+    buffer::{MisalignedError, SampleSliceError, SampleSliceExt, TypeMismatchError},
+    byte_rate::ByteRate,
+    bytes::{Bytes, DisplaySize},
+    channel_layout::{
+        AmbisonicsOrder, ChannelLayout, ChannelPosition, Channels, TooFewChannelsError,
+        UnknownMaskError,
+    },
+    chunks::{chunks_frames, chunks_frames_mut, FrameChunks, FrameChunksMut},
+    cross_system::{
+        drift_between, drift_within, CrossSystemError, FromSystem, IntoSystem, ReinterpretError,
+    },
+    dyn_sample_type::DynSampleType,
+    dyn_system::{DynBytes, DynFrames, DynSamples, DynSystem, DynSystemError},
+    fps::Fps,
+    frame_range::FrameRange,
     frames::Frames,
-    sample::SampleType,
-    sample_rate::SampleRate,
+    frames_delta::FramesDelta,
+    int24::{I24, U24},
+    note_value::NoteValue,
+    opus::GranulePos,
+    period::PeriodPolicy,
+    presets::AUDIO_CD,
+    pts::Pts90k,
+    rational_rate::{RationalRate, RationalSystem},
+    reference_time::{
+        reference_time_from_duration, reference_time_to_duration, ReferenceTimeError,
+        REFERENCE_TIME_PER_SEC,
+    },
+    resample_counter::{ChunkSize, ResampleCounter},
+    rounding::Rounding,
+    rtp::RtpTimestamp,
+    sample::{Endianness, SampleKind, SampleType},
+    sample_rate::{
+        NonIntegralRateError, ParseSampleRateError, RateFamily, SampleRate, ZeroRateError,
+    },
     samples::Samples,
-    system::{System, AUDIO_CD},
+    stream_format::{StreamParamsError, UnsupportedSampleTypeError},
+    system::System,
+    tag::TagError,
+    tempo::{BeatsError, Tempo},
+    ticks::{frames_to_ticks, segments_to_frames, ticks_to_frames, Ppq, Ticks},
+    time_base::TimeBase,
+    time_components::{TimeComponents, TimeComponentsError},
+    web_audio::{duration_of_web_audio_buffer, WebAudioError},
+};
+#[cfg(feature = "std")]
+pub use crate::{
+    buffer::AllocError,
+    channel_layout::ParseChannelLayoutError,
+    chunks::{into_chunks_frames, IntoFrameChunks},
+    counting::{CountingReader, CountingWriter},
+    dyn_system::ParseSystemError,
+    seek::SeekTimeExt,
+    tool_args::UnsupportedError,
 };
 
 #[derive(thiserror::Error, Debug)]
 #[error("Overflow error")]
 pub struct OverflowError(());
 
+/// Error returned when a span does not contain a whole number of frames.
+#[derive(thiserror::Error, Debug)]
+#[error("Not a whole number of frames")]
+pub struct NotIntegralError(());
+
 #[cfg(test)]
 mod tests {
 
@@ -78,10 +231,17 @@ mod tests {
 
     use audio_time::*;
 
+    /// Converts `value` into the type of `_like`, so the conversion's target
+    /// stays inferable even when `T` implements `PartialEq` for several types
+    /// (e.g. `Duration`, once the `time` crate is enabled).
+    fn try_into_like<T, U: TryInto<T>>(_like: &T, value: U) -> Result<T, U::Error> {
+        value.try_into()
+    }
+
     macro_rules! assert_bidi {
         ($a:expr, $b:expr) => {
-            assert_eq!($a, $b.try_into().unwrap());
-            assert_eq!($b, $a.try_into().unwrap());
+            assert_eq!($a, try_into_like(&$a, $b).unwrap());
+            assert_eq!($b, try_into_like(&$b, $a).unwrap());
         };
     }
 
@@ -117,11 +277,14 @@ mod tests {
         {
             const SYS: System = system!(48_000, Mono, i16);
             let millisecond = Frames::<SYS>::new(48);
-            assert_eq!(Duration::from_millis(1), millisecond.try_into()?);
+            assert_eq!(Duration::from_millis(1), Duration::try_from(millisecond)?);
 
             let sub_millisecond = Frames::<SYS>::new(millisecond.get() - 1);
             // this conversion is lossy for durations of under 1 milliseconds
-            assert_eq!(Duration::from_millis(0), sub_millisecond.try_into()?);
+            assert_eq!(
+                Duration::from_millis(0),
+                Duration::try_from(sub_millisecond)?
+            );
             assert_ne!(
                 sub_millisecond,
                 Duration::try_from(sub_millisecond)?.try_into()?
@@ -163,4 +326,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_frames_to_nanos() -> Result<(), OverflowError> {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        for nanos in [0, 20_000_000, 1_000_000_000, 3_600_000_000_000] {
+            let frames = Frames::<SYS>::from_nanos_u64(nanos)?;
+            assert_eq!(nanos, frames.to_nanos_u64()?);
+        }
+        assert_eq!(
+            Frames::<SYS>::new(1),
+            Frames::<SYS>::from_nanos_u64(20_834)?
+        );
+
+        // ~584 years
+        assert!(Frames::<SYS>::from_nanos_u64(u64::MAX).is_ok());
+        let six_centuries = Frames::<SYS>::new(600 * 365 * 24 * 3_600 * 48_000);
+        assert!(six_centuries.to_nanos_u64().is_err());
+
+        Ok(())
+    }
 }
@@ -1,15 +1,15 @@
 #[macro_export]
 macro_rules! impl_fmt {
     ($name:ident) => {
-        impl<const SYS: System> ::std::fmt::Display for $name<SYS> {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                ::std::fmt::Display::fmt(&self.get(), f)
+        impl<const SYS: System> ::core::fmt::Display for $name<SYS> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.get(), f)
             }
         }
 
-        impl<const SYS: System> ::std::fmt::Debug for $name<SYS> {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                ::std::fmt::Debug::fmt(&self.get(), f)
+        impl<const SYS: System> ::core::fmt::Debug for $name<SYS> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.get(), f)
             }
         }
     };
@@ -0,0 +1,186 @@
+use core::time::Duration;
+
+use crate::{
+    rounding::gcd,
+    sample_rate::{split_nanos, NANOS_PER_SEC},
+    Frames, OverflowError, Rounding, System, Tempo,
+};
+
+/// A musical note value, e.g. for specifying tempo-synced delay times.
+///
+/// A quarter note is taken to be a single beat.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NoteValue {
+    Whole,
+    Half,
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+    SixtyFourth,
+    DottedWhole,
+    DottedHalf,
+    DottedQuarter,
+    DottedEighth,
+    DottedSixteenth,
+    DottedThirtySecond,
+    DottedSixtyFourth,
+    TripletWhole,
+    TripletHalf,
+    TripletQuarter,
+    TripletEighth,
+    TripletSixteenth,
+    TripletThirtySecond,
+    TripletSixtyFourth,
+}
+
+impl NoteValue {
+    /// The number of beats (quarter notes) spanned by this note value, as an
+    /// exact `(numerator, denominator)` pair in lowest terms.
+    pub const fn beats(self) -> (u32, u32) {
+        use NoteValue::*;
+
+        // the length of the undotted note, in 64th notes
+        let sixty_fourths = match self {
+            Whole | DottedWhole | TripletWhole => 64,
+            Half | DottedHalf | TripletHalf => 32,
+            Quarter | DottedQuarter | TripletQuarter => 16,
+            Eighth | DottedEighth | TripletEighth => 8,
+            Sixteenth | DottedSixteenth | TripletSixteenth => 4,
+            ThirtySecond | DottedThirtySecond | TripletThirtySecond => 2,
+            SixtyFourth | DottedSixtyFourth | TripletSixtyFourth => 1,
+        };
+
+        let (num, den) = match self {
+            DottedWhole | DottedHalf | DottedQuarter | DottedEighth | DottedSixteenth
+            | DottedThirtySecond | DottedSixtyFourth => (sixty_fourths * 3, 16 * 2),
+            TripletWhole | TripletHalf | TripletQuarter | TripletEighth | TripletSixteenth
+            | TripletThirtySecond | TripletSixtyFourth => (sixty_fourths * 2, 16 * 3),
+            _ => (sixty_fourths, 16),
+        };
+
+        let gcd = gcd(num as u128, den as u128) as u32;
+        (num / gcd, den / gcd)
+    }
+
+    /// The duration of this note value at the given tempo, truncated to whole
+    /// nanoseconds.
+    pub const fn duration(self, tempo: Tempo) -> Duration {
+        let (beats_num, beats_den) = self.beats();
+        let (bpm_num, bpm_den) = tempo.ratio();
+
+        // nanos = beats * 60 * 10^9 / bpm
+        let nanos = (beats_num as u128 * 60 * NANOS_PER_SEC as u128 * bpm_den.get() as u128)
+            / (beats_den as u128 * bpm_num.get() as u128);
+
+        let (secs, nanos) = split_nanos(nanos);
+        Duration::new(secs as u64, nanos)
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The number of frames spanned by a single `note` at the given tempo,
+    /// rounded to the nearest frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows (only possible for extremely slow
+    /// tempos).
+    #[inline]
+    #[track_caller]
+    pub const fn for_note(tempo: Tempo, note: NoteValue) -> Self {
+        match Self::for_notes(tempo, note, 1, Rounding::Nearest) {
+            Ok(frames) => frames,
+            Err(_) => {
+                panic!("Overflowed trying to calculate frames per note")
+            }
+        }
+    }
+
+    /// The number of frames spanned by `count` consecutive `note`s at the
+    /// given tempo.
+    ///
+    /// The total is computed exactly before rounding, so e.g. four quarter
+    /// notes always span the same number of frames as a whole note.
+    #[inline]
+    pub const fn for_notes(
+        tempo: Tempo,
+        note: NoteValue,
+        count: u64,
+        rounding: Rounding,
+    ) -> Result<Self, OverflowError> {
+        let (num, den) = note.beats();
+        tempo.frames_for_beats(num as u128 * count as u128, den as u128, rounding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    #[test]
+    fn test_beats() {
+        assert_eq!((4, 1), NoteValue::Whole.beats());
+        assert_eq!((1, 1), NoteValue::Quarter.beats());
+        assert_eq!((3, 4), NoteValue::DottedEighth.beats());
+        assert_eq!((1, 3), NoteValue::TripletEighth.beats());
+        assert_eq!((1, 16), NoteValue::SixtyFourth.beats());
+        assert_eq!((1, 24), NoteValue::TripletSixtyFourth.beats());
+    }
+
+    #[test]
+    fn test_duration() {
+        let tempo = Tempo::from_bpm(120).unwrap();
+        assert_eq!(
+            Duration::from_millis(500),
+            NoteValue::Quarter.duration(tempo)
+        );
+        assert_eq!(
+            Duration::from_millis(375),
+            NoteValue::DottedEighth.duration(tempo)
+        );
+        assert_eq!(Duration::from_secs(2), NoteValue::Whole.duration(tempo));
+    }
+
+    fn assert_exact<const SYS: System>() {
+        for bpm in [60, 97, 120, 128, 174] {
+            let tempo = Tempo::from_bpm(bpm).unwrap();
+            for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+                let notes = |note, count| Frames::<SYS>::for_notes(tempo, note, count, rounding);
+
+                assert_eq!(
+                    notes(NoteValue::Quarter, 1).unwrap(),
+                    notes(NoteValue::TripletEighth, 3).unwrap()
+                );
+                assert_eq!(
+                    notes(NoteValue::Whole, 1).unwrap(),
+                    notes(NoteValue::Quarter, 4).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact() {
+        assert_exact::<{ system!(44_100, Stereo, i16) }>();
+        assert_exact::<{ system!(48_000, Stereo, f32) }>();
+        assert_exact::<{ system!(96_000, Mono, i32) }>();
+        assert_exact::<{ system!(22_050, Mono, u8) }>();
+    }
+
+    #[test]
+    fn test_for_note() {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        let tempo = Tempo::from_bpm(120).unwrap();
+        assert_eq!(
+            18_000,
+            Frames::<SYS>::for_note(tempo, NoteValue::DottedEighth).get()
+        );
+        assert_eq!(
+            Frames::<SYS>::per_beat(tempo),
+            Frames::<SYS>::for_note(tempo, NoteValue::Quarter)
+        );
+    }
+}
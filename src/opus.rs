@@ -0,0 +1,234 @@
+//! Granule positions of Opus streams in Ogg
+//! ([RFC 7845](https://www.rfc-editor.org/rfc/rfc7845)).
+//!
+//! Granule positions always count 48 kHz samples, whatever the rate the
+//! stream is decoded at, and include the `pre_skip` samples from the ID
+//! header that are decoded but not played back.
+
+use crate::{Frames, OverflowError, Rounding, System};
+
+/// The rate granule positions are measured in, in hertz.
+pub const GRANULE_RATE: u32 = 48_000;
+
+/// An Ogg page's granule position: the number of 48 kHz samples decoded from
+/// the start of the stream once the page's last completed packet is decoded.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct GranulePos(u64);
+
+/// Error returned when converting a [`GranulePos`] into frames.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GranuleError {
+    /// The page has no granule position, i.e. no packet is completed on it.
+    #[error("Page has no granule position")]
+    NoGranule,
+    #[error("Overflow error")]
+    Overflow,
+}
+
+impl GranulePos {
+    /// The granule position of pages on which no packet is completed (`-1` in
+    /// the page header).
+    pub const NONE: Self = Self(u64::MAX);
+
+    /// A granule position as stored in a page header, including
+    /// [`NONE`](Self::NONE).
+    #[inline]
+    pub const fn new(n: u64) -> Self {
+        Self(n)
+    }
+
+    #[inline]
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn is_none(&self) -> bool {
+        self.0 == Self::NONE.0
+    }
+
+    /// The playback position this granule position corresponds to, in frames
+    /// of the decoder's output rate (truncating any partial frame).
+    ///
+    /// Positions within the pre-skip are clamped to zero.
+    ///
+    /// ```
+    /// # use audio_time::{opus::GranulePos, AUDIO_CD};
+    /// #
+    /// // RFC 7845, section 4.3: 1 s into the stream
+    /// let pos = GranulePos::new(59_971);
+    /// assert_eq!(
+    ///     44_100,
+    ///     pos.to_output_frames::<AUDIO_CD>(11_971).unwrap().get()
+    /// );
+    /// ```
+    #[inline]
+    pub const fn to_output_frames<const SYS: System>(
+        self,
+        pre_skip: u16,
+    ) -> Result<Frames<SYS>, GranuleError> {
+        if self.is_none() {
+            return Err(GranuleError::NoGranule);
+        }
+
+        let ticks = self.get().saturating_sub(pre_skip as u64);
+        let frames = Rounding::Down.div(
+            ticks as u128 * SYS.sample_rate.get().get() as u128,
+            GRANULE_RATE as u128,
+        );
+
+        if frames <= usize::MAX as u128 {
+            Ok(Frames::new(frames as usize))
+        } else {
+            Err(GranuleError::Overflow)
+        }
+    }
+
+    /// The granule position of a playback position of `frames` at the
+    /// decoder's output rate, rounding up to a whole 48 kHz sample.
+    ///
+    /// At rates of up to 48 kHz, [`to_output_frames`](Self::to_output_frames)
+    /// gives back `frames`. Above 48 kHz, granule positions can't express
+    /// every frame, and it gives back the first frame at or after `frames`
+    /// that they can (e.g. at 96 kHz, frame 1 becomes frame 2).
+    ///
+    /// Fails if the result would not fit in a granule position (other than
+    /// [`NONE`](Self::NONE)).
+    #[inline]
+    pub const fn from_output_frames<const SYS: System>(
+        frames: Frames<SYS>,
+        pre_skip: u16,
+    ) -> Result<Self, OverflowError> {
+        let ticks = Rounding::Up.div(
+            frames.get() as u128 * GRANULE_RATE as u128,
+            SYS.sample_rate.get().get() as u128,
+        ) + pre_skip as u128;
+
+        if ticks < Self::NONE.0 as u128 {
+            Ok(Self::new(ticks as u64))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+}
+
+impl From<u64> for GranulePos {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<GranulePos> for u64 {
+    #[inline]
+    fn from(value: GranulePos) -> Self {
+        value.get()
+    }
+}
+
+/// The length of a track, in frames of the decoder's output rate, given the
+/// granule position of its final page.
+///
+/// Any samples decoded past this length (from the final packets, which are
+/// padded to a whole Opus frame) are end-trimmed, and not played back.
+#[inline]
+pub const fn trimmed_len<const SYS: System>(
+    final_granule: GranulePos,
+    pre_skip: u16,
+) -> Result<Frames<SYS>, GranuleError> {
+    final_granule.to_output_frames(pre_skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codec, codec::OpusFrameDuration, system, AUDIO_CD};
+
+    const SYS_48K: System = system!(48_000, Stereo, f32);
+
+    const SYS_96K: System = system!(96_000, Stereo, f32);
+
+    /// The pre-skip of the example in RFC 7845, section 4.3.
+    const PRE_SKIP: u16 = 11_971;
+
+    #[test]
+    fn test_trimming() -> Result<(), GranuleError> {
+        // RFC 7845, section 4.3: the last sample decoded from a first page with
+        // a granule position of 59,971 is at PCM sample position 48,000, or 1 s
+        let first = GranulePos::new(59_971);
+        assert_eq!(48_000, first.to_output_frames::<SYS_48K>(PRE_SKIP)?.get());
+        assert_eq!(44_100, first.to_output_frames::<AUDIO_CD>(PRE_SKIP)?.get());
+        assert_eq!(96_000, first.to_output_frames::<SYS_96K>(PRE_SKIP)?.get());
+
+        // were it the final page, any samples decoded past it are end-trimmed
+        assert_eq!(44_100, trimmed_len::<AUDIO_CD>(first, PRE_SKIP)?.get());
+
+        // a first page holding a single 20 ms packet ends within the pre-skip
+        let packet = codec::opus::<SYS_48K>(OpusFrameDuration::Ms20).unwrap();
+        let first = GranulePos::new(packet.get() as u64);
+        assert_eq!(0, first.to_output_frames::<SYS_48K>(PRE_SKIP)?.get());
+        assert_eq!(0, first.to_output_frames::<AUDIO_CD>(PRE_SKIP)?.get());
+
+        // 9,187.5 frames
+        let pos = GranulePos::new(PRE_SKIP as u64 + 10_000);
+        assert_eq!(9_187, pos.to_output_frames::<AUDIO_CD>(PRE_SKIP)?.get());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_output_frames() -> Result<(), OverflowError> {
+        assert_eq!(
+            GranulePos::new(59_971),
+            GranulePos::from_output_frames(Frames::<AUDIO_CD>::new(44_100), PRE_SKIP)?
+        );
+        assert_eq!(
+            GranulePos::new(PRE_SKIP as u64),
+            GranulePos::from_output_frames(Frames::<AUDIO_CD>::new(0), PRE_SKIP)?
+        );
+
+        for n in (0..100_000).step_by(997) {
+            let frames = Frames::<AUDIO_CD>::new(n);
+            let pos = GranulePos::from_output_frames(frames, PRE_SKIP)?;
+            assert_eq!(Ok(frames), pos.to_output_frames(PRE_SKIP), "{n}");
+
+            let frames = Frames::<SYS_48K>::new(n);
+            let pos = GranulePos::from_output_frames(frames, 0)?;
+            assert_eq!(n as u64, pos.get());
+
+            // above 48 kHz, odd frames round up to the next granule
+            let frames = Frames::<SYS_96K>::new(n);
+            let pos = GranulePos::from_output_frames(frames, PRE_SKIP)?;
+            let expected = Frames::<SYS_96K>::new(n.next_multiple_of(2));
+            assert_eq!(Ok(expected), pos.to_output_frames(PRE_SKIP), "{n}");
+        }
+        let pos = GranulePos::from_output_frames(Frames::<SYS_96K>::new(1), 0)?;
+        assert_eq!(1, pos.get());
+        assert_eq!(Ok(Frames::new(2)), pos.to_output_frames::<SYS_96K>(0));
+
+        assert!(GranulePos::from_output_frames(Frames::<SYS_48K>::new(usize::MAX), 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_granule() {
+        assert!(GranulePos::new(u64::MAX).is_none());
+        assert!(!GranulePos::new(0).is_none());
+        assert_eq!(
+            Err(GranuleError::NoGranule),
+            GranulePos::NONE.to_output_frames::<AUDIO_CD>(PRE_SKIP)
+        );
+        assert_eq!(
+            Err(GranuleError::NoGranule),
+            trimmed_len::<SYS_48K>(GranulePos::NONE, 0)
+        );
+
+        // the largest granule position isn't mistaken for it
+        assert!(GranulePos::new(u64::MAX - 1)
+            .to_output_frames::<SYS_48K>(0)
+            .is_ok());
+    }
+}
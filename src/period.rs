@@ -0,0 +1,170 @@
+use core::{num::NonZeroUsize, time::Duration};
+
+use crate::{Frames, System};
+
+/// How [`Frames::period_for_latency`] picks a period size.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PeriodPolicy {
+    /// The largest power of two that doesn't exceed the target.
+    PowerOfTwoAtMost,
+    /// The power of two closest to the target, which may exceed it. Ties are
+    /// broken towards the smaller period.
+    PowerOfTwoNearest,
+    /// The largest multiple of the given number of frames (e.g. a device's
+    /// period granularity) that doesn't exceed the target.
+    MultipleOf(NonZeroUsize),
+}
+
+impl PeriodPolicy {
+    /// The smallest period allowed by this policy.
+    #[inline]
+    const fn min(self) -> usize {
+        match self {
+            Self::PowerOfTwoAtMost | Self::PowerOfTwoNearest => 1,
+            Self::MultipleOf(granule) => granule.get(),
+        }
+    }
+
+    /// The period for a target of `exact` frames, or `None` on overflow.
+    #[inline]
+    const fn pick(self, exact: usize) -> Option<usize> {
+        if exact < self.min() {
+            return Some(self.min());
+        }
+
+        match self {
+            Self::PowerOfTwoAtMost => Some(1 << exact.ilog2()),
+            Self::PowerOfTwoNearest => {
+                let below = 1 << exact.ilog2();
+                if exact == below {
+                    return Some(below);
+                }
+                match below.checked_mul(2) {
+                    Some(above) if above - exact < exact - below => Some(above),
+                    Some(_) => Some(below),
+                    None => None,
+                }
+            }
+            Self::MultipleOf(granule) => Some(exact - exact % granule.get()),
+        }
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// A period (buffer) size for low-latency backends such as ALSA or JACK,
+    /// picked by `policy` to be as close as possible to `target`, along with
+    /// the resulting latency (truncated to whole nanoseconds).
+    ///
+    /// Targets shorter than the smallest period allowed by `policy` (e.g. less
+    /// than a single frame) get that smallest period. Returns `None` if the
+    /// period doesn't fit in a `usize`.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use audio_time::{system, Frames, PeriodPolicy, System};
+    /// #
+    /// const SYS: System = system!(48_000, Stereo, f32);
+    ///
+    /// // 5ms is 240 frames, so 256 would exceed the target
+    /// let target = Duration::from_millis(5);
+    /// let (period, latency) =
+    ///     Frames::<SYS>::period_for_latency(target, PeriodPolicy::PowerOfTwoAtMost).unwrap();
+    /// assert_eq!(128, period.get());
+    /// assert_eq!(Duration::from_nanos(2_666_666), latency);
+    /// ```
+    pub const fn period_for_latency(
+        target: Duration,
+        policy: PeriodPolicy,
+    ) -> Option<(Self, Duration)> {
+        let exact = SYS.sample_rate.periods_in(target);
+        if exact > usize::MAX as u128 {
+            return None;
+        }
+
+        let Some(frames) = policy.pick(exact as usize) else {
+            return None;
+        };
+        let latency = SYS.sample_rate.duration_of(frames as u64);
+
+        Some((Self::new(frames), latency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    const SYS: System = system!(48_000, Stereo, i16);
+
+    fn period(target: Duration, policy: PeriodPolicy) -> Option<(usize, Duration)> {
+        Frames::<SYS>::period_for_latency(target, policy).map(|(frames, dur)| (frames.get(), dur))
+    }
+
+    #[test]
+    fn test_power_of_two() {
+        let target = Duration::from_millis(5);
+        assert_eq!(
+            Some((128, Duration::from_nanos(2_666_666))),
+            period(target, PeriodPolicy::PowerOfTwoAtMost)
+        );
+        assert_eq!(
+            Some((256, Duration::from_nanos(5_333_333))),
+            period(target, PeriodPolicy::PowerOfTwoNearest)
+        );
+
+        // 4ms is 192 frames, exactly halfway between 128 and 256
+        assert_eq!(
+            Some(128),
+            period(Duration::from_millis(4), PeriodPolicy::PowerOfTwoNearest).map(|(n, _)| n)
+        );
+        // 1024 frames exactly
+        let target = Duration::from_nanos(21_333_334);
+        for policy in [
+            PeriodPolicy::PowerOfTwoAtMost,
+            PeriodPolicy::PowerOfTwoNearest,
+        ] {
+            assert_eq!(Some(1_024), period(target, policy).map(|(n, _)| n));
+        }
+    }
+
+    #[test]
+    fn test_multiple_of() {
+        let policy = PeriodPolicy::MultipleOf(NonZeroUsize::new(48).unwrap());
+        assert_eq!(
+            Some((240, Duration::from_millis(5))),
+            period(Duration::from_millis(5), policy)
+        );
+        assert_eq!(
+            Some((240, Duration::from_millis(5))),
+            period(Duration::from_micros(5_990), policy)
+        );
+        assert_eq!(
+            Some((288, Duration::from_millis(6))),
+            period(Duration::from_millis(6), policy)
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        for target in [Duration::ZERO, Duration::from_micros(20)] {
+            assert_eq!(
+                Some((1, Duration::from_nanos(20_833))),
+                period(target, PeriodPolicy::PowerOfTwoAtMost)
+            );
+            assert_eq!(
+                Some((1, Duration::from_nanos(20_833))),
+                period(target, PeriodPolicy::PowerOfTwoNearest)
+            );
+            assert_eq!(
+                Some((48, Duration::from_millis(1))),
+                period(
+                    target,
+                    PeriodPolicy::MultipleOf(NonZeroUsize::new(48).unwrap())
+                )
+            );
+        }
+
+        assert_eq!(None, period(Duration::MAX, PeriodPolicy::PowerOfTwoAtMost));
+    }
+}
@@ -0,0 +1,262 @@
+//! Conversions between [`DynSystem`]s and the raw audio formats of PipeWire
+//! streams (`spa_audio_info_raw`).
+//!
+//! Formats are identified by the numeric values of the `SPA_AUDIO_FORMAT_*`
+//! constants, so no PipeWire bindings are needed. Only interleaved PCM formats
+//! are supported.
+
+use crate::{
+    stream_format::{format_lookups, s24_32},
+    DynSystem, SampleType, StreamParamsError, UnsupportedSampleTypeError, I24, U24,
+};
+
+pub const SPA_AUDIO_FORMAT_S8: u32 = 0x101;
+pub const SPA_AUDIO_FORMAT_U8: u32 = 0x102;
+pub const SPA_AUDIO_FORMAT_S16_LE: u32 = 0x103;
+pub const SPA_AUDIO_FORMAT_S16_BE: u32 = 0x104;
+pub const SPA_AUDIO_FORMAT_U16_LE: u32 = 0x105;
+pub const SPA_AUDIO_FORMAT_U16_BE: u32 = 0x106;
+/// 24-bit samples in the low bits of 32-bit containers.
+pub const SPA_AUDIO_FORMAT_S24_32_LE: u32 = 0x107;
+pub const SPA_AUDIO_FORMAT_S24_32_BE: u32 = 0x108;
+pub const SPA_AUDIO_FORMAT_U24_32_LE: u32 = 0x109;
+pub const SPA_AUDIO_FORMAT_U24_32_BE: u32 = 0x10a;
+pub const SPA_AUDIO_FORMAT_S32_LE: u32 = 0x10b;
+pub const SPA_AUDIO_FORMAT_S32_BE: u32 = 0x10c;
+pub const SPA_AUDIO_FORMAT_U32_LE: u32 = 0x10d;
+pub const SPA_AUDIO_FORMAT_U32_BE: u32 = 0x10e;
+/// Packed 24-bit samples.
+pub const SPA_AUDIO_FORMAT_S24_LE: u32 = 0x10f;
+pub const SPA_AUDIO_FORMAT_S24_BE: u32 = 0x110;
+pub const SPA_AUDIO_FORMAT_U24_LE: u32 = 0x111;
+pub const SPA_AUDIO_FORMAT_U24_BE: u32 = 0x112;
+pub const SPA_AUDIO_FORMAT_F32_LE: u32 = 0x11b;
+pub const SPA_AUDIO_FORMAT_F32_BE: u32 = 0x11c;
+pub const SPA_AUDIO_FORMAT_F64_LE: u32 = 0x11d;
+pub const SPA_AUDIO_FORMAT_F64_BE: u32 = 0x11e;
+
+/// The supported formats, along with their sample types.
+const FORMATS: &[(u32, SampleType)] = &[
+    (SPA_AUDIO_FORMAT_S8, SampleType::new::<i8>()),
+    (SPA_AUDIO_FORMAT_U8, SampleType::new::<u8>()),
+    (SPA_AUDIO_FORMAT_S16_LE, SampleType::new_le::<i16>()),
+    (SPA_AUDIO_FORMAT_S16_BE, SampleType::new_be::<i16>()),
+    (SPA_AUDIO_FORMAT_U16_LE, SampleType::new_le::<u16>()),
+    (SPA_AUDIO_FORMAT_U16_BE, SampleType::new_be::<u16>()),
+    (
+        SPA_AUDIO_FORMAT_S24_32_LE,
+        s24_32(SampleType::new_le::<i32>()),
+    ),
+    (
+        SPA_AUDIO_FORMAT_S24_32_BE,
+        s24_32(SampleType::new_be::<i32>()),
+    ),
+    (
+        SPA_AUDIO_FORMAT_U24_32_LE,
+        s24_32(SampleType::new_le::<u32>()),
+    ),
+    (
+        SPA_AUDIO_FORMAT_U24_32_BE,
+        s24_32(SampleType::new_be::<u32>()),
+    ),
+    (SPA_AUDIO_FORMAT_S32_LE, SampleType::new_le::<i32>()),
+    (SPA_AUDIO_FORMAT_S32_BE, SampleType::new_be::<i32>()),
+    (SPA_AUDIO_FORMAT_U32_LE, SampleType::new_le::<u32>()),
+    (SPA_AUDIO_FORMAT_U32_BE, SampleType::new_be::<u32>()),
+    (SPA_AUDIO_FORMAT_S24_LE, SampleType::new_le::<I24>()),
+    (SPA_AUDIO_FORMAT_S24_BE, SampleType::new_be::<I24>()),
+    (SPA_AUDIO_FORMAT_U24_LE, SampleType::new_le::<U24>()),
+    (SPA_AUDIO_FORMAT_U24_BE, SampleType::new_be::<U24>()),
+    (SPA_AUDIO_FORMAT_F32_LE, SampleType::new_le::<f32>()),
+    (SPA_AUDIO_FORMAT_F32_BE, SampleType::new_be::<f32>()),
+    (SPA_AUDIO_FORMAT_F64_LE, SampleType::new_le::<f64>()),
+    (SPA_AUDIO_FORMAT_F64_BE, SampleType::new_be::<f64>()),
+];
+
+format_lookups!(u32);
+
+/// The format, rate and number of channels of a raw audio stream, as in
+/// `spa_audio_info_raw` (without its channel positions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioInfo {
+    /// One of the `SPA_AUDIO_FORMAT_*` constants.
+    pub format: u32,
+    pub rate: u32,
+    pub channels: u32,
+}
+
+/// Error returned when [`AudioInfo`] can't be described by a [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioInfoError {
+    #[error("Unsupported audio format {0:#x}")]
+    UnsupportedFormat(u32),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+}
+
+impl AudioInfo {
+    /// The system of a stream in this format.
+    ///
+    /// The layout is derived from the number of channels alone (see
+    /// [`ChannelLayout::from_channels`](crate::ChannelLayout::from_channels)).
+    pub const fn to_system(&self) -> Result<DynSystem, AudioInfoError> {
+        let Some(sample_type) = sample_type_of(self.format) else {
+            return Err(AudioInfoError::UnsupportedFormat(self.format));
+        };
+        match DynSystem::from_stream_params(self.rate, self.channels, sample_type) {
+            Ok(system) => Ok(system),
+            Err(err) => Err(AudioInfoError::Params(err)),
+        }
+    }
+
+    /// The format of a stream in `system`.
+    ///
+    /// Native-endian sample types get the format of the target's byte order.
+    pub const fn from_system(system: &DynSystem) -> Result<Self, UnsupportedSampleTypeError> {
+        match format_of(&system.sample_type) {
+            Some(format) => Ok(Self {
+                format,
+                rate: system.sample_rate.get().get(),
+                channels: system.channel_layout.channels().get() as u32,
+            }),
+            None => Err(UnsupportedSampleTypeError(system.sample_type)),
+        }
+    }
+
+    /// The size of a single sample in this format, as PipeWire computes it
+    /// (independently of the [system](AudioInfo::to_system) it maps to), or
+    /// `None` for unsupported formats.
+    #[inline]
+    pub const fn sample_size(&self) -> Option<u32> {
+        match self.format {
+            SPA_AUDIO_FORMAT_S8 | SPA_AUDIO_FORMAT_U8 => Some(1),
+            SPA_AUDIO_FORMAT_S16_LE..=SPA_AUDIO_FORMAT_U16_BE => Some(2),
+            SPA_AUDIO_FORMAT_S24_32_LE..=SPA_AUDIO_FORMAT_U32_BE => Some(4),
+            SPA_AUDIO_FORMAT_S24_LE..=SPA_AUDIO_FORMAT_U24_BE => Some(3),
+            SPA_AUDIO_FORMAT_F32_LE | SPA_AUDIO_FORMAT_F32_BE => Some(4),
+            SPA_AUDIO_FORMAT_F64_LE | SPA_AUDIO_FORMAT_F64_BE => Some(8),
+            _ => None,
+        }
+    }
+
+    /// The number of bytes in a second of a stream in this format, or `None`
+    /// for unsupported formats.
+    #[inline]
+    pub const fn bytes_per_second(&self) -> Option<u64> {
+        match self.sample_size() {
+            Some(size) => Some(size as u64 * self.channels as u64 * self.rate as u64),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, ChannelLayout, Endianness};
+
+    const fn info(format: u32, rate: u32, channels: u32) -> AudioInfo {
+        AudioInfo {
+            format,
+            rate,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_to_system() {
+        let system = info(SPA_AUDIO_FORMAT_S16_LE, 48_000, 2)
+            .to_system()
+            .unwrap();
+        assert_eq!(
+            DynSystem::from_system(system!(48_000, Stereo, i16))
+                .sample_type
+                .with_endianness(Endianness::Little),
+            system.sample_type
+        );
+        assert_eq!(192_000, system.to_system().bytes_per_second());
+
+        let system = info(SPA_AUDIO_FORMAT_F32_LE, 44_100, 1)
+            .to_system()
+            .unwrap();
+        assert_eq!(SampleType::new_le::<f32>(), system.sample_type);
+        assert_eq!(ChannelLayout::Mono, system.channel_layout);
+
+        let system = info(SPA_AUDIO_FORMAT_S24_32_LE, 96_000, 6)
+            .to_system()
+            .unwrap();
+        assert_eq!(4, system.sample_type.byte_depth().get());
+        assert_eq!(24, system.sample_type.bit_depth().get());
+        assert_eq!(6, system.channel_layout.channels().get());
+
+        let system = info(SPA_AUDIO_FORMAT_S24_LE, 96_000, 2)
+            .to_system()
+            .unwrap();
+        assert_eq!(SampleType::new_le::<I24>(), system.sample_type);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &(format, _) in FORMATS {
+            for (rate, channels) in [(44_100, 1), (48_000, 2), (192_000, 8)] {
+                let info = info(format, rate, channels);
+                let system = info.to_system().unwrap();
+                assert_eq!(Ok(info), AudioInfo::from_system(&system), "{format:#x}");
+
+                // catches mismatched byte depths in the mapping
+                assert_eq!(
+                    info.bytes_per_second(),
+                    Some(system.to_system().bytes_per_second()),
+                    "{format:#x}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_native_endianness() {
+        let system = DynSystem::from_system(system!(48_000, Stereo, f32));
+        let expected = if cfg!(target_endian = "big") {
+            SPA_AUDIO_FORMAT_F32_BE
+        } else {
+            SPA_AUDIO_FORMAT_F32_LE
+        };
+        assert_eq!(expected, AudioInfo::from_system(&system).unwrap().format);
+
+        let system = DynSystem::from_system(system!(48_000, Stereo, u8));
+        assert_eq!(
+            SPA_AUDIO_FORMAT_U8,
+            AudioInfo::from_system(&system).unwrap().format
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        // SPA_AUDIO_FORMAT_ULAW
+        let ulaw = info(0x11f, 8_000, 1);
+        assert_eq!(
+            Err(AudioInfoError::UnsupportedFormat(0x11f)),
+            ulaw.to_system()
+        );
+        assert_eq!(None, ulaw.bytes_per_second());
+
+        assert_eq!(
+            Err(AudioInfoError::Params(StreamParamsError::ZeroRate)),
+            info(SPA_AUDIO_FORMAT_S16_LE, 0, 2).to_system()
+        );
+        for channels in [0, 256] {
+            assert_eq!(
+                Err(AudioInfoError::Params(
+                    StreamParamsError::UnsupportedChannels(channels)
+                )),
+                info(SPA_AUDIO_FORMAT_S16_LE, 48_000, channels).to_system()
+            );
+        }
+
+        let system = DynSystem::from_system(system!(48_000, Stereo, i64));
+        assert_eq!(
+            Err(UnsupportedSampleTypeError(system.sample_type)),
+            AudioInfo::from_system(&system)
+        );
+    }
+}
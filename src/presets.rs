@@ -0,0 +1,182 @@
+//! Commonly used encoding [`System`]s.
+
+use core::num::NonZeroU8;
+
+use crate::{system, SampleType, System};
+
+/// Audio CD encoding system.
+///
+/// <https://en.wikipedia.org/wiki/Compact_Disc_Digital_Audio>:
+/// ```text
+/// 2 channels of LPCM audio, each signed 16-bit values sampled at 44100 Hz
+/// ```
+pub const AUDIO_CD: System = system!(44_100, Stereo, i16);
+
+/// Digital Audio Tape, and the de facto standard for broadcast and video
+/// production: 2 channels of signed 16-bit values sampled at 48 kHz.
+///
+/// <https://en.wikipedia.org/wiki/Digital_Audio_Tape>
+pub const DAT_48K: System = system!(48_000, Stereo, i16);
+
+/// Narrowband telephony (e.g. linear PCM over SIP/RTP, as `L16` at 8 kHz): a
+/// single channel of signed 16-bit values sampled at 8 kHz.
+///
+/// <https://en.wikipedia.org/wiki/Pulse-code_modulation#Telephony>
+pub const TELEPHONY_8K: System = system!(8_000, Mono, i16);
+
+/// DVD-Audio's most common high resolution stereo mode: 2 channels of 24-bit
+/// values sampled at 96 kHz, stored in 32-bit containers.
+///
+/// <https://en.wikipedia.org/wiki/DVD-Audio>
+pub const DVD_AUDIO_96K_24: System = System {
+    sample_type: I32_24_BIT,
+    ..system!(96_000, Stereo, i32)
+};
+
+/// High resolution audio at 192 kHz, the highest rate supported by DVD-Audio
+/// and most "studio master" releases: 2 channels of 24-bit values, stored in
+/// 32-bit containers.
+///
+/// <https://en.wikipedia.org/wiki/High-resolution_audio>
+pub const HIRES_192K: System = System {
+    sample_type: I32_24_BIT,
+    ..system!(192_000, Stereo, i32)
+};
+
+/// The internal format of most DAWs and audio plugin hosts: 2 channels of
+/// 32-bit floating point values sampled at 48 kHz.
+pub const STUDIO_48K_F32: System = system!(48_000, Stereo, f32);
+
+/// Stereo DSD64, the format of SACDs: 2 channels of 1-bit samples at
+/// 2.8224 MHz.
+///
+/// Each sample is described as a `u8` with a single meaningful bit, so
+/// [`Bytes`](crate::Bytes) count one (mostly empty) container per sample. Use
+/// [`Bits`](crate::Bits) (e.g.
+/// [`Bits::to_bytes_exact`](crate::Bits::to_bytes_exact))
+/// or [`System::packed_bytes_for`] for the size of the actual, tightly packed
+/// stream, in which 8 frames of mono DSD occupy a single byte.
+///
+/// <https://en.wikipedia.org/wiki/Direct_Stream_Digital>
+pub const DSD64_STEREO: System = System {
+    sample_type: DSD,
+    ..system!(2_822_400, Stereo, u8)
+};
+
+/// Stereo DSD128 ("double-rate DSD"): 2 channels of 1-bit samples at
+/// 5.6448 MHz. See [`DSD64_STEREO`].
+pub const DSD128_STEREO: System = System {
+    sample_type: DSD,
+    ..system!(5_644_800, Stereo, u8)
+};
+
+/// All of the presets in this module, along with their names.
+pub const ALL: &[(&str, System)] = &[
+    ("AUDIO_CD", AUDIO_CD),
+    ("DAT_48K", DAT_48K),
+    ("TELEPHONY_8K", TELEPHONY_8K),
+    ("DVD_AUDIO_96K_24", DVD_AUDIO_96K_24),
+    ("HIRES_192K", HIRES_192K),
+    ("STUDIO_48K_F32", STUDIO_48K_F32),
+    ("DSD64_STEREO", DSD64_STEREO),
+    ("DSD128_STEREO", DSD128_STEREO),
+];
+
+/// 24 valid bits, in a 32-bit container.
+const I32_24_BIT: SampleType = SampleType::new::<i32>()
+    .with_bit_depth(NonZeroU8::new(24).unwrap())
+    .unwrap();
+
+/// A single bit, in an 8-bit container.
+const DSD: SampleType = SampleType::with_valid_bits::<u8>(1).unwrap();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_rates() {
+        for (bytes_per_second, system) in [
+            (176_400, AUDIO_CD),
+            (192_000, DAT_48K),
+            (16_000, TELEPHONY_8K),
+            (768_000, DVD_AUDIO_96K_24),
+            (1_536_000, HIRES_192K),
+            (384_000, STUDIO_48K_F32),
+        ] {
+            assert_eq!(bytes_per_second, system.bytes_per_second(), "{system}");
+        }
+
+        assert_eq!(24, DVD_AUDIO_96K_24.bits_per_sample().get());
+    }
+
+    #[test]
+    fn test_dsd() -> Result<(), crate::OverflowError> {
+        use std::time::Duration;
+
+        use crate::{Bits, Frames, SampleRate};
+
+        assert_eq!(SampleRate::DSD64, DSD64_STEREO.sample_rate);
+        assert_eq!(SampleRate::DSD128, DSD128_STEREO.sample_rate);
+        assert_eq!(1, DSD64_STEREO.bits_per_sample().get());
+
+        let second = Duration::from_secs(1);
+        assert_eq!(705_600, DSD64_STEREO.packed_bytes_for(second)?);
+        assert_eq!(1_411_200, DSD128_STEREO.packed_bytes_for(second)?);
+
+        let frames = Frames::<DSD64_STEREO>::from_duration(second);
+        assert_eq!(
+            Ok(705_600),
+            Bits::from_samples(frames.into_samples()).to_bytes_exact()
+        );
+
+        // 2 whole frames (4 bits) are padded up to a whole byte
+        assert_eq!(1, DSD64_STEREO.packed_bytes_for(Duration::from_micros(1))?);
+        // as are 5 whole frames (10 bits) to 2 bytes
+        assert_eq!(2, DSD64_STEREO.packed_bytes_for(Duration::from_micros(2))?);
+        assert_eq!(0, DSD64_STEREO.packed_bytes_for(Duration::from_nanos(300))?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        for &(_, system) in ALL {
+            let json = serde_json::to_string(&system).unwrap();
+            assert_eq!(system, serde_json::from_str::<System>(&json).unwrap());
+
+            let bin = bincode::serialize(&system).unwrap();
+            assert_eq!(system, bincode::deserialize::<System>(&bin).unwrap());
+        }
+
+        assert_eq!(
+            r#"{"sample_rate":44100,"channel_layout":"stereo","sample_type":"i16"}"#,
+            serde_json::to_string(&AUDIO_CD).unwrap()
+        );
+        assert_eq!(
+            r#"{"sample_rate":96000,"channel_layout":"stereo","sample_type":"i32/24"}"#,
+            serde_json::to_string(&DVD_AUDIO_96K_24).unwrap()
+        );
+        assert_eq!(
+            crate::DynSystem::from(TELEPHONY_8K),
+            serde_json::from_str(
+                r#"{"sample_rate":8000,"channel_layout":"mono","sample_type":"i16"}"#
+            )
+            .unwrap()
+        );
+
+        for invalid in [
+            r#"{"sample_rate":0,"channel_layout":"stereo","sample_type":"i16"}"#,
+            r#"{"sample_rate":44100,"channel_layout":"surround","sample_type":"i16"}"#,
+            r#"{"sample_rate":44100,"channel_layout":"stereo","sample_type":"i48"}"#,
+            r#"{"sample_rate":44100,"channel_layout":"stereo","sample_type":"i16/17"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<System>(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+        assert!(serde_json::to_string(&crate::system!(8_000, Mono, [u8; 3])).is_err());
+    }
+}
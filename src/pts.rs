@@ -0,0 +1,172 @@
+use core::time::Duration;
+
+use crate::{
+    sample_rate::{duration_of_ticks, ticks_in},
+    Frames, OverflowError, Rounding, System,
+};
+
+/// An MPEG (e.g. MPEG-TS) presentation timestamp: a 33-bit counter of ticks of
+/// a 90 kHz clock, which wraps around on overflow.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Pts90k(u64);
+
+impl Pts90k {
+    /// The frequency of the clock PTS values are measured in, in hertz.
+    pub const CLOCK_RATE: u32 = 90_000;
+
+    /// The number of distinct PTS values, after which the counter wraps.
+    pub const MODULUS: u64 = 1 << 33;
+
+    /// Create a `Pts90k` if the given value fits in 33 bits.
+    #[inline]
+    pub const fn new(n: u64) -> Option<Self> {
+        if n < Self::MODULUS {
+            Some(Self(n))
+        } else {
+            None
+        }
+    }
+
+    /// Create a `Pts90k`, discarding all but the 33 lowest bits of the given
+    /// value.
+    #[inline]
+    pub const fn new_wrapping(n: u64) -> Self {
+        Self(n % Self::MODULUS)
+    }
+
+    #[inline]
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// The number of ticks elapsed since `earlier`, using modular arithmetic:
+    /// differences of 2³² or more are interpreted as `earlier` actually being
+    /// the later of the two timestamps.
+    #[inline]
+    pub const fn diff(&self, earlier: &Self) -> i64 {
+        let diff = self.get().wrapping_sub(earlier.get()) % Self::MODULUS;
+
+        if diff >= Self::MODULUS / 2 {
+            diff as i64 - Self::MODULUS as i64
+        } else {
+            diff as i64
+        }
+    }
+
+    /// The position of this timestamp in frames, rounded in the direction of
+    /// `rounding`.
+    pub const fn to_frames<const SYS: System>(
+        self,
+        rounding: Rounding,
+    ) -> Result<Frames<SYS>, OverflowError> {
+        let frames = rounding.div(
+            self.get() as u128 * SYS.sample_rate.get().get() as u128,
+            Self::CLOCK_RATE as u128,
+        );
+
+        if frames <= usize::MAX as u128 {
+            Ok(Frames::new(frames as usize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// The timestamp of the given position in frames, rounded in the direction
+    /// of `rounding` and wrapped to 33 bits.
+    pub const fn from_frames<const SYS: System>(frames: Frames<SYS>, rounding: Rounding) -> Self {
+        let ticks = rounding.div(
+            frames.get() as u128 * Self::CLOCK_RATE as u128,
+            SYS.sample_rate.get().get() as u128,
+        );
+
+        Self((ticks % Self::MODULUS as u128) as u64)
+    }
+
+    /// The time elapsed since the (latest) zero timestamp, truncated to whole
+    /// nanoseconds.
+    #[inline]
+    pub const fn to_duration(self) -> Duration {
+        duration_of_ticks(self.get(), Self::CLOCK_RATE as u64)
+    }
+
+    /// The timestamp of the given duration, rounded in the direction of
+    /// `rounding` and wrapped to 33 bits.
+    #[inline]
+    pub const fn from_duration(dur: Duration, rounding: Rounding) -> Self {
+        let ticks = ticks_in(dur, Self::CLOCK_RATE, rounding);
+
+        Self((ticks % Self::MODULUS as u128) as u64)
+    }
+}
+
+impl From<Pts90k> for Duration {
+    #[inline]
+    fn from(value: Pts90k) -> Self {
+        value.to_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    const SYS: System = system!(48_000, Stereo, i16);
+
+    #[test]
+    fn test_frames() -> Result<(), OverflowError> {
+        let hour = Frames::<SYS>::new(3_600 * 48_000);
+        let pts = Pts90k::from_frames(hour, Rounding::Nearest);
+        assert_eq!(3_600 * 90_000, pts.get());
+        assert_eq!(hour, pts.to_frames(Rounding::Nearest)?);
+        assert_eq!(Duration::from_secs(3_600), pts.to_duration());
+
+        // a 90 kHz tick is shorter than a 48 kHz frame, so the round trip is
+        // lossless
+        for n in (hour.get()..hour.get() + 48_000).step_by(7) {
+            let frames = Frames::<SYS>::new(n);
+            for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+                let pts = Pts90k::from_frames(frames, rounding);
+                assert_eq!(frames, pts.to_frames(Rounding::Nearest)?);
+            }
+        }
+
+        // one frame is 1.875 ticks
+        let frame = Frames::<SYS>::new(1);
+        assert_eq!(1, Pts90k::from_frames(frame, Rounding::Down).get());
+        assert_eq!(2, Pts90k::from_frames(frame, Rounding::Up).get());
+        assert_eq!(2, Pts90k::from_frames(frame, Rounding::Nearest).get());
+
+        // 30 kHz divides 90 kHz evenly
+        assert_eq!(
+            Frames::<{ system!(30_000, Mono, i16) }>::new(1),
+            Pts90k::new(3).unwrap().to_frames(Rounding::Down)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert!(Pts90k::new(Pts90k::MODULUS).is_none());
+
+        let before = Pts90k::new(Pts90k::MODULUS - 900).unwrap();
+        let after = Pts90k::new_wrapping(before.get() + 1_800);
+        assert_eq!(900, after.get());
+        assert_eq!(1_800, after.diff(&before));
+        assert_eq!(-1_800, before.diff(&after));
+
+        // positions past the ~26.5 hour mark wrap around
+        let wrapped = Frames::<SYS>::new(27 * 3_600 * 48_000);
+        assert_eq!(
+            27 * 3_600 * 90_000 - Pts90k::MODULUS,
+            Pts90k::from_frames(wrapped, Rounding::Down).get()
+        );
+        assert_eq!(
+            Pts90k::from_frames(wrapped, Rounding::Down),
+            Pts90k::from_duration(Duration::from_secs(27 * 3_600), Rounding::Down)
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Conversions between [`DynSystem`]s and PulseAudio sample specs
+//! (`pa_sample_spec`).
+//!
+//! Formats are identified by the numeric values of the `PA_SAMPLE_*`
+//! constants, so no PulseAudio bindings are needed.
+
+use crate::{
+    stream_format::{format_lookups, s24_32},
+    DynSystem, SampleType, StreamParamsError, UnsupportedSampleTypeError, I24,
+};
+
+pub const PA_SAMPLE_U8: i32 = 0;
+pub const PA_SAMPLE_S16LE: i32 = 3;
+pub const PA_SAMPLE_S16BE: i32 = 4;
+pub const PA_SAMPLE_FLOAT32LE: i32 = 5;
+pub const PA_SAMPLE_FLOAT32BE: i32 = 6;
+pub const PA_SAMPLE_S32LE: i32 = 7;
+pub const PA_SAMPLE_S32BE: i32 = 8;
+/// Packed 24-bit samples.
+pub const PA_SAMPLE_S24LE: i32 = 9;
+pub const PA_SAMPLE_S24BE: i32 = 10;
+/// 24-bit samples in the low bits of 32-bit containers.
+pub const PA_SAMPLE_S24_32LE: i32 = 11;
+pub const PA_SAMPLE_S24_32BE: i32 = 12;
+
+/// The supported formats, along with their sample types.
+const FORMATS: &[(i32, SampleType)] = &[
+    (PA_SAMPLE_U8, SampleType::new::<u8>()),
+    (PA_SAMPLE_S16LE, SampleType::new_le::<i16>()),
+    (PA_SAMPLE_S16BE, SampleType::new_be::<i16>()),
+    (PA_SAMPLE_FLOAT32LE, SampleType::new_le::<f32>()),
+    (PA_SAMPLE_FLOAT32BE, SampleType::new_be::<f32>()),
+    (PA_SAMPLE_S32LE, SampleType::new_le::<i32>()),
+    (PA_SAMPLE_S32BE, SampleType::new_be::<i32>()),
+    (PA_SAMPLE_S24LE, SampleType::new_le::<I24>()),
+    (PA_SAMPLE_S24BE, SampleType::new_be::<I24>()),
+    (PA_SAMPLE_S24_32LE, s24_32(SampleType::new_le::<i32>())),
+    (PA_SAMPLE_S24_32BE, s24_32(SampleType::new_be::<i32>())),
+];
+
+format_lookups!(i32);
+
+/// The format, rate and number of channels of a stream, as in
+/// `pa_sample_spec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SampleSpec {
+    /// One of the `PA_SAMPLE_*` constants.
+    pub format: i32,
+    pub rate: u32,
+    pub channels: u8,
+}
+
+/// Error returned when a [`SampleSpec`] can't be described by a
+/// [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleSpecError {
+    #[error("Unsupported sample format {0}")]
+    UnsupportedFormat(i32),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+}
+
+impl SampleSpec {
+    /// The system of a stream with this spec.
+    ///
+    /// The layout is derived from the number of channels alone (see
+    /// [`ChannelLayout::from_channels`](crate::ChannelLayout::from_channels)).
+    pub const fn to_system(&self) -> Result<DynSystem, SampleSpecError> {
+        let Some(sample_type) = sample_type_of(self.format) else {
+            return Err(SampleSpecError::UnsupportedFormat(self.format));
+        };
+        match DynSystem::from_stream_params(self.rate, self.channels as u32, sample_type) {
+            Ok(system) => Ok(system),
+            Err(err) => Err(SampleSpecError::Params(err)),
+        }
+    }
+
+    /// The spec of a stream in `system`.
+    ///
+    /// Native-endian sample types get the format of the target's byte order.
+    pub const fn from_system(system: &DynSystem) -> Result<Self, UnsupportedSampleTypeError> {
+        match format_of(&system.sample_type) {
+            Some(format) => Ok(Self {
+                format,
+                rate: system.sample_rate.get().get(),
+                channels: system.channel_layout.channels().get(),
+            }),
+            None => Err(UnsupportedSampleTypeError(system.sample_type)),
+        }
+    }
+
+    /// The equivalent of `pa_sample_size`: the size of a single sample in
+    /// this spec's format, computed independently of the
+    /// [system](SampleSpec::to_system) it maps to, or `None` for unsupported
+    /// formats.
+    #[inline]
+    pub const fn sample_size(&self) -> Option<u32> {
+        match self.format {
+            PA_SAMPLE_U8 => Some(1),
+            PA_SAMPLE_S16LE | PA_SAMPLE_S16BE => Some(2),
+            PA_SAMPLE_S24LE | PA_SAMPLE_S24BE => Some(3),
+            PA_SAMPLE_FLOAT32LE | PA_SAMPLE_FLOAT32BE => Some(4),
+            PA_SAMPLE_S32LE | PA_SAMPLE_S32BE => Some(4),
+            PA_SAMPLE_S24_32LE | PA_SAMPLE_S24_32BE => Some(4),
+            _ => None,
+        }
+    }
+
+    /// The equivalent of `pa_bytes_per_second`, or `None` for unsupported
+    /// formats.
+    #[inline]
+    pub const fn bytes_per_second(&self) -> Option<u64> {
+        match self.sample_size() {
+            Some(size) => Some(size as u64 * self.channels as u64 * self.rate as u64),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, ChannelLayout};
+
+    const fn spec(format: i32, rate: u32, channels: u8) -> SampleSpec {
+        SampleSpec {
+            format,
+            rate,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_to_system() {
+        let system = spec(PA_SAMPLE_S16LE, 44_100, 2).to_system().unwrap();
+        assert_eq!(SampleType::new_le::<i16>(), system.sample_type);
+        assert_eq!(ChannelLayout::Stereo, system.channel_layout);
+        assert_eq!(176_400, system.to_system().bytes_per_second());
+
+        let system = spec(PA_SAMPLE_FLOAT32LE, 48_000, 1).to_system().unwrap();
+        assert_eq!(SampleType::new_le::<f32>(), system.sample_type);
+
+        let system = spec(PA_SAMPLE_S24_32LE, 96_000, 2).to_system().unwrap();
+        assert_eq!(4, system.sample_type.byte_depth().get());
+        assert_eq!(24, system.sample_type.bit_depth().get());
+        assert_eq!(768_000, system.to_system().bytes_per_second());
+
+        let system = spec(PA_SAMPLE_S24LE, 96_000, 2).to_system().unwrap();
+        assert_eq!(SampleType::new_le::<I24>(), system.sample_type);
+        assert_eq!(576_000, system.to_system().bytes_per_second());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &(format, _) in FORMATS {
+            for (rate, channels) in [(8_000, 1), (44_100, 2), (192_000, 32)] {
+                let spec = spec(format, rate, channels);
+                let system = spec.to_system().unwrap();
+                assert_eq!(Ok(spec), SampleSpec::from_system(&system), "{format}");
+
+                // catches mismatched byte depths in the mapping
+                assert_eq!(
+                    spec.bytes_per_second(),
+                    Some(system.to_system().bytes_per_second()),
+                    "{format}"
+                );
+            }
+        }
+
+        let system = DynSystem::from_system(system!(48_000, Stereo, i16));
+        let expected = if cfg!(target_endian = "big") {
+            PA_SAMPLE_S16BE
+        } else {
+            PA_SAMPLE_S16LE
+        };
+        assert_eq!(expected, SampleSpec::from_system(&system).unwrap().format);
+    }
+
+    #[test]
+    fn test_unsupported() {
+        // PA_SAMPLE_ULAW
+        let ulaw = spec(2, 8_000, 1);
+        assert_eq!(Err(SampleSpecError::UnsupportedFormat(2)), ulaw.to_system());
+        assert_eq!(None, ulaw.bytes_per_second());
+        // PA_SAMPLE_INVALID
+        assert_eq!(
+            Err(SampleSpecError::UnsupportedFormat(-1)),
+            spec(-1, 8_000, 1).to_system()
+        );
+
+        assert_eq!(
+            Err(SampleSpecError::Params(StreamParamsError::ZeroRate)),
+            spec(PA_SAMPLE_S16LE, 0, 2).to_system()
+        );
+        assert_eq!(
+            Err(SampleSpecError::Params(
+                StreamParamsError::UnsupportedChannels(0)
+            )),
+            spec(PA_SAMPLE_S16LE, 44_100, 0).to_system()
+        );
+
+        // PulseAudio has no unsigned formats wider than a byte
+        let system = DynSystem::from_system(system!(48_000, Stereo, u16));
+        assert_eq!(
+            Err(UnsupportedSampleTypeError(system.sample_type)),
+            SampleSpec::from_system(&system)
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use core::{
+    fmt,
+    marker::ConstParamTy,
+    num::{NonZeroU16, NonZeroU32},
+    time::Duration,
+};
+
+use crate::{
+    rounding::gcd,
+    sample_rate::{duration_from_nanos, NANOS_PER_SEC},
+    ChannelLayout, OverflowError, SampleRate, SampleType, System,
+};
+
+mod sealed {
+    use core::marker::ConstParamTy;
+
+    use nonzero_const_param::NonZeroU32;
+
+    /// A sample rate that isn't necessarily a whole number of hertz, expressed
+    /// as a `numerator / denominator` ratio in lowest terms.
+    ///
+    /// Useful for pull-down workflows, e.g. 44.1 kHz audio slowed down to match
+    /// NTSC video runs at `44_100 * 1000 / 1001` (≈ 44 055.944) Hz, which a
+    /// [`SampleRate`](crate::SampleRate) can't represent exactly.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ConstParamTy)]
+    pub struct RationalRate {
+        num: NonZeroU32,
+        den: NonZeroU32,
+    }
+
+    impl RationalRate {
+        /// The caller must ensure `num / den` is in lowest terms.
+        #[inline]
+        pub(super) const fn new_unchecked(
+            num: core::num::NonZeroU32,
+            den: core::num::NonZeroU32,
+        ) -> Self {
+            Self {
+                num: NonZeroU32::from_std(num),
+                den: NonZeroU32::from_std(den),
+            }
+        }
+
+        #[inline]
+        pub const fn numer(&self) -> core::num::NonZeroU32 {
+            self.num.into_std()
+        }
+
+        #[inline]
+        pub const fn denom(&self) -> core::num::NonZeroU32 {
+            self.den.into_std()
+        }
+    }
+}
+
+pub use self::sealed::RationalRate;
+
+impl RationalRate {
+    /// A rate of `num / den` hertz, reduced to lowest terms.
+    #[inline]
+    pub const fn new(num: NonZeroU32, den: NonZeroU32) -> Self {
+        let gcd = gcd(num.get() as u128, den.get() as u128) as u32;
+        Self::new_unchecked(
+            NonZeroU32::new(num.get() / gcd).unwrap(),
+            NonZeroU32::new(den.get() / gcd).unwrap(),
+        )
+    }
+
+    /// A whole sample rate, as a rational one.
+    #[inline]
+    pub const fn from_rate(rate: SampleRate) -> Self {
+        Self::new_unchecked(rate.get(), NonZeroU32::MIN)
+    }
+
+    /// `rate` slowed down by a factor of 1.001, as when pulling audio down to
+    /// match NTSC video (e.g. 48 kHz becomes ≈ 47 952.048 Hz).
+    ///
+    /// Returns `None` if `rate * 1000` overflows a `u32`.
+    #[inline]
+    pub const fn pulled_down(rate: SampleRate) -> Option<Self> {
+        match rate.get().checked_mul(NonZeroU32::new(1_000).unwrap()) {
+            Some(num) => Some(Self::new(num, NonZeroU32::new(1_001).unwrap())),
+            None => None,
+        }
+    }
+
+    /// This rate as a whole [`SampleRate`], or `None` if it isn't a whole
+    /// number of hertz.
+    #[inline]
+    pub const fn to_sample_rate(&self) -> Option<SampleRate> {
+        if self.denom().get() == 1 {
+            Some(SampleRate::new(self.numer()))
+        } else {
+            None
+        }
+    }
+
+    /// The (approximate) rate in hertz.
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        self.numer().get() as f64 / self.denom().get() as f64
+    }
+
+    /// The number of whole frames that fit in `dur` at this rate, rounded down.
+    ///
+    /// Returns an error if the result does not fit in a `u64`.
+    #[inline]
+    pub const fn frames_in(&self, dur: Duration) -> Result<u64, OverflowError> {
+        let (num, den) = (self.numer().get() as u128, self.denom().get() as u128);
+        let frames = dur.as_nanos() * num / (den * NANOS_PER_SEC as u128);
+
+        if frames <= u64::MAX as u128 {
+            Ok(frames as u64)
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// The duration of `frames` frames at this rate, truncated to whole
+    /// nanoseconds.
+    ///
+    /// Returns an error if the result does not fit in a [`Duration`].
+    #[inline]
+    pub const fn duration_of(&self, frames: u64) -> Result<Duration, OverflowError> {
+        let (num, den) = (self.numer().get() as u128, self.denom().get() as u128);
+        let nanos = frames as u128 * den * NANOS_PER_SEC as u128 / num;
+
+        match duration_from_nanos(nanos) {
+            Some(dur) => Ok(dur),
+            None => Err(OverflowError(())),
+        }
+    }
+}
+
+/// A [`System`] whose sample rate is a [`RationalRate`], e.g. for the
+/// pulled-down audio of NTSC video.
+///
+/// Like [`System`], it can be used as a const generic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ConstParamTy)]
+pub struct RationalSystem {
+    pub rate: RationalRate,
+    pub channel_layout: ChannelLayout,
+    pub sample_type: SampleType,
+}
+
+impl System {
+    /// This system, with its sample rate replaced by a (possibly fractional)
+    /// rational rate.
+    ///
+    /// ```
+    /// # #![feature(const_option)]
+    /// # use audio_time::{RationalRate, RationalSystem, SampleRate, AUDIO_CD};
+    /// #
+    /// const PULLED_DOWN: RationalSystem =
+    ///     AUDIO_CD.with_rational_rate(RationalRate::pulled_down(SampleRate::HZ_44_1K).unwrap());
+    /// assert_eq!(
+    ///     44_055,
+    ///     PULLED_DOWN
+    ///         .duration_to_frames(std::time::Duration::from_secs(1))
+    ///         .unwrap()
+    /// );
+    /// ```
+    #[inline]
+    pub const fn with_rational_rate(self, rate: RationalRate) -> RationalSystem {
+        RationalSystem {
+            rate,
+            channel_layout: self.channel_layout,
+            sample_type: self.sample_type,
+        }
+    }
+}
+
+impl RationalSystem {
+    /// The number of bytes used to represent a single frame, see
+    /// [`System::frame_size`].
+    #[inline]
+    pub const fn frame_size(&self) -> NonZeroU16 {
+        self.with_whole_rate().frame_size()
+    }
+
+    /// This system as a [`System`], or `None` if its rate isn't a whole number
+    /// of hertz.
+    #[inline]
+    pub const fn to_system(&self) -> Option<System> {
+        match self.rate.to_sample_rate() {
+            Some(sample_rate) => Some(self.with_whole_rate().with_sample_rate(sample_rate)),
+            None => None,
+        }
+    }
+
+    /// The number of whole frames in `dur`, rounded down (see
+    /// [`RationalRate::frames_in`]).
+    #[inline]
+    pub const fn duration_to_frames(&self, dur: Duration) -> Result<u64, OverflowError> {
+        self.rate.frames_in(dur)
+    }
+
+    /// The duration of `frames` frames, truncated to whole nanoseconds (see
+    /// [`RationalRate::duration_of`]).
+    #[inline]
+    pub const fn frames_to_duration(&self, frames: u64) -> Result<Duration, OverflowError> {
+        self.rate.duration_of(frames)
+    }
+
+    /// The number of bytes needed to represent `dur`, truncated to a whole
+    /// number of frames.
+    #[inline]
+    pub const fn bytes_for(&self, dur: Duration) -> Result<u64, OverflowError> {
+        match self.duration_to_frames(dur) {
+            Ok(frames) => match frames.checked_mul(self.frame_size().get() as u64) {
+                Some(bytes) => Ok(bytes),
+                None => Err(OverflowError(())),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The duration represented by `bytes` bytes, ignoring any trailing
+    /// partial frame.
+    #[inline]
+    pub const fn duration_for_bytes(&self, bytes: u64) -> Result<Duration, OverflowError> {
+        self.frames_to_duration(bytes / self.frame_size().get() as u64)
+    }
+
+    /// The non-rate parameters of this system, at an arbitrary whole rate.
+    #[inline]
+    const fn with_whole_rate(&self) -> System {
+        System {
+            sample_rate: SampleRate::HZ_48K,
+            channel_layout: self.channel_layout,
+            sample_type: self.sample_type,
+        }
+    }
+}
+
+impl From<System> for RationalSystem {
+    fn from(value: System) -> Self {
+        value.with_rational_rate(RationalRate::from_rate(value.sample_rate))
+    }
+}
+
+impl From<SampleRate> for RationalRate {
+    fn from(value: SampleRate) -> Self {
+        Self::from_rate(value)
+    }
+}
+
+/// Prints whole rates in hertz (e.g. `48000 Hz`), and others as a ratio (e.g.
+/// `6300000/143 Hz`).
+impl fmt::Display for RationalRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.denom().get() {
+            1 => write!(f, "{} Hz", self.numer()),
+            den => write!(f, "{}/{den} Hz", self.numer()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[test]
+    fn test_new() {
+        let rate = RationalRate::new(
+            NonZeroU32::new(88_200).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+        );
+        assert_eq!(Some(SampleRate::HZ_44_1K), rate.to_sample_rate());
+        assert_eq!(RationalRate::from(SampleRate::HZ_44_1K), rate);
+
+        let pulled_down = RationalRate::pulled_down(SampleRate::HZ_44_1K).unwrap();
+        assert_eq!(None, pulled_down.to_sample_rate());
+        assert_eq!("6300000/143 Hz", pulled_down.to_string());
+        assert!((pulled_down.as_f64() - 44_055.944).abs() < 0.001);
+
+        assert!(RationalRate::pulled_down(SampleRate::new(NonZeroU32::MAX)).is_none());
+    }
+
+    #[test]
+    fn test_pull_down_drift() -> Result<(), OverflowError> {
+        const NOMINAL: RationalRate = RationalRate::from_rate(SampleRate::HZ_44_1K);
+        const PULLED_DOWN: RationalRate = RationalRate::pulled_down(SampleRate::HZ_44_1K).unwrap();
+        let hour = Duration::from_secs(3_600);
+
+        assert_eq!(158_760_000, NOMINAL.frames_in(hour)?);
+        // 158_760_000 / 1.001 = 158_601_398.6
+        assert_eq!(158_601_398, PULLED_DOWN.frames_in(hour)?);
+        // rounding the rate to 44 056 Hz instead would be off by 202 frames
+        assert_eq!(158_601_600, 44_056 * 3_600);
+
+        // an hour's worth of nominal frames takes 3.6 seconds longer to play
+        let drift = PULLED_DOWN.duration_of(NOMINAL.frames_in(hour)?)? - hour;
+        assert_eq!(Duration::from_millis(3_600), drift);
+
+        assert_eq!(hour, NOMINAL.duration_of(158_760_000)?);
+        assert!(NOMINAL.duration_of(u64::MAX).is_ok());
+        assert!(PULLED_DOWN.frames_in(Duration::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_system() -> Result<(), OverflowError> {
+        const NOMINAL: RationalSystem =
+            AUDIO_CD.with_rational_rate(RationalRate::from_rate(SampleRate::HZ_44_1K));
+        const PULLED_DOWN: RationalSystem =
+            AUDIO_CD.with_rational_rate(RationalRate::pulled_down(SampleRate::HZ_44_1K).unwrap());
+
+        /// Usable as a const generic, like a [`System`].
+        fn bytes_per_hour<const SYS: RationalSystem>() -> u64 {
+            SYS.bytes_for(Duration::from_secs(3_600)).unwrap()
+        }
+
+        let hour = Duration::from_secs(3_600);
+        assert_eq!(RationalSystem::from(AUDIO_CD), NOMINAL);
+        assert_eq!(Some(AUDIO_CD), NOMINAL.to_system());
+        assert_eq!(None, PULLED_DOWN.to_system());
+        assert_eq!(4, PULLED_DOWN.frame_size().get());
+
+        assert_eq!(AUDIO_CD.bytes_for(hour)?, bytes_per_hour::<NOMINAL>());
+        assert_eq!(158_601_398 * 4, bytes_per_hour::<PULLED_DOWN>());
+        assert_eq!(158_601_398, PULLED_DOWN.duration_to_frames(hour)?);
+
+        // playing back an hour of nominal audio at the pulled-down rate takes
+        // 3.6 seconds longer
+        let bytes = NOMINAL.bytes_for(hour)?;
+        assert_eq!(hour, NOMINAL.duration_for_bytes(bytes)?);
+        assert_eq!(
+            hour + Duration::from_millis(3_600),
+            PULLED_DOWN.duration_for_bytes(bytes)?
+        );
+        assert_eq!(
+            hour + Duration::from_millis(3_600),
+            PULLED_DOWN.frames_to_duration(158_760_000)?
+        );
+
+        assert!(PULLED_DOWN.bytes_for(Duration::MAX).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+//! Conversions to and from Windows `REFERENCE_TIME`s: signed counts of
+//! 100-nanosecond intervals, as used by WASAPI for buffer durations, latencies
+//! and device periods.
+
+use core::time::Duration;
+
+use crate::{time_base::rescale, Frames, FramesDelta, OverflowError, Rounding, System};
+
+/// The number of `REFERENCE_TIME` units in a second.
+pub const REFERENCE_TIME_PER_SEC: i64 = 10_000_000;
+
+const NANOS_PER_UNIT: u128 = 100;
+
+/// Error returned when converting a `REFERENCE_TIME` into an unsigned span.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceTimeError {
+    #[error("REFERENCE_TIME of {0} is negative")]
+    Negative(i64),
+    #[error("Overflow error")]
+    Overflow,
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The number of frames in `rt` 100-nanosecond units, rounded in the
+    /// direction of `rounding`.
+    ///
+    /// Negative values are rejected, see [`FramesDelta::from_reference_time`]
+    /// for signed offsets.
+    ///
+    /// ```
+    /// # use audio_time::{Frames, Rounding, AUDIO_CD};
+    /// #
+    /// // 10ms
+    /// let frames = Frames::<AUDIO_CD>::from_reference_time(100_000, Rounding::Up).unwrap();
+    /// assert_eq!(441, frames.get());
+    /// ```
+    #[inline]
+    pub const fn from_reference_time(
+        rt: i64,
+        rounding: Rounding,
+    ) -> Result<Self, ReferenceTimeError> {
+        if rt < 0 {
+            return Err(ReferenceTimeError::Negative(rt));
+        }
+        match FramesDelta::<SYS>::from_reference_time(rt, rounding) {
+            Ok(delta) => Ok(Self::new(delta.get() as usize)),
+            Err(_) => Err(ReferenceTimeError::Overflow),
+        }
+    }
+
+    /// The duration of these frames in 100-nanosecond units, rounded in the
+    /// direction of `rounding` (e.g. [`Rounding::Up`] for the buffer duration
+    /// passed to `IAudioClient::Initialize`).
+    #[inline]
+    pub const fn to_reference_time(&self, rounding: Rounding) -> Result<i64, OverflowError> {
+        frames_to_reference_time::<SYS>(self.get() as i128, rounding)
+    }
+}
+
+impl<const SYS: System> FramesDelta<SYS> {
+    /// The number of frames in `rt` 100-nanosecond units, with its magnitude
+    /// rounded in the direction of `rounding`.
+    #[inline]
+    pub const fn from_reference_time(rt: i64, rounding: Rounding) -> Result<Self, OverflowError> {
+        let frames = rescale(
+            rt as i128,
+            SYS.sample_rate.get().get() as u128,
+            REFERENCE_TIME_PER_SEC as u128,
+            rounding,
+        );
+
+        if isize::MIN as i128 <= frames && frames <= isize::MAX as i128 {
+            Ok(Self::new(frames as isize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// See [`Frames::to_reference_time`].
+    #[inline]
+    pub const fn to_reference_time(&self, rounding: Rounding) -> Result<i64, OverflowError> {
+        frames_to_reference_time::<SYS>(self.get() as i128, rounding)
+    }
+}
+
+#[inline]
+const fn frames_to_reference_time<const SYS: System>(
+    frames: i128,
+    rounding: Rounding,
+) -> Result<i64, OverflowError> {
+    let rt = rescale(
+        frames,
+        REFERENCE_TIME_PER_SEC as u128,
+        SYS.sample_rate.get().get() as u128,
+        rounding,
+    );
+
+    if i64::MIN as i128 <= rt && rt <= i64::MAX as i128 {
+        Ok(rt as i64)
+    } else {
+        Err(OverflowError(()))
+    }
+}
+
+/// The number of 100-nanosecond units in `dur`, rounded in the direction of
+/// `rounding`.
+#[inline]
+pub const fn reference_time_from_duration(
+    dur: Duration,
+    rounding: Rounding,
+) -> Result<i64, OverflowError> {
+    let rt = rounding.div(dur.as_nanos(), NANOS_PER_UNIT);
+
+    if rt <= i64::MAX as u128 {
+        Ok(rt as i64)
+    } else {
+        Err(OverflowError(()))
+    }
+}
+
+/// The duration of `rt` 100-nanosecond units, which must not be negative.
+#[inline]
+pub const fn reference_time_to_duration(rt: i64) -> Result<Duration, ReferenceTimeError> {
+    if rt < 0 {
+        return Err(ReferenceTimeError::Negative(rt));
+    }
+
+    let rt = rt as u64;
+    let per_sec = REFERENCE_TIME_PER_SEC as u64;
+    Ok(Duration::new(
+        rt / per_sec,
+        ((rt % per_sec) * NANOS_PER_UNIT as u64) as u32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_frames() -> Result<(), OverflowError> {
+        // 10ms
+        for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+            assert_eq!(
+                Ok(Frames::<AUDIO_CD>::new(441)),
+                Frames::from_reference_time(100_000, rounding)
+            );
+            assert_eq!(
+                100_000,
+                Frames::<AUDIO_CD>::new(441).to_reference_time(rounding)?
+            );
+        }
+
+        // 100ns is 0.00441 frames at 44.1 kHz
+        assert_eq!(
+            Ok(Frames::<AUDIO_CD>::new(0)),
+            Frames::from_reference_time(1, Rounding::Down)
+        );
+        assert_eq!(
+            Ok(Frames::<AUDIO_CD>::new(1)),
+            Frames::from_reference_time(1, Rounding::Up)
+        );
+        assert_eq!(
+            Ok(Frames::<AUDIO_CD>::new(0)),
+            Frames::from_reference_time(1, Rounding::Nearest)
+        );
+
+        // a frame is 226.757... units
+        let frame = Frames::<AUDIO_CD>::new(1);
+        assert_eq!(226, frame.to_reference_time(Rounding::Down)?);
+        assert_eq!(227, frame.to_reference_time(Rounding::Up)?);
+        assert_eq!(227, frame.to_reference_time(Rounding::Nearest)?);
+
+        // a default WASAPI device period at 48 kHz
+        const SYS: System = system!(48_000, Stereo, f32);
+        assert_eq!(
+            Ok(Frames::<SYS>::new(480)),
+            Frames::from_reference_time(100_000, Rounding::Up)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_negative() -> Result<(), OverflowError> {
+        assert_eq!(
+            Err(ReferenceTimeError::Negative(-1)),
+            Frames::<AUDIO_CD>::from_reference_time(-1, Rounding::Down)
+        );
+
+        let delta = FramesDelta::<AUDIO_CD>::from_reference_time(-100_000, Rounding::Up)?;
+        assert_eq!(-441, delta.get());
+        assert_eq!(-100_000, delta.to_reference_time(Rounding::Nearest)?);
+        // magnitudes are rounded
+        assert_eq!(
+            -1,
+            FramesDelta::<AUDIO_CD>::from_reference_time(-1, Rounding::Up)?.get()
+        );
+        assert_eq!(
+            0,
+            FramesDelta::<AUDIO_CD>::from_reference_time(-1, Rounding::Down)?.get()
+        );
+
+        assert!(FramesDelta::<AUDIO_CD>::from_reference_time(i64::MIN, Rounding::Down).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration() -> Result<(), OverflowError> {
+        let ten_ms = Duration::from_millis(10);
+        assert_eq!(
+            100_000,
+            reference_time_from_duration(ten_ms, Rounding::Down)?
+        );
+        assert_eq!(Ok(ten_ms), reference_time_to_duration(100_000));
+
+        let dur = Duration::from_nanos(150);
+        assert_eq!(1, reference_time_from_duration(dur, Rounding::Down)?);
+        assert_eq!(2, reference_time_from_duration(dur, Rounding::Up)?);
+        assert_eq!(2, reference_time_from_duration(dur, Rounding::Nearest)?);
+
+        assert_eq!(
+            Ok(Duration::new(922_337_203_685, 477_580_700)),
+            reference_time_to_duration(i64::MAX)
+        );
+        assert_eq!(
+            i64::MAX,
+            reference_time_from_duration(
+                Duration::new(922_337_203_685, 477_580_700),
+                Rounding::Down
+            )?
+        );
+        assert!(reference_time_from_duration(Duration::MAX, Rounding::Down).is_err());
+        assert_eq!(
+            Err(ReferenceTimeError::Negative(-1)),
+            reference_time_to_duration(-1)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use core::num::NonZeroU32;
+
+use crate::{Frames, OverflowError, System};
+
+/// The size of the chunks on one side of a resampler, when the chunks on the
+/// other side have a fixed size.
+///
+/// See [`ResampleCounter::input_chunk`] and
+/// [`ResampleCounter::output_chunk`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ChunkSize<const SYS: System> {
+    /// Every chunk has this size, since the ratio between the sample rates
+    /// makes it whole.
+    Exact(Frames<SYS>),
+    /// Chunks vary between these two (consecutive) sizes, so that their total
+    /// size stays exact.
+    Varying { min: Frames<SYS>, max: Frames<SYS> },
+}
+
+impl<const SYS: System> ChunkSize<SYS> {
+    /// The smallest chunk.
+    #[inline]
+    pub const fn min(&self) -> Frames<SYS> {
+        match *self {
+            Self::Exact(frames) | Self::Varying { min: frames, .. } => frames,
+        }
+    }
+
+    /// The largest chunk, e.g. for allocating buffers.
+    #[inline]
+    pub const fn max(&self) -> Frames<SYS> {
+        match *self {
+            Self::Exact(frames) | Self::Varying { max: frames, .. } => frames,
+        }
+    }
+
+    /// `n * num / den` frames, or its floor and ceiling if it isn't whole.
+    #[inline]
+    const fn scaled(n: usize, num: u128, den: u128) -> Result<Self, OverflowError> {
+        let scaled = n as u128 * num;
+        let (quot, rem) = (scaled / den, scaled % den);
+
+        if rem == 0 && quot <= usize::MAX as u128 {
+            Ok(Self::Exact(Frames::new(quot as usize)))
+        } else if quot < usize::MAX as u128 {
+            Ok(Self::Varying {
+                min: Frames::new(quot as usize),
+                max: Frames::new(quot as usize + 1),
+            })
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+}
+
+/// Keeps track of the number of output frames produced when resampling a
+/// stream from system `SRC` to system `DST` in chunks.
+///
+/// When the ratio between the sample rates isn't whole (e.g. 44.1 kHz to
+/// 48 kHz), a chunk of input frames generally corresponds to a fractional
+/// number of output frames. The counter carries the fraction over between
+/// chunks, so that the total output over any sequence of
+/// [`push`](ResampleCounter::push)es always equals the (truncated) exact
+/// conversion of the total input.
+#[derive(Clone, Debug)]
+pub struct ResampleCounter<const SRC: System, const DST: System> {
+    /// Pending output, in units of `1 / den` output frames. Always less than
+    /// `den` after a [`push`](ResampleCounter::push), and less than `num` after
+    /// a [`pull`](ResampleCounter::pull).
+    remainder: u64,
+}
+
+impl<const SRC: System, const DST: System> Default for ResampleCounter<SRC, DST> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SRC: System, const DST: System> ResampleCounter<SRC, DST> {
+    /// `(DST.sample_rate / SRC.sample_rate)` in lowest terms.
+    const RATIO: (NonZeroU32, NonZeroU32) = DST.resample_ratio(&SRC);
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self { remainder: 0 }
+    }
+
+    /// The size of the input chunks needed to produce `output` frames per
+    /// chunk, e.g. for a fixed-output resampler such as rubato's
+    /// `SincFixedOut`.
+    ///
+    /// If the size varies, [`pull`](ResampleCounter::pull) gives the exact
+    /// size of each chunk, which stays within this range as long as the stream
+    /// is resampled in fixed-output mode.
+    #[inline]
+    pub const fn input_chunk(output: Frames<DST>) -> Result<ChunkSize<SRC>, OverflowError> {
+        let (num, den) = (Self::RATIO.0.get() as u128, Self::RATIO.1.get() as u128);
+        ChunkSize::scaled(output.get(), den, num)
+    }
+
+    /// The size of the output chunks produced from `input` frames per chunk,
+    /// e.g. for a fixed-input resampler such as rubato's `SincFixedIn`.
+    ///
+    /// If the size varies, [`output_for`](ResampleCounter::output_for) gives
+    /// the exact size of the next chunk.
+    #[inline]
+    pub const fn output_chunk(input: Frames<SRC>) -> Result<ChunkSize<DST>, OverflowError> {
+        let (num, den) = (Self::RATIO.0.get() as u128, Self::RATIO.1.get() as u128);
+        ChunkSize::scaled(input.get(), num, den)
+    }
+
+    /// The number of whole output frames that
+    /// [`push`](ResampleCounter::push)ing `input` frames would complete,
+    /// without pushing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of output frames overflows.
+    #[inline]
+    #[track_caller]
+    pub fn output_for(&self, input: Frames<SRC>) -> Frames<DST> {
+        self.clone().push(input)
+    }
+
+    /// The number of input frames that [`pull`](ResampleCounter::pull)ing
+    /// `output` frames would return, without pulling them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of input frames overflows.
+    #[inline]
+    #[track_caller]
+    pub fn input_for(&self, output: Frames<DST>) -> Frames<SRC> {
+        self.clone().pull(output)
+    }
+
+    /// Account for `output` frames in fixed-output mode, returning the
+    /// smallest number of input frames needed to complete them (in addition to
+    /// any pending output), i.e. the size of the next input chunk.
+    ///
+    /// When upsampling, the last of those input frames may complete more
+    /// output than was pulled, which is then pending.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of input frames overflows.
+    #[inline]
+    #[track_caller]
+    pub fn pull(&mut self, output: Frames<DST>) -> Frames<SRC> {
+        let (num, den) = (Self::RATIO.0.get() as u128, Self::RATIO.1.get() as u128);
+        let output = output.get() as u128 * den;
+        let input = output.saturating_sub(self.remainder as u128).div_ceil(num);
+
+        self.remainder = (self.remainder as u128 + input * num - output) as u64;
+        Frames::new(
+            input
+                .try_into()
+                .expect("Overflowed trying to count input frames"),
+        )
+    }
+
+    /// Account for `input` frames, returning the number of whole output frames
+    /// they complete.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of output frames overflows.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, input: Frames<SRC>) -> Frames<DST> {
+        let (num, den) = (Self::RATIO.0.get() as u128, Self::RATIO.1.get() as u128);
+        let total = self.remainder as u128 + input.get() as u128 * num;
+
+        self.remainder = (total % den) as u64;
+        Frames::new(
+            (total / den)
+                .try_into()
+                .expect("Overflowed trying to count output frames"),
+        )
+    }
+
+    /// The pending output, as a `(numerator, denominator)` pair of output
+    /// frames. This is less than a whole frame, unless output was
+    /// [`pull`](ResampleCounter::pull)ed while upsampling.
+    #[inline]
+    pub const fn remainder(&self) -> (u32, NonZeroU32) {
+        (self.remainder as u32, Self::RATIO.1)
+    }
+
+    /// End the stream, returning the pending output rounded up to whole frames
+    /// (i.e. a single output frame if any fraction of one is pending), and
+    /// [reset](ResampleCounter::reset) the counter.
+    #[inline]
+    pub fn flush(&mut self) -> Frames<DST> {
+        let pending = self.remainder.div_ceil(Self::RATIO.1.get() as u64);
+        self.reset();
+        Frames::new(pending as usize)
+    }
+
+    /// Discard any pending fraction of an output frame.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.remainder = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, Rounding, AUDIO_CD};
+
+    const DAT: System = system!(48_000, Stereo, f32);
+
+    #[test]
+    fn test_exact_total() {
+        let mut counter = ResampleCounter::<AUDIO_CD, DAT>::new();
+        let (mut input, mut output) = (0, 0);
+
+        // simple LCG, for deterministic "random" chunk sizes
+        let mut seed = 0x2545_f491_u64;
+        for _ in 0..1_000 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let chunk = (seed >> 33) as usize % 4_096;
+
+            input += chunk;
+            output += counter.push(Frames::new(chunk)).get();
+
+            assert_eq!(
+                Frames::<AUDIO_CD>::new(input)
+                    .convert_to::<DAT>(Rounding::Down)
+                    .unwrap()
+                    .get(),
+                output
+            );
+        }
+
+        let (rem, den) = counter.remainder();
+        assert_eq!(147, den.get());
+        assert_eq!((input * 160 % 147) as u32, rem);
+    }
+
+    /// Resample `chunks` fixed-size chunks of `n` frames in fixed-input and
+    /// fixed-output modes, checking that the other side's chunks stay in range
+    /// and that the totals stay exact.
+    fn check_chunks<const SRC: System, const DST: System>(n: usize, chunks: usize) {
+        let fixed_in = ResampleCounter::<SRC, DST>::output_chunk(Frames::new(n)).unwrap();
+        let mut counter = ResampleCounter::<SRC, DST>::new();
+        let mut output = 0;
+        for i in 1..=chunks {
+            let predicted = counter.output_for(Frames::new(n));
+            let chunk = counter.push(Frames::new(n));
+            assert_eq!(predicted, chunk);
+            assert!((fixed_in.min()..=fixed_in.max()).contains(&chunk));
+
+            output += chunk.get();
+            let exact = Frames::<SRC>::new(i * n).convert_to::<DST>(Rounding::Down);
+            assert_eq!(exact.unwrap().get(), output);
+        }
+
+        let fixed_out = ResampleCounter::<SRC, DST>::input_chunk(Frames::new(n)).unwrap();
+        let mut counter = ResampleCounter::<SRC, DST>::new();
+        let mut input = 0;
+        for i in 1..=chunks {
+            let predicted = counter.input_for(Frames::new(n));
+            let chunk = counter.pull(Frames::new(n));
+            assert_eq!(predicted, chunk);
+            assert!((fixed_out.min()..=fixed_out.max()).contains(&chunk));
+
+            input += chunk.get();
+            let exact = Frames::<DST>::new(i * n).convert_to::<SRC>(Rounding::Up);
+            assert_eq!(exact.unwrap().get(), input);
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        type Up = ResampleCounter<AUDIO_CD, DAT>;
+        type Down = ResampleCounter<DAT, AUDIO_CD>;
+
+        assert_eq!(
+            ChunkSize::Varying {
+                min: Frames::new(1_114),
+                max: Frames::new(1_115)
+            },
+            Up::output_chunk(Frames::new(1_024)).unwrap()
+        );
+        assert_eq!(
+            ChunkSize::Varying {
+                min: Frames::new(940),
+                max: Frames::new(941)
+            },
+            Up::input_chunk(Frames::new(1_024)).unwrap()
+        );
+        assert_eq!(
+            ChunkSize::Exact(Frames::new(147)),
+            Up::input_chunk(Frames::new(160)).unwrap()
+        );
+        assert_eq!(
+            ChunkSize::Exact(Frames::new(1_470)),
+            Down::output_chunk(Frames::new(1_600)).unwrap()
+        );
+        assert!(Up::output_chunk(Frames::new(usize::MAX)).is_err());
+
+        for n in [1, 147, 160, 441, 480, 1_024] {
+            check_chunks::<AUDIO_CD, DAT>(n, 500);
+            check_chunks::<DAT, AUDIO_CD>(n, 500);
+        }
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut counter = ResampleCounter::<AUDIO_CD, DAT>::new();
+        assert_eq!(Frames::new(1), counter.push(Frames::new(1)));
+        assert_eq!((13, NonZeroU32::new(147).unwrap()), counter.remainder());
+        assert_eq!(Frames::new(1), counter.flush());
+        assert_eq!(0, counter.remainder().0);
+        assert_eq!(Frames::new(0), counter.flush());
+
+        // downsampling
+        let mut counter = ResampleCounter::<DAT, AUDIO_CD>::new();
+        assert_eq!(Frames::new(0), counter.push(Frames::new(1)));
+        assert_eq!(Frames::new(147), counter.push(Frames::new(159)));
+        counter.reset();
+        assert_eq!(Frames::new(0), counter.flush());
+    }
+}
@@ -0,0 +1,302 @@
+//! Zero-copy archiving of spans and sample rates using rkyv.
+//!
+//! Each archived type is a transparent wrapper around rkyv's archived form of
+//! the inner integer (so e.g. an [`ArchivedFrames`] has the same layout as an
+//! [`ArchivedUsize`]). When validating untrusted archives, the invariants of
+//! [`Samples`] and [`Bytes`] are re-checked.
+//!
+//! Like other `usize`s, spans are archived as [`FixedUsize`]s, whose width is
+//! chosen by rkyv's `pointer_width_*` features (32 bits by default). Unlike
+//! rkyv's own `usize` impl, serializing a span that does not fit in one fails
+//! with an [`OverflowError`] instead of truncating it.
+
+use core::{fmt, num::NonZeroU32};
+
+use ::rkyv::{
+    bytecheck::CheckBytes,
+    primitive::{ArchivedNonZeroU32, ArchivedUsize, FixedUsize},
+    rancor::{Fallible, Source},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+use crate::{Bytes, Frames, MisalignedError, OverflowError, SampleRate, Samples, System};
+
+macro_rules! archive_span {
+    ($(#[$attr:meta])* $name:ident, $archived:ident, $divisor:expr) => {
+        $(#[$attr])*
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $archived<const SYS: System>(ArchivedUsize);
+
+        impl<const SYS: System> $archived<SYS> {
+            #[inline]
+            pub const fn get(&self) -> usize {
+                self.0.to_native() as usize
+            }
+        }
+
+        impl<const SYS: System> fmt::Debug for $archived<SYS> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.get(), f)
+            }
+        }
+
+        // SAFETY: a transparent wrapper around a portable type
+        unsafe impl<const SYS: System> Portable for $archived<SYS> {}
+
+        // SAFETY: the inner integer is valid for any bit pattern, and the
+        // span's invariant is checked before returning `Ok`
+        unsafe impl<C, const SYS: System> CheckBytes<C> for $archived<SYS>
+        where
+            C: Fallible + ?Sized,
+            C::Error: Source,
+        {
+            unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+                let inner = value.cast::<ArchivedUsize>();
+                ArchivedUsize::check_bytes(inner, context)?;
+
+                let divisor: usize = $divisor;
+                match (*inner).to_native() as usize % divisor {
+                    0 => Ok(()),
+                    remainder => Err(C::Error::new(MisalignedError { remainder })),
+                }
+            }
+        }
+
+        impl<const SYS: System> Archive for $name<SYS> {
+            type Archived = $archived<SYS>;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+                // SAFETY: `$archived` is a transparent wrapper around `ArchivedUsize`
+                let out = unsafe { out.cast_unchecked::<ArchivedUsize>() };
+                self.get().resolve(resolver, out);
+            }
+        }
+
+        /// Fails if the span does not fit in a [`FixedUsize`].
+        impl<S, const SYS: System> Serialize<S> for $name<SYS>
+        where
+            S: Fallible + ?Sized,
+            S::Error: Source,
+        {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                match FixedUsize::try_from(self.get()) {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(S::Error::new(OverflowError(()))),
+                }
+            }
+        }
+    };
+}
+
+archive_span!(
+    /// An archived [`Frames`].
+    Frames,
+    ArchivedFrames,
+    1
+);
+
+archive_span!(
+    /// An archived [`Samples`].
+    ///
+    /// Validation fails if the number of samples is not divisible by the number
+    /// of channels.
+    Samples,
+    ArchivedSamples,
+    SYS.channel_layout.channels().get() as usize
+);
+
+archive_span!(
+    /// An archived [`Bytes`].
+    ///
+    /// Validation fails if the number of bytes is not divisible by the size of
+    /// a frame.
+    Bytes,
+    ArchivedBytes,
+    SYS.frame_size().get() as usize
+);
+
+impl<D: Fallible + ?Sized, const SYS: System> Deserialize<Frames<SYS>, D> for ArchivedFrames<SYS> {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Frames<SYS>, D::Error> {
+        Ok(Frames::new(self.get()))
+    }
+}
+
+/// Fails for archives that weren't validated and hold an invalid number of
+/// samples.
+impl<D, const SYS: System> Deserialize<Samples<SYS>, D> for ArchivedSamples<SYS>
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Samples<SYS>, D::Error> {
+        let channels = SYS.channel_layout.channels().get() as usize;
+        Samples::new(self.get()).ok_or_else(|| {
+            D::Error::new(MisalignedError {
+                remainder: self.get() % channels,
+            })
+        })
+    }
+}
+
+/// Fails for archives that weren't validated and hold an invalid number of
+/// bytes.
+impl<D, const SYS: System> Deserialize<Bytes<SYS>, D> for ArchivedBytes<SYS>
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Bytes<SYS>, D::Error> {
+        let frame_size = SYS.frame_size().get() as usize;
+        Bytes::new(self.get()).ok_or_else(|| {
+            D::Error::new(MisalignedError {
+                remainder: self.get() % frame_size,
+            })
+        })
+    }
+}
+
+/// An archived [`SampleRate`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, CheckBytes)]
+#[bytecheck(crate = ::rkyv::bytecheck)]
+#[repr(transparent)]
+pub struct ArchivedSampleRate(ArchivedNonZeroU32);
+
+impl ArchivedSampleRate {
+    #[inline]
+    pub const fn get(&self) -> NonZeroU32 {
+        self.0.to_native()
+    }
+}
+
+impl fmt::Debug for ArchivedSampleRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+// SAFETY: a transparent wrapper around a portable type
+unsafe impl Portable for ArchivedSampleRate {}
+
+impl Archive for SampleRate {
+    type Archived = ArchivedSampleRate;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `ArchivedSampleRate` is a transparent wrapper around
+        // `ArchivedNonZeroU32`
+        let out = unsafe { out.cast_unchecked::<ArchivedNonZeroU32>() };
+        self.get().resolve(resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for SampleRate {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<SampleRate, D> for ArchivedSampleRate {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<SampleRate, D::Error> {
+        Ok(SampleRate::new(self.get()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rkyv::{rancor::Error, vec::ArchivedVec};
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[test]
+    fn test_archive() -> Result<(), Error> {
+        let frames: Vec<_> = (0..1_000).map(Frames::<AUDIO_CD>::new).collect();
+        let bin = ::rkyv::to_bytes::<Error>(&frames)?;
+        let archived = ::rkyv::access::<ArchivedVec<ArchivedFrames<AUDIO_CD>>, Error>(&bin)?;
+        assert_eq!(1_000, archived.len());
+        assert_eq!(999, archived[999].get());
+        assert_eq!(
+            frames,
+            ::rkyv::deserialize::<Vec<Frames<_>>, Error>(archived)?
+        );
+
+        let bytes: Vec<_> = (0..1_000)
+            .map(|n| Bytes::<AUDIO_CD>::new(n * 4).unwrap())
+            .collect();
+        let bin = ::rkyv::to_bytes::<Error>(&bytes)?;
+        let archived = ::rkyv::access::<ArchivedVec<ArchivedBytes<AUDIO_CD>>, Error>(&bin)?;
+        assert_eq!(3_996, archived[999].get());
+        assert_eq!(
+            bytes,
+            ::rkyv::deserialize::<Vec<Bytes<_>>, Error>(archived)?
+        );
+
+        let samples = vec![Samples::<AUDIO_CD>::new(2).unwrap()];
+        let bin = ::rkyv::to_bytes::<Error>(&samples)?;
+        let archived = ::rkyv::access::<ArchivedVec<ArchivedSamples<AUDIO_CD>>, Error>(&bin)?;
+        assert_eq!(
+            samples,
+            ::rkyv::deserialize::<Vec<Samples<_>>, Error>(archived)?
+        );
+
+        let rates = vec![SampleRate::HZ_44_1K, SampleRate::HZ_48K];
+        let bin = ::rkyv::to_bytes::<Error>(&rates)?;
+        let archived = ::rkyv::access::<ArchivedVec<ArchivedSampleRate>, Error>(&bin)?;
+        assert_eq!(48_000, archived[1].get().get());
+        assert_eq!(
+            rates,
+            ::rkyv::deserialize::<Vec<SampleRate>, Error>(archived)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> Result<(), Error> {
+        let bytes = vec![
+            Bytes::<AUDIO_CD>::new(4).unwrap(),
+            Bytes::<AUDIO_CD>::new(8).unwrap(),
+        ];
+        let mut bin = ::rkyv::to_bytes::<Error>(&bytes)?;
+        // the elements are archived first, as little-endian integers
+        assert_eq!(4, bin[0]);
+        bin[0] = 5;
+
+        assert!(::rkyv::access::<ArchivedVec<ArchivedBytes<AUDIO_CD>>, Error>(&bin).is_err());
+        // frames have no such invariant
+        let frames = ::rkyv::access::<ArchivedVec<ArchivedFrames<AUDIO_CD>>, Error>(&bin)?;
+        assert_eq!(5, frames[0].get());
+
+        let mut bin = ::rkyv::to_bytes::<Error>(&vec![SampleRate::HZ_8K])?;
+        bin[..4].fill(0);
+        assert!(::rkyv::access::<ArchivedVec<ArchivedSampleRate>, Error>(&bin).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> Result<(), Error> {
+        let max = FixedUsize::MAX as usize;
+        let bin = ::rkyv::to_bytes::<Error>(&vec![Frames::<AUDIO_CD>::new(max)])?;
+        let archived = ::rkyv::access::<ArchivedVec<ArchivedFrames<AUDIO_CD>>, Error>(&bin)?;
+        assert_eq!(max, archived[0].get());
+
+        if let Some(n) = max.checked_add(1) {
+            assert!(::rkyv::to_bytes::<Error>(&vec![Frames::<AUDIO_CD>::new(n)]).is_err());
+            let bytes = Bytes::<AUDIO_CD>::new(max + 1).unwrap();
+            assert!(::rkyv::to_bytes::<Error>(&vec![bytes]).is_err());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+//! Measuring [`rodio`] sources in frames, samples and bytes.
+//!
+//! Note that enabling the `rodio` feature pulls in [`cpal`](https://docs.rs/cpal)
+//! (and ALSA on Linux), which rodio 0.20 depends on unconditionally.
+
+use core::num::{NonZeroU32, NonZeroU8};
+
+use ::rodio::{Sample, Source};
+
+use crate::{
+    ChannelLayout, DynBytes, DynFrames, DynSamples, DynSystem, MisalignedError, SampleRate,
+    SampleType,
+};
+
+/// Extension methods for measuring the length of a [`Source`] using this
+/// crate's exact conversions.
+///
+/// Implemented for all sources whose samples are also
+/// [`audio_core::Sample`]s (i.e. `i16`, `u16` and `f32`).
+pub trait SourceTimeExt: Source
+where
+    Self::Item: Sample,
+{
+    /// The system of this source's samples, at its current sample rate and
+    /// number of channels, or `None` if either is zero (or there are too many
+    /// channels).
+    fn system(&self) -> Option<DynSystem>;
+
+    /// The number of frames in this source's
+    /// [total duration](Source::total_duration), truncated to a whole frame, or
+    /// `None` if the duration is unknown (or infinite).
+    fn frames_len(&self) -> Option<DynFrames> {
+        let dur = self.total_duration()?;
+        DynFrames::try_from_duration(dur, self.system()?).ok()
+    }
+
+    /// The number of samples (across all channels) in this source's
+    /// [`frames_len`](SourceTimeExt::frames_len).
+    fn samples_len(&self) -> Option<DynSamples> {
+        self.frames_len()?.try_into_samples().ok()
+    }
+
+    /// The number of bytes needed to hold this source's
+    /// [`frames_len`](SourceTimeExt::frames_len) once decoded into samples of
+    /// type `S`.
+    fn bytes_len<S: audio_core::Sample + 'static>(&self) -> Option<DynBytes> {
+        let system = DynSystem {
+            sample_type: SampleType::new::<S>(),
+            ..self.system()?
+        };
+
+        DynFrames::new(self.frames_len()?.get(), system)
+            .try_into_bytes()
+            .ok()
+    }
+
+    /// Checks that this source's
+    /// [`current_frame_len`](Source::current_frame_len), which is measured in
+    /// samples, is a whole number of frames.
+    ///
+    /// Sources without a current frame length always pass.
+    fn validate_frame_len(&self) -> Result<(), MisalignedError> {
+        let Some(len) = self.current_frame_len() else {
+            return Ok(());
+        };
+
+        match len.checked_rem(self.channels() as usize) {
+            Some(0) => Ok(()),
+            remainder => Err(MisalignedError {
+                remainder: remainder.unwrap_or(len),
+            }),
+        }
+    }
+}
+
+impl<S: Source> SourceTimeExt for S
+where
+    S::Item: Sample + audio_core::Sample + 'static,
+{
+    fn system(&self) -> Option<DynSystem> {
+        let sample_rate = NonZeroU32::new(self.sample_rate())?;
+        let channels = NonZeroU8::new(u8::try_from(self.channels()).ok()?)?;
+
+        Some(DynSystem {
+            sample_rate: SampleRate::new(sample_rate),
+            channel_layout: ChannelLayout::from_channels(channels),
+            sample_type: SampleType::new::<S::Item>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    /// A source of `len` silent samples, in frames of `frame_len` samples.
+    struct Silence {
+        len: usize,
+        frame_len: Option<usize>,
+        channels: u16,
+        sample_rate: u32,
+    }
+
+    impl Iterator for Silence {
+        type Item = i16;
+
+        fn next(&mut self) -> Option<i16> {
+            self.len = self.len.checked_sub(1)?;
+            Some(0)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl Source for Silence {
+        fn current_frame_len(&self) -> Option<usize> {
+            self.frame_len
+        }
+
+        fn channels(&self) -> u16 {
+            self.channels
+        }
+
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            let frames = (self.len / self.channels.max(1) as usize) as u64;
+            Some(Duration::from_nanos(
+                frames * 1_000_000_000 / self.sample_rate as u64,
+            ))
+        }
+    }
+
+    const CD: Silence = Silence {
+        len: 44_100,
+        frame_len: Some(1_024),
+        channels: 2,
+        sample_rate: 44_100,
+    };
+
+    #[test]
+    fn test_lengths() {
+        let source = CD;
+        assert!(source.system().unwrap().is::<AUDIO_CD>());
+        assert_eq!(22_050, source.frames_len().unwrap().get());
+        assert_eq!(
+            (source.len, Some(source.len)),
+            (source.samples_len().unwrap().get(), source.size_hint().1)
+        );
+        assert_eq!(88_200, source.bytes_len::<i16>().unwrap().get());
+        assert_eq!(176_400, source.bytes_len::<f32>().unwrap().get());
+
+        assert_eq!(44_100, source.count());
+
+        let source = Silence { channels: 0, ..CD };
+        assert_eq!(None, source.system());
+        assert_eq!(None, source.frames_len());
+    }
+
+    #[test]
+    fn test_validate_frame_len() {
+        assert_eq!(Ok(()), CD.validate_frame_len());
+        assert_eq!(
+            Ok(()),
+            Silence {
+                frame_len: None,
+                ..CD
+            }
+            .validate_frame_len()
+        );
+        assert_eq!(
+            Err(MisalignedError { remainder: 1 }),
+            Silence {
+                frame_len: Some(1_023),
+                ..CD
+            }
+            .validate_frame_len()
+        );
+        assert_eq!(
+            Err(MisalignedError { remainder: 1_024 }),
+            Silence { channels: 0, ..CD }.validate_frame_len()
+        );
+    }
+}
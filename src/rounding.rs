@@ -0,0 +1,60 @@
+/// The direction in which to round a conversion whose result is not a whole
+/// number of units.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Rounding {
+    /// Round towards zero.
+    Down,
+    /// Round away from zero.
+    Up,
+    /// Round to the nearest whole unit, with halfway cases rounded up.
+    Nearest,
+}
+
+impl Rounding {
+    /// Divide `n` by `d`, rounding the quotient in this direction.
+    #[inline]
+    pub(crate) const fn div(self, n: u128, d: u128) -> u128 {
+        let (quot, rem) = (n / d, n % d);
+
+        match self {
+            Self::Down => quot,
+            Self::Up if rem != 0 => quot + 1,
+            Self::Up => quot,
+            Self::Nearest if rem >= d - rem => quot + 1,
+            Self::Nearest => quot,
+        }
+    }
+
+    /// Round a non-negative float to a whole number in this direction.
+    #[inline]
+    pub(crate) fn round(self, n: f64) -> f64 {
+        match self {
+            Self::Down => n.floor(),
+            Self::Up => n.ceil(),
+            Self::Nearest => n.round(),
+        }
+    }
+}
+
+/// Greatest common divisor of `a` and `b`.
+pub(crate) const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div() {
+        assert_eq!(3, Rounding::Down.div(7, 2));
+        assert_eq!(4, Rounding::Up.div(7, 2));
+        assert_eq!(4, Rounding::Nearest.div(7, 2));
+        assert_eq!(2, Rounding::Nearest.div(7, 3));
+        assert_eq!(3, Rounding::Up.div(6, 2));
+        assert_eq!(3, Rounding::Nearest.div(6, 2));
+    }
+}
@@ -0,0 +1,361 @@
+//! RTP timestamps ([RFC 3550](https://www.rfc-editor.org/rfc/rfc3550)).
+
+use core::time::Duration;
+
+use crate::{
+    convert::frames_to_bytes, sample_rate::NANOS_PER_SEC, Bytes, Frames, FramesDelta,
+    NotIntegralError, OverflowError, Rounding, System,
+};
+
+/// An RTP media timestamp: a 32-bit counter of frames (in units of the clock
+/// rate, which for audio is the sample rate) which wraps around on overflow.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct RtpTimestamp<const SYS: System>(u32);
+
+impl<const SYS: System> RtpTimestamp<SYS> {
+    #[inline]
+    pub const fn new(n: u32) -> Self {
+        Self(n)
+    }
+
+    #[inline]
+    pub const fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// The number of frames elapsed since `earlier`, using modular arithmetic:
+    /// differences of 2³¹ or more are interpreted as `earlier` actually being
+    /// the later of the two timestamps.
+    #[inline]
+    pub const fn diff(&self, earlier: &Self) -> FramesDelta<SYS> {
+        FramesDelta::new(self.get().wrapping_sub(earlier.get()) as i32 as isize)
+    }
+
+    /// Advance this timestamp by `frames`, wrapping around on overflow.
+    #[inline]
+    pub const fn wrapping_add(self, frames: Frames<SYS>) -> Self {
+        Self::new(self.get().wrapping_add(frames.get() as u32))
+    }
+}
+
+impl<const SYS: System> From<u32> for RtpTimestamp<SYS> {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const SYS: System> From<RtpTimestamp<SYS>> for u32 {
+    #[inline]
+    fn from(value: RtpTimestamp<SYS>) -> Self {
+        value.get()
+    }
+}
+
+/// Extends successive (wrapping) [`RtpTimestamp`]s into a 64-bit position.
+///
+/// The first timestamp is extended to its own value, and every following one
+/// is placed relative to the latest timestamp seen so far (see
+/// [`RtpTimestamp::diff`]), so timestamps that arrive out of order are still
+/// extended correctly.
+#[derive(Clone, Debug, Default)]
+pub struct Unwrapper<const SYS: System> {
+    latest: Option<(RtpTimestamp<SYS>, u64)>,
+}
+
+impl<const SYS: System> Unwrapper<SYS> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { latest: None }
+    }
+
+    /// Extend `timestamp` into a position in frames.
+    ///
+    /// A timestamp that appears to precede the very first one by more than its
+    /// own value is clamped to position 0.
+    pub fn extend(&mut self, timestamp: RtpTimestamp<SYS>) -> u64 {
+        let position = match self.latest {
+            None => timestamp.get() as u64,
+            Some((latest, position)) => {
+                position.saturating_add_signed(timestamp.diff(&latest).get() as i64)
+            }
+        };
+
+        match self.latest {
+            Some((_, latest)) if latest >= position => {}
+            _ => self.latest = Some((timestamp, position)),
+        }
+
+        position
+    }
+
+    /// Equivalent to [`extend`](Unwrapper::extend), returning [`Frames`].
+    pub fn extend_frames(
+        &mut self,
+        timestamp: RtpTimestamp<SYS>,
+    ) -> Result<Frames<SYS>, OverflowError> {
+        let position = self.extend(timestamp);
+        usize::try_from(position)
+            .map(Frames::new)
+            .map_err(|_| OverflowError(()))
+    }
+}
+
+/// The layout of RTP packets carrying raw audio in `SYS` (e.g. G.711), for a
+/// negotiated packet time (`ptime`).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PacketLayout<const SYS: System> {
+    pub frames_per_packet: Frames<SYS>,
+    /// The size of each packet's payload.
+    pub bytes_per_packet: Bytes<SYS>,
+    /// The number of packets sent each second, rounded up if a second isn't a
+    /// whole number of packets (e.g. 34 for a 30ms packet time).
+    pub packets_per_second: u32,
+}
+
+/// Error returned when laying out RTP packets for a packet time.
+#[derive(thiserror::Error, Debug)]
+pub enum PacketLayoutError {
+    #[error(transparent)]
+    NotIntegral(#[from] NotIntegralError),
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+/// The layout of packets of `ptime` worth of audio in `SYS`.
+///
+/// Fails with [`PacketLayoutError::NotIntegral`] if `ptime` is zero or isn't a
+/// whole number of frames (e.g. 2.5ms at 44.1 kHz), and with
+/// [`PacketLayoutError::Overflow`] if a packet's payload doesn't fit in a
+/// `usize`.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use audio_time::{rtp::packet_layout, AUDIO_CD};
+/// #
+/// let layout = packet_layout::<AUDIO_CD>(Duration::from_millis(20)).unwrap();
+/// assert_eq!(882, layout.frames_per_packet.get());
+/// assert_eq!(3_528, layout.bytes_per_packet.get());
+/// assert_eq!(50, layout.packets_per_second);
+/// ```
+pub const fn packet_layout<const SYS: System>(
+    ptime: Duration,
+) -> Result<PacketLayout<SYS>, PacketLayoutError> {
+    let rate = SYS.sample_rate.get().get() as u128;
+    let frames = SYS.sample_rate.periods_in(ptime);
+
+    if frames == 0 || frames != SYS.sample_rate.periods_in_rounded(ptime, Rounding::Up) {
+        return Err(PacketLayoutError::NotIntegral(NotIntegralError(())));
+    }
+    if frames > usize::MAX as u128 {
+        return Err(PacketLayoutError::Overflow(OverflowError(())));
+    }
+
+    let frames_per_packet = Frames::new(frames as usize);
+    let bytes_per_packet = match frames_to_bytes(frames_per_packet) {
+        Ok(bytes) => bytes,
+        Err(err) => return Err(PacketLayoutError::Overflow(err)),
+    };
+
+    Ok(PacketLayout {
+        frames_per_packet,
+        bytes_per_packet,
+        packets_per_second: Rounding::Up.div(rate, frames) as u32,
+    })
+}
+
+impl<const SYS: System> PacketLayout<SYS> {
+    /// The packet time.
+    #[inline]
+    pub const fn ptime(&self) -> Duration {
+        SYS.sample_rate
+            .duration_of(self.frames_per_packet.get() as u64)
+    }
+
+    /// The total bandwidth of the stream in bits per second (rounded up),
+    /// including `overhead` bytes of headers per packet (e.g. 40 for RTP, UDP
+    /// and IPv4).
+    #[inline]
+    pub const fn bandwidth(&self, overhead: u32) -> u64 {
+        let bits = (self.bytes_per_packet.get() as u128 + overhead as u128) * 8;
+        let bps = Rounding::Up.div(
+            bits * SYS.sample_rate.get().get() as u128,
+            self.frames_per_packet.get() as u128,
+        );
+
+        if bps <= u64::MAX as u128 {
+            bps as u64
+        } else {
+            u64::MAX
+        }
+    }
+
+    /// The number of packets needed to send `dur` worth of audio, with a
+    /// trailing partial packet either dropped ([`Rounding::Down`]), padded
+    /// into a whole packet ([`Rounding::Up`]), or whichever is closer
+    /// ([`Rounding::Nearest`]).
+    #[inline]
+    pub const fn packets_for(
+        &self,
+        dur: Duration,
+        rounding: Rounding,
+    ) -> Result<u64, OverflowError> {
+        let packets = rounding.div(
+            dur.as_nanos() * SYS.sample_rate.get().get() as u128,
+            self.frames_per_packet.get() as u128 * NANOS_PER_SEC as u128,
+        );
+
+        if packets <= u64::MAX as u128 {
+            Ok(packets as u64)
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::system;
+
+    const SYS: System = system!(48_000, Mono, i16);
+
+    #[test]
+    fn test_diff() {
+        let before = RtpTimestamp::<SYS>::new(u32::MAX - 479);
+        let after = before.wrapping_add(Frames::new(960));
+        assert_eq!(480, after.get());
+
+        assert_eq!(FramesDelta::new(960), after.diff(&before));
+        assert_eq!(FramesDelta::new(-960), before.diff(&after));
+        assert_eq!(
+            Duration::from_millis(20),
+            after.diff(&before).abs_duration()
+        );
+        assert_eq!(
+            Duration::from_millis(20),
+            before.diff(&after).abs_duration()
+        );
+    }
+
+    #[test]
+    fn test_packet_layout() {
+        const G711: System = system!(8_000, Mono, u8);
+
+        let layout = packet_layout::<G711>(Duration::from_millis(20)).unwrap();
+        assert_eq!(160, layout.frames_per_packet.get());
+        assert_eq!(160, layout.bytes_per_packet.get());
+        assert_eq!(50, layout.packets_per_second);
+        assert_eq!(Duration::from_millis(20), layout.ptime());
+        // 64 kbps of payload, plus 40 bytes of headers per packet
+        assert_eq!(64_000, layout.bandwidth(0));
+        assert_eq!(80_000, layout.bandwidth(40));
+
+        let layout = packet_layout::<G711>(Duration::from_millis(30)).unwrap();
+        assert_eq!(240, layout.bytes_per_packet.get());
+        assert_eq!(34, layout.packets_per_second);
+        assert_eq!(74_667, layout.bandwidth(40));
+
+        let layout = packet_layout::<SYS>(Duration::from_millis(10)).unwrap();
+        assert_eq!(480, layout.frames_per_packet.get());
+        assert_eq!(960, layout.bytes_per_packet.get());
+        assert_eq!(100, layout.packets_per_second);
+
+        const CD: System = crate::AUDIO_CD;
+        let layout = packet_layout::<CD>(Duration::from_millis(20)).unwrap();
+        assert_eq!(882, layout.frames_per_packet.get());
+        assert_eq!(3_528, layout.bytes_per_packet.get());
+    }
+
+    #[test]
+    fn test_packet_layout_not_integral() {
+        const CD: System = crate::AUDIO_CD;
+
+        // 110.25 frames
+        assert!(matches!(
+            packet_layout::<CD>(Duration::from_micros(2_500)),
+            Err(PacketLayoutError::NotIntegral(_))
+        ));
+        assert!(matches!(
+            packet_layout::<CD>(Duration::ZERO),
+            Err(PacketLayoutError::NotIntegral(_))
+        ));
+        assert!(packet_layout::<CD>(Duration::MAX).is_err());
+        // a fraction of a nanosecond doesn't make a whole frame either
+        assert!(matches!(
+            packet_layout::<SYS>(Duration::from_nanos(20_000_001)),
+            Err(PacketLayoutError::NotIntegral(_))
+        ));
+    }
+
+    #[test]
+    fn test_packet_layout_overflow() {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        // a whole number of frames, whose payload doesn't fit in a `usize`
+        let ptime = Duration::from_secs(usize::MAX as u64 / 48_000 / 2);
+        assert!(matches!(
+            packet_layout::<SYS>(ptime),
+            Err(PacketLayoutError::Overflow(_))
+        ));
+        assert!(matches!(
+            packet_layout::<SYS>(Duration::from_secs(u64::MAX)),
+            Err(PacketLayoutError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_packets_for() -> Result<(), OverflowError> {
+        let layout = packet_layout::<SYS>(Duration::from_millis(20)).unwrap();
+
+        let dur = Duration::from_millis(1_010);
+        assert_eq!(50, layout.packets_for(dur, Rounding::Down)?);
+        assert_eq!(51, layout.packets_for(dur, Rounding::Up)?);
+        assert_eq!(51, layout.packets_for(dur, Rounding::Nearest)?);
+        assert_eq!(
+            50,
+            layout.packets_for(Duration::from_millis(1_009), Rounding::Nearest)?
+        );
+
+        let dur = Duration::from_secs(1);
+        for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+            assert_eq!(50, layout.packets_for(dur, rounding)?);
+        }
+        assert_eq!(0, layout.packets_for(Duration::ZERO, Rounding::Up)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unwrapper() {
+        let mut unwrapper = Unwrapper::<SYS>::new();
+        let mut timestamp = RtpTimestamp::<SYS>::new(u32::MAX - 10 * 960);
+        let start = timestamp.get() as u64;
+
+        // 20ms packets, crossing the wrap boundary several times
+        let packets = 3 * (1u64 << 32) / 960;
+        for packet in 0..packets {
+            assert_eq!(start + packet * 960, unwrapper.extend(timestamp));
+            timestamp = timestamp.wrapping_add(Frames::new(960));
+        }
+
+        // a packet that arrives late (after its successor) is still placed
+        // correctly, including across the wrap boundary
+        let mut unwrapper = Unwrapper::<SYS>::new();
+        let late = RtpTimestamp::<SYS>::new(u32::MAX - 100);
+        let early = late.wrapping_add(Frames::new(960));
+        assert_eq!(u32::MAX as u64 - 100, unwrapper.extend(late));
+        assert_eq!(u32::MAX as u64 + 860, unwrapper.extend(early));
+        assert_eq!(u32::MAX as u64 - 100, unwrapper.extend(late));
+        assert_eq!(
+            Frames::new(u32::MAX as usize + 1_820),
+            unwrapper
+                .extend_frames(early.wrapping_add(Frames::new(960)))
+                .unwrap()
+        );
+    }
+}
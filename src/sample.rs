@@ -1,7 +1,9 @@
-use std::{fmt, intrinsics::type_id, marker::ConstParamTy, mem::size_of};
+use core::{fmt, intrinsics::type_id, marker::ConstParamTy, mem::size_of};
 
 use nonzero_const_param::NonZeroU8;
 
+use crate::{I24, U24};
+
 /// A type used to encode a single sample, created from a type that implements
 /// [audio_core::Sample].
 ///
@@ -9,6 +11,14 @@ use nonzero_const_param::NonZeroU8;
 /// a const generic. On creation, it encodes the size of the sample type, which
 /// can be retrieved later using [`byte_depth`](SampleType::byte_depth).
 ///
+/// The number of meaningful bits in each sample defaults to the full width of
+/// the type, but can be narrowed using
+/// [`with_bit_depth`](SampleType::with_bit_depth), e.g. for 20-bit audio
+/// stored in 32-bit containers.
+///
+/// Packed 24-bit samples, which have no primitive Rust type, can be described
+/// using [`I24`](crate::I24) and [`U24`](crate::U24).
+///
 /// The struct also encodes the type's unique [`type_id`], so that two
 /// `SampleType`s created from different types with the same size are not
 /// equal, e.g.:
@@ -18,33 +28,615 @@ use nonzero_const_param::NonZeroU8;
 /// assert_eq!(SampleType::new::<i16>(), SampleType::new::<i16>());
 /// assert_ne!(SampleType::new::<i16>(), SampleType::new::<u16>());
 /// ```
+///
+/// Any type implementing [audio_core::Sample] can be used, including
+/// user-defined newtypes, which are distinct from the types they wrap:
+/// ```
+/// # use audio_time::{system, SampleType, System};
+/// #
+/// /// A Q15 fixed-point sample.
+/// #[derive(Clone, Copy)]
+/// #[repr(transparent)]
+/// struct Q15(i16);
+///
+/// // SAFETY: all-zeros is a valid (zero) Q15 value
+/// unsafe impl audio_core::Sample for Q15 {
+///     const ZERO: Self = Self(0);
+/// }
+///
+/// const SYS: System = system!(48_000, Stereo, Q15);
+/// assert_eq!(2, SYS.sample_type.byte_depth().get());
+/// assert_ne!(SampleType::new::<i16>(), SampleType::new::<Q15>());
+/// ```
+///
+/// Sample types are totally ordered, first by byte depth and then by bit
+/// depth. The order between different types of the same depths is unspecified
+/// (but consistent for the same build), so sample types can be used as keys in
+/// ordered collections.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
 pub struct SampleType {
     byte_depth: NonZeroU8,
+    bit_depth: NonZeroU8,
+    kind: u8,
+    endianness: Endianness,
     _type: u128,
 }
 
 impl fmt::Debug for SampleType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SampleType")
+            .field("name", &self.name())
             .field("byte_depth", &self.byte_depth())
+            .field("bit_depth", &self.bit_depth())
+            .field("kind", &self.kind())
+            .field("endianness", &self.endianness)
             .finish()
     }
 }
 
+/// Prints the sample type's [name](SampleType::name), or `[u8; N]` for sample
+/// types without one. Sample types with an explicit
+/// [endianness](SampleType::endianness) have `le` or `be` appended (e.g.
+/// `i16be`), and ones with a narrowed [bit depth](SampleType::bit_depth) have
+/// it appended last (e.g. `i32le/24`).
+impl fmt::Display for SampleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind() {
+            Some(_) => f.write_str(self.name())?,
+            None => write!(f, "[u8; {}]", self.byte_depth())?,
+        }
+        f.write_str(self.endianness.suffix())?;
+
+        if !self.is_full_width() {
+            write!(f, "/{}", self.bit_depth())?;
+        }
+        Ok(())
+    }
+}
+
 impl SampleType {
     #[inline]
     pub const fn new<Sample: audio_core::Sample + 'static>() -> Self {
+        Self::of_type::<Sample>()
+    }
+
+    /// Like [`SampleType::new`], for sample types that don't implement
+    /// [audio_core::Sample] (e.g. because of the orphan rule).
+    ///
+    /// The bit depth of types of 32 bytes or more, whose width in bits doesn't
+    /// fit in a `u8`, saturates at 255.
+    #[inline]
+    pub(crate) const fn of_type<Sample: 'static>() -> Self {
+        let byte_depth = NonZeroU8::new(size_of::<Sample>() as u8).unwrap();
+
         Self {
-            byte_depth: NonZeroU8::new(size_of::<Sample>() as u8).unwrap(),
+            byte_depth,
+            bit_depth: NonZeroU8::new(byte_depth.get().saturating_mul(8)).unwrap(),
+            kind: match SampleKind::of::<Sample>() {
+                Some(kind) => kind as u8,
+                None => 0,
+            },
+            endianness: Endianness::Native,
             _type: type_id::<Sample>(),
         }
     }
 
+    /// Create a sample type from `Sample`, explicitly stored in little-endian
+    /// byte order.
+    #[inline]
+    pub const fn new_le<Sample: audio_core::Sample + 'static>() -> Self {
+        Self::new::<Sample>().with_endianness(Endianness::Little)
+    }
+
+    /// Create a sample type from `Sample`, explicitly stored in big-endian byte
+    /// order.
+    #[inline]
+    pub const fn new_be<Sample: audio_core::Sample + 'static>() -> Self {
+        Self::new::<Sample>().with_endianness(Endianness::Big)
+    }
+
+    /// This sample type, reporting the given kind.
+    #[cfg(feature = "fixed")]
+    #[inline]
+    pub(crate) const fn with_kind(self, kind: SampleKind) -> Self {
+        Self {
+            kind: kind as u8,
+            ..self
+        }
+    }
+
+    /// This sample type, stored in the given byte order.
+    ///
+    /// Byte order doesn't affect any of the byte math, but sample types with
+    /// different [`Endianness`] values are never equal (including
+    /// [`Endianness::Native`] and the target's actual byte order).
+    #[inline]
+    pub const fn with_endianness(self, endianness: Endianness) -> Self {
+        Self { endianness, ..self }
+    }
+
+    /// The byte order samples are stored in.
+    #[inline]
+    pub const fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// This sample type with [`Endianness::Native`] resolved to the target's
+    /// byte order, or with no explicit endianness at all for single-byte
+    /// types (whose byte order is meaningless).
+    #[inline]
+    pub(crate) const fn with_resolved_endianness(self) -> Self {
+        self.with_endianness(match self.byte_depth.get() {
+            1 => Endianness::Native,
+            _ if self.is_big_endian() => Endianness::Big,
+            _ => Endianness::Little,
+        })
+    }
+
+    /// Whether samples are stored in big-endian byte order, resolving
+    /// [`Endianness::Native`] to the target's byte order.
+    #[inline]
+    pub const fn is_big_endian(&self) -> bool {
+        match self.endianness {
+            Endianness::Native => cfg!(target_endian = "big"),
+            Endianness::Little => false,
+            Endianness::Big => true,
+        }
+    }
+
+    /// Narrow the number of meaningful bits in each sample, e.g. for 20-bit
+    /// audio stored in an `i32`.
+    ///
+    /// Returns `None` if `bits` is wider than the type's container
+    /// (`8 * self.byte_depth()`).
+    #[inline]
+    pub const fn with_bit_depth(self, bits: core::num::NonZeroU8) -> Option<Self> {
+        if bits.get() > self.bit_depth.get() {
+            return None;
+        }
+
+        Some(Self {
+            bit_depth: NonZeroU8::from_std(bits),
+            ..self
+        })
+    }
+
+    /// Create a sample type from `Sample`, with only `bits` of each sample's
+    /// container being meaningful, e.g. 24-bit audio stored in an `i32`:
+    ///
+    /// ```
+    /// # #![feature(const_option)]
+    /// # use audio_time::SampleType;
+    /// #
+    /// const S24: SampleType = SampleType::with_valid_bits::<i32>(24).unwrap();
+    /// assert_eq!(4, S24.byte_depth().get());
+    /// assert_eq!(24, S24.bit_depth().get());
+    /// ```
+    ///
+    /// Returns `None` if `bits` is zero or wider than the container.
+    #[inline]
+    pub const fn with_valid_bits<Sample: audio_core::Sample + 'static>(bits: u8) -> Option<Self> {
+        match core::num::NonZeroU8::new(bits) {
+            Some(bits) => Self::new::<Sample>().with_bit_depth(bits),
+            None => None,
+        }
+    }
+
     /// The [number of bytes](size_of) used to represent this sample type.
-    pub const fn byte_depth(&self) -> std::num::NonZeroU8 {
+    pub const fn byte_depth(&self) -> core::num::NonZeroU8 {
         self.byte_depth.into_std()
     }
+
+    /// The number of meaningful bits in a single sample.
+    ///
+    /// Equal to `8 * self.byte_depth()`, unless narrowed using
+    /// [`with_bit_depth`](SampleType::with_bit_depth).
+    pub const fn bit_depth(&self) -> core::num::NonZeroU8 {
+        self.bit_depth.into_std()
+    }
+
+    /// Whether the bit depth spans the whole container, i.e. it wasn't
+    /// narrowed using [`with_bit_depth`](SampleType::with_bit_depth).
+    #[inline]
+    pub(crate) const fn is_full_width(&self) -> bool {
+        self.bit_depth.get() == self.byte_depth.get().saturating_mul(8)
+    }
+
+    /// Whether this sample type was created from `Sample`, regardless of its
+    /// [bit depth](SampleType::bit_depth).
+    #[inline]
+    pub(crate) const fn is<Sample: 'static>(&self) -> bool {
+        self._type == type_id::<Sample>()
+    }
+
+    /// How samples of this type are encoded, if it was created from one of
+    /// the built-in primitives (or [`I24`]/[`U24`]).
+    ///
+    /// Returns `None` for other types, e.g. opaque byte arrays like `[u8; 3]`
+    /// or the pointer-sized `isize` and `usize`.
+    #[inline]
+    pub const fn kind(&self) -> Option<SampleKind> {
+        SampleKind::from_repr(self.kind)
+    }
+
+    /// Whether samples of this type are floating point numbers.
+    #[inline]
+    pub const fn is_float(&self) -> bool {
+        matches!(self.kind(), Some(SampleKind::Float))
+    }
+
+    /// Whether samples of this type are signed (floating point samples are
+    /// always signed).
+    #[inline]
+    pub const fn is_signed(&self) -> bool {
+        matches!(self.kind(), Some(SampleKind::SignedInt | SampleKind::Float))
+    }
+
+    /// The byte value that, repeated across a buffer, encodes silence: `0x80`
+    /// for `u8` samples (whose midpoint is 128), and zero otherwise.
+    ///
+    /// Note that wider unsigned types (e.g. `u16`) have no single silence byte
+    /// in general, so they are treated like zeroed buffers as well.
+    #[inline]
+    pub const fn silence_byte(&self) -> u8 {
+        if self.is::<u8>() {
+            0x80
+        } else {
+            0x00
+        }
+    }
+
+    /// The built-in primitive sample type of the given kind and byte depth, if
+    /// any.
+    pub(crate) const fn from_kind(kind: SampleKind, byte_depth: u8) -> Option<Self> {
+        Some(match (kind, byte_depth) {
+            (SampleKind::UnsignedInt, 1) => Self::new::<u8>(),
+            (SampleKind::UnsignedInt, 2) => Self::new::<u16>(),
+            (SampleKind::UnsignedInt, 3) => Self::new::<U24>(),
+            (SampleKind::UnsignedInt, 4) => Self::new::<u32>(),
+            (SampleKind::UnsignedInt, 8) => Self::new::<u64>(),
+            (SampleKind::SignedInt, 1) => Self::new::<i8>(),
+            (SampleKind::SignedInt, 2) => Self::new::<i16>(),
+            (SampleKind::SignedInt, 3) => Self::new::<I24>(),
+            (SampleKind::SignedInt, 4) => Self::new::<i32>(),
+            (SampleKind::SignedInt, 8) => Self::new::<i64>(),
+            #[cfg(feature = "half")]
+            (SampleKind::Float, 2) => Self::f16(),
+            (SampleKind::Float, 4) => Self::new::<f32>(),
+            (SampleKind::Float, 8) => Self::new::<f64>(),
+            _ => return None,
+        })
+    }
+
+    /// The conventional short name of this sample type, derived from its
+    /// [kind](SampleType::kind) and byte depth, e.g. `"i16"`, `"i24"` or
+    /// `"f32"`, ignoring its [bit depth](SampleType::bit_depth).
+    ///
+    /// Fixed-point types (see `SampleType::of_fixed`) are named after their
+    /// underlying integer, e.g. `"fixed_i16"` for Q15.
+    ///
+    /// Returns `"raw"` for sample types without a conventional name, e.g.
+    /// opaque byte arrays like `[u8; 3]`. Pointer-sized integers (`isize` and
+    /// `usize`) have no [kind](SampleType::kind) and are named `"raw"` too,
+    /// since their width depends on the target.
+    pub const fn name(&self) -> &'static str {
+        match (self.kind(), self.byte_depth.get()) {
+            (Some(SampleKind::UnsignedInt), 1) if self.is_fixed() => "fixed_u8",
+            (Some(SampleKind::UnsignedInt), 2) if self.is_fixed() => "fixed_u16",
+            (Some(SampleKind::UnsignedInt), 4) if self.is_fixed() => "fixed_u32",
+            (Some(SampleKind::UnsignedInt), 8) if self.is_fixed() => "fixed_u64",
+            (Some(SampleKind::UnsignedInt), 16) if self.is_fixed() => "fixed_u128",
+            (Some(SampleKind::SignedInt), 1) if self.is_fixed() => "fixed_i8",
+            (Some(SampleKind::SignedInt), 2) if self.is_fixed() => "fixed_i16",
+            (Some(SampleKind::SignedInt), 4) if self.is_fixed() => "fixed_i32",
+            (Some(SampleKind::SignedInt), 8) if self.is_fixed() => "fixed_i64",
+            (Some(SampleKind::SignedInt), 16) if self.is_fixed() => "fixed_i128",
+            (Some(SampleKind::UnsignedInt), 1) => "u8",
+            (Some(SampleKind::UnsignedInt), 2) => "u16",
+            (Some(SampleKind::UnsignedInt), 3) => "u24",
+            (Some(SampleKind::UnsignedInt), 4) => "u32",
+            (Some(SampleKind::UnsignedInt), 8) => "u64",
+            (Some(SampleKind::UnsignedInt), 16) => "u128",
+            (Some(SampleKind::SignedInt), 1) => "i8",
+            (Some(SampleKind::SignedInt), 2) => "i16",
+            (Some(SampleKind::SignedInt), 3) => "i24",
+            (Some(SampleKind::SignedInt), 4) => "i32",
+            (Some(SampleKind::SignedInt), 8) => "i64",
+            (Some(SampleKind::SignedInt), 16) => "i128",
+            (Some(SampleKind::Float), 2) if self.is_bf16() => "bf16",
+            (Some(SampleKind::Float), 2) => "f16",
+            (Some(SampleKind::Float), 4) => "f32",
+            (Some(SampleKind::Float), 8) => "f64",
+            _ => "raw",
+        }
+    }
+
+    /// The built-in sample type of this one's kind and byte depth (see
+    /// [`from_kind`](SampleType::from_kind)), if it is the same type.
+    #[cfg(feature = "serde")]
+    fn builtin(&self) -> Option<Self> {
+        let builtin = Self::from_kind(self.kind()?, self.byte_depth.get())?;
+        (builtin._type == self._type).then_some(builtin)
+    }
+
+    /// Look up a built-in sample type by its [name](SampleType::name),
+    /// optionally followed by an `le` or `be` endianness suffix.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let (name, endianness) = match (name.strip_suffix("le"), name.strip_suffix("be")) {
+            (Some(name), _) => (name, Endianness::Little),
+            (_, Some(name)) => (name, Endianness::Big),
+            _ => (name, Endianness::Native),
+        };
+
+        [
+            SampleKind::UnsignedInt,
+            SampleKind::SignedInt,
+            SampleKind::Float,
+        ]
+        .into_iter()
+        .flat_map(|kind| [1, 2, 3, 4, 8].map(|byte_depth| Self::from_kind(kind, byte_depth)))
+        .flatten()
+        .find(|sample_type| sample_type.name() == name)
+        .map(|sample_type| sample_type.with_endianness(endianness))
+    }
+
+    /// Whether this sample type was created from one of the `fixed` crate's
+    /// fixed-point types (see `SampleType::of_fixed`), which share their kind
+    /// and depth with the primitive integers.
+    const fn is_fixed(&self) -> bool {
+        matches!(
+            self.kind(),
+            Some(SampleKind::UnsignedInt | SampleKind::SignedInt)
+        ) && !(self.is::<u8>()
+            || self.is::<u16>()
+            || self.is::<U24>()
+            || self.is::<u32>()
+            || self.is::<u64>()
+            || self.is::<u128>()
+            || self.is::<i8>()
+            || self.is::<i16>()
+            || self.is::<I24>()
+            || self.is::<i32>()
+            || self.is::<i64>()
+            || self.is::<i128>())
+    }
+
+    /// Whether this sample type was created from [`half::bf16`], which shares
+    /// its kind and depth with `f16`.
+    const fn is_bf16(&self) -> bool {
+        #[cfg(feature = "half")]
+        return self.is::<half::bf16>();
+        #[cfg(not(feature = "half"))]
+        return false;
+    }
+
+    /// The [kind](SampleType::kind) discriminant in the low nibble, and the
+    /// [endianness](SampleType::endianness) discriminant in the high one.
+    ///
+    /// The kind is 0 for sample types that aren't the
+    /// [built-in](SampleType::from_kind) type of their kind and byte depth
+    /// (e.g. `bf16`, which shares both with `f16`), so that they can't be
+    /// mistaken for it.
+    pub(crate) const fn packed_kind(&self) -> u8 {
+        let kind = match self.kind() {
+            Some(kind) => match Self::from_kind(kind, self.byte_depth.get()) {
+                Some(builtin) if builtin._type == self._type => kind as u8,
+                _ => 0,
+            },
+            None => 0,
+        };
+
+        kind | (self.endianness as u8) << 4
+    }
+
+    /// The built-in sample type with the given
+    /// [packed kind](SampleType::packed_kind) and byte depth, if any.
+    pub(crate) const fn from_packed_kind(packed: u8, byte_depth: u8) -> Option<Self> {
+        let (Some(kind), Some(endianness)) = (
+            SampleKind::from_repr(packed & 0xf),
+            Endianness::from_repr(packed >> 4),
+        ) else {
+            return None;
+        };
+
+        match Self::from_kind(kind, byte_depth) {
+            Some(sample_type) => Some(sample_type.with_endianness(endianness)),
+            None => None,
+        }
+    }
+
+    /// Equivalent to `self == other`, but usable in const contexts.
+    #[inline]
+    pub(crate) const fn const_eq(&self, other: &Self) -> bool {
+        self.byte_depth.get() == other.byte_depth.get()
+            && self.bit_depth.get() == other.bit_depth.get()
+            && self.endianness as u8 == other.endianness as u8
+            && self._type == other._type
+    }
+}
+
+/// The byte order samples of a [`SampleType`] are stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ConstParamTy)]
+pub enum Endianness {
+    /// The target's byte order, when it isn't stated explicitly.
+    #[default]
+    Native = 0,
+    Little = 1,
+    Big = 2,
+}
+
+impl Endianness {
+    /// The variant with the given discriminant, if any.
+    pub(crate) const fn from_repr(repr: u8) -> Option<Self> {
+        match repr {
+            0 => Some(Self::Native),
+            1 => Some(Self::Little),
+            2 => Some(Self::Big),
+            _ => None,
+        }
+    }
+
+    const fn suffix(&self) -> &'static str {
+        match self {
+            Self::Native => "",
+            Self::Little => "le",
+            Self::Big => "be",
+        }
+    }
+}
+
+/// How the samples of a [`SampleType`] are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SampleKind {
+    UnsignedInt = 1,
+    SignedInt = 2,
+    Float = 3,
+}
+
+const fn same<A: 'static, B: 'static>() -> bool {
+    type_id::<A>() == type_id::<B>()
+}
+
+/// Whether `T` is one of `half`'s floating point types.
+#[cfg_attr(not(feature = "half"), allow(clippy::extra_unused_type_parameters))]
+const fn is_half<T: 'static>() -> bool {
+    #[cfg(feature = "half")]
+    return same::<T, half::f16>() || same::<T, half::bf16>();
+    #[cfg(not(feature = "half"))]
+    return false;
+}
+
+impl SampleKind {
+    /// The kind with the given discriminant, if any.
+    pub(crate) const fn from_repr(repr: u8) -> Option<Self> {
+        match repr {
+            1 => Some(Self::UnsignedInt),
+            2 => Some(Self::SignedInt),
+            3 => Some(Self::Float),
+            _ => None,
+        }
+    }
+
+    const fn of<Sample: 'static>() -> Option<Self> {
+        if same::<Sample, f32>() || same::<Sample, f64>() || is_half::<Sample>() {
+            Some(Self::Float)
+        } else if same::<Sample, i8>()
+            || same::<Sample, i16>()
+            || same::<Sample, I24>()
+            || same::<Sample, i32>()
+            || same::<Sample, i64>()
+            || same::<Sample, i128>()
+        {
+            Some(Self::SignedInt)
+        } else if same::<Sample, u8>()
+            || same::<Sample, u16>()
+            || same::<Sample, U24>()
+            || same::<Sample, u32>()
+            || same::<Sample, u64>()
+            || same::<Sample, u128>()
+        {
+            Some(Self::UnsignedInt)
+        } else {
+            None
+        }
+    }
+
+    /// A short description of the kind, e.g. `"int"` or `"float"`.
+    pub(crate) const fn name(&self) -> &'static str {
+        match self {
+            Self::UnsignedInt => "uint",
+            Self::SignedInt => "int",
+            Self::Float => "float",
+        }
+    }
+}
+
+/// In human-readable formats (e.g. JSON), serialized as a string tag, e.g.
+/// `"i16"` or `"f32"` (see [`SampleType::name`]). Sample types with a narrowed
+/// [bit depth](SampleType::bit_depth) have it appended, e.g. `"i32/24"`.
+///
+/// In binary formats, serialized as a compact `(kind, byte_depth, bit_depth)`
+/// tuple of `u8`s, where `kind` holds the discriminant of the [`SampleKind`]
+/// in its low nibble, and that of the [`Endianness`] in its high nibble.
+///
+/// Both forms are stable, and will keep deserializing to the same sample types
+/// in future versions.
+///
+/// Fails for sample types that weren't created from one of the built-in
+/// primitives (or [`I24`]/[`U24`]).
+#[cfg(feature = "serde")]
+impl serde::Serialize for SampleType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.builtin().is_none() {
+            return Err(serde::ser::Error::custom("sample type has no stable tag"));
+        }
+
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(
+                &(
+                    self.packed_kind(),
+                    self.byte_depth.get(),
+                    self.bit_depth.get(),
+                ),
+                serializer,
+            )
+        }
+    }
+}
+
+/// Fails for unknown tags, or invalid bit depths.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SampleType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        if !deserializer.is_human_readable() {
+            let (kind, byte_depth, bit_depth) =
+                <(u8, u8, u8) as serde::Deserialize>::deserialize(deserializer)?;
+            return Self::from_packed_kind(kind, byte_depth)
+                .and_then(|sample_type| {
+                    sample_type.with_bit_depth(NonZeroU8::new(bit_depth)?.into_std())
+                })
+                .ok_or_else(|| {
+                    D::Error::custom(format_args!(
+                        "unknown sample type ({kind}, {byte_depth}, {bit_depth})"
+                    ))
+                });
+        }
+
+        struct TagVisitor;
+
+        impl serde::de::Visitor<'_> for TagVisitor {
+            type Value = SampleType;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sample type tag")
+            }
+
+            fn visit_str<E: Error>(self, tag: &str) -> Result<Self::Value, E> {
+                let (name, bits) = match tag.split_once('/') {
+                    Some((name, bits)) => (name, Some(bits)),
+                    None => (tag, None),
+                };
+
+                let sample_type = SampleType::from_name(name)
+                    .ok_or_else(|| E::custom(format_args!("unknown sample type `{name}`")))?;
+                match bits {
+                    Some(bits) => bits
+                        .parse()
+                        .ok()
+                        .and_then(|bits| sample_type.with_bit_depth(bits))
+                        .ok_or_else(|| E::custom(format_args!("invalid bit depth `{bits}`"))),
+                    None => Ok(sample_type),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(TagVisitor)
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +650,242 @@ mod tests {
         assert_eq!(4, SampleType::new::<u32>().byte_depth().get());
         assert_eq!(8, SampleType::new::<f64>().byte_depth().get());
     }
+
+    #[test]
+    fn test_bit_depth() {
+        assert_eq!(16, SampleType::new::<i16>().bit_depth().get());
+        assert_eq!(64, SampleType::new::<f64>().bit_depth().get());
+
+        let twenty_bit = SampleType::new::<i32>()
+            .with_bit_depth(std::num::NonZeroU8::new(20).unwrap())
+            .unwrap();
+        assert_eq!(20, twenty_bit.bit_depth().get());
+        assert_eq!(4, twenty_bit.byte_depth().get());
+        assert_ne!(twenty_bit, SampleType::new::<i32>());
+
+        assert!(SampleType::new::<i16>()
+            .with_bit_depth(std::num::NonZeroU8::new(17).unwrap())
+            .is_none());
+
+        // 256 bits don't fit in the bit depth
+        let wide = SampleType::of_type::<[u8; 32]>();
+        assert_eq!(32, wide.byte_depth().get());
+        assert_eq!(255, wide.bit_depth().get());
+        assert_eq!("[u8; 32]", wide.to_string());
+        assert_eq!(255, SampleType::of_type::<[u8; 255]>().bit_depth().get());
+    }
+
+    #[test]
+    fn test_kind() {
+        use SampleKind::*;
+
+        for (kind, sample_type) in [
+            (UnsignedInt, SampleType::new::<u8>()),
+            (UnsignedInt, SampleType::new::<u16>()),
+            (UnsignedInt, SampleType::new::<U24>()),
+            (UnsignedInt, SampleType::new::<u32>()),
+            (UnsignedInt, SampleType::new::<u64>()),
+            (SignedInt, SampleType::new::<i8>()),
+            (SignedInt, SampleType::new::<i16>()),
+            (SignedInt, SampleType::new::<I24>()),
+            (SignedInt, SampleType::new::<i32>()),
+            (SignedInt, SampleType::new::<i64>()),
+            (Float, SampleType::new::<f32>()),
+            (Float, SampleType::new::<f64>()),
+        ] {
+            assert_eq!(Some(kind), sample_type.kind(), "{sample_type:?}");
+            assert_eq!(kind == Float, sample_type.is_float());
+            assert_eq!(kind != UnsignedInt, sample_type.is_signed());
+        }
+
+        let bytes = SampleType::new::<[u8; 3]>();
+        assert_eq!(None, bytes.kind());
+        assert!(!bytes.is_float() && !bytes.is_signed());
+
+        assert_eq!(0x80, SampleType::new::<u8>().silence_byte());
+        assert_eq!(0x00, SampleType::new::<i16>().silence_byte());
+        assert_eq!(0x00, SampleType::new::<f32>().silence_byte());
+
+        assert_eq!(
+            "SampleType { name: \"i16\", byte_depth: 2, bit_depth: 16, kind: Some(SignedInt), endianness: Native }",
+            format!("{:?}", SampleType::new::<i16>())
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for (name, sample_type) in [
+            ("u8", SampleType::new::<u8>()),
+            ("u16", SampleType::new::<u16>()),
+            ("u24", SampleType::new::<U24>()),
+            ("u32", SampleType::new::<u32>()),
+            ("u64", SampleType::new::<u64>()),
+            ("u128", SampleType::new::<u128>()),
+            ("i8", SampleType::new::<i8>()),
+            ("i16", SampleType::new::<i16>()),
+            ("i24", SampleType::new::<I24>()),
+            ("i32", SampleType::new::<i32>()),
+            ("i64", SampleType::new::<i64>()),
+            ("i128", SampleType::new::<i128>()),
+            ("f32", SampleType::new::<f32>()),
+            ("f64", SampleType::new::<f64>()),
+        ] {
+            assert_eq!(name, sample_type.name());
+            assert_eq!(name, sample_type.to_string());
+        }
+
+        assert_eq!("raw", SampleType::new::<[u8; 4]>().name());
+        assert_eq!("raw", SampleType::new::<isize>().name());
+        assert_eq!(None, SampleType::new::<usize>().kind());
+        assert_eq!("[u8; 4]", SampleType::new::<[u8; 4]>().to_string());
+        assert_eq!(
+            "i32/24",
+            SampleType::with_valid_bits::<i32>(24).unwrap().to_string()
+        );
+        assert_eq!("i16be", SampleType::new_be::<i16>().to_string());
+        assert_eq!(
+            "i32le/24",
+            SampleType::new_le::<i32>()
+                .with_bit_depth(std::num::NonZeroU8::new(24).unwrap())
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_user_defined() {
+        use crate::{system, Frames, System};
+
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        struct Q15(i16);
+
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        struct Q1_15(i16);
+
+        // SAFETY: all-zeros is a valid value for both
+        unsafe impl audio_core::Sample for Q15 {
+            const ZERO: Self = Self(0);
+        }
+        unsafe impl audio_core::Sample for Q1_15 {
+            const ZERO: Self = Self(0);
+        }
+
+        const Q15_SYS: System = system!(48_000, Stereo, Q15);
+        const Q1_15_SYS: System = system!(48_000, Stereo, Q1_15);
+
+        assert_ne!(SampleType::new::<i16>(), Q15_SYS.sample_type);
+        assert_ne!(Q15_SYS.sample_type, Q1_15_SYS.sample_type);
+        assert_ne!(system!(48_000, Stereo, i16), Q15_SYS);
+        assert_ne!(Q15_SYS, Q1_15_SYS);
+        assert!(Q15_SYS.sample_type.is::<Q15>());
+
+        assert_eq!(None, Q15_SYS.sample_type.kind());
+        assert_eq!(4_000, Frames::<Q15_SYS>::new(1_000).into_bytes().get());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        for (json, sample_type) in [
+            (r#""u8""#, SampleType::new::<u8>()),
+            (r#""u16""#, SampleType::new::<u16>()),
+            (r#""u24""#, SampleType::new::<U24>()),
+            (r#""u32""#, SampleType::new::<u32>()),
+            (r#""u64""#, SampleType::new::<u64>()),
+            (r#""i8""#, SampleType::new::<i8>()),
+            (r#""i16""#, SampleType::new::<i16>()),
+            (r#""i24""#, SampleType::new::<I24>()),
+            (r#""i32""#, SampleType::new::<i32>()),
+            (r#""i64""#, SampleType::new::<i64>()),
+            (r#""f32""#, SampleType::new::<f32>()),
+            (r#""f64""#, SampleType::new::<f64>()),
+            (
+                r#""i32/24""#,
+                SampleType::with_valid_bits::<i32>(24).unwrap(),
+            ),
+            (r#""f32le""#, SampleType::new_le::<f32>()),
+            (r#""i16be""#, SampleType::new_be::<i16>()),
+        ] {
+            assert_eq!(json, serde_json::to_string(&sample_type).unwrap());
+            assert_eq!(sample_type, serde_json::from_str(json).unwrap());
+
+            let bin = bincode::serialize(&sample_type).unwrap();
+            assert_eq!(
+                [
+                    sample_type.kind().unwrap() as u8 | (sample_type.endianness() as u8) << 4,
+                    sample_type.byte_depth().get(),
+                    sample_type.bit_depth().get()
+                ],
+                *bin
+            );
+            assert_eq!(sample_type, bincode::deserialize(&bin).unwrap());
+        }
+
+        for invalid in [
+            r#""i48""#,
+            r#""int16""#,
+            r#""i16/0""#,
+            r#""i16/17""#,
+            r#""i16ne""#,
+            "16",
+        ] {
+            assert!(
+                serde_json::from_str::<SampleType>(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+        for invalid in [
+            [0, 2, 16],
+            [2, 5, 40],
+            [2, 2, 17],
+            [2, 2, 0],
+            [4, 4, 32],
+            [0x32, 2, 16],
+        ] {
+            assert!(bincode::deserialize::<SampleType>(&invalid).is_err());
+        }
+
+        for unsupported in [
+            SampleType::new::<i128>(),
+            SampleType::new::<isize>(),
+            SampleType::new::<[u8; 2]>(),
+        ] {
+            assert!(serde_json::to_string(&unsupported).is_err());
+            assert!(bincode::serialize(&unsupported).is_err());
+        }
+    }
+
+    #[test]
+    fn test_valid_bits() {
+        use std::time::Duration;
+
+        use crate::{system, Frames, System};
+
+        const S24_32: SampleType = SampleType::with_valid_bits::<i32>(24).unwrap();
+        const S32: System = system!(48_000, Stereo, i32);
+        const S24: System = S32.with_sample_type(S24_32);
+
+        assert_ne!(SampleType::new::<i32>(), S24_32);
+        assert_eq!(
+            SampleType::new::<i32>(),
+            SampleType::with_valid_bits::<i32>(32).unwrap()
+        );
+        assert_eq!(S32.frame_size(), S24.frame_size());
+        assert_eq!(S32.bytes_per_second(), S24.bytes_per_second());
+        assert_eq!(
+            Frames::<S32>::from_duration(Duration::from_secs(1))
+                .into_bytes()
+                .get(),
+            Frames::<S24>::from_duration(Duration::from_secs(1))
+                .into_bytes()
+                .get()
+        );
+        assert_eq!("48 kHz, stereo, 24-bit int", S24.to_string());
+        assert_eq!("48000Hz/2ch/24bit", format!("{S24:#}"));
+
+        assert!(SampleType::with_valid_bits::<i32>(0).is_none());
+        assert!(SampleType::with_valid_bits::<i32>(33).is_none());
+    }
 }
@@ -1,25 +1,29 @@
-use std::{fmt, num::NonZeroU32};
+use core::{fmt, num::NonZeroU32, str::FromStr, time::Duration};
+
+use crate::{rounding::gcd, Rounding};
+
+/// The number of nanoseconds in a second.
+pub(crate) const NANOS_PER_SEC: u64 = 1_000_000_000;
 
 mod sealed {
-    use std::marker::ConstParamTy;
+    use core::marker::ConstParamTy;
 
     use nonzero_const_param::NonZeroU32;
 
     /// Audio sampling rate, the number of samples in a single second (i.e.
     /// measured in hertz).
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct SampleRate(NonZeroU32);
 
     impl SampleRate {
         #[inline]
-        pub const fn new(n: std::num::NonZeroU32) -> Self {
+        pub const fn new(n: core::num::NonZeroU32) -> Self {
             Self(NonZeroU32::from_std(n))
         }
 
         #[inline]
-        pub const fn get(&self) -> std::num::NonZeroU32 {
+        pub const fn get(&self) -> core::num::NonZeroU32 {
             self.0.into_std()
         }
     }
@@ -27,21 +31,370 @@ mod sealed {
 
 pub use self::sealed::SampleRate;
 
+/// The family of a [`SampleRate`], i.e. which of the two common base rates it
+/// is related to by an integer ratio.
+///
+/// Converting between rates of the same family only requires a simple integer
+/// ratio, while converting across families requires full resampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateFamily {
+    /// Multiples and integer fractions of 44.1 kHz (e.g. 22.05 kHz, 88.2 kHz).
+    Hz44_1k,
+    /// Multiples and integer fractions of 48 kHz (e.g. 8 kHz, 16 kHz, 96 kHz).
+    Hz48k,
+}
+
+impl RateFamily {
+    /// The base rate of this family.
+    #[inline]
+    pub const fn base(&self) -> SampleRate {
+        match self {
+            Self::Hz44_1k => SampleRate::HZ_44_1K,
+            Self::Hz48k => SampleRate::HZ_48K,
+        }
+    }
+}
+
+impl SampleRate {
+    pub const HZ_8K: Self = Self::from_hz(8_000).unwrap();
+    pub const HZ_16K: Self = Self::from_hz(16_000).unwrap();
+    pub const HZ_22_05K: Self = Self::from_hz(22_050).unwrap();
+    pub const HZ_44_1K: Self = Self::from_hz(44_100).unwrap();
+    pub const HZ_48K: Self = Self::from_hz(48_000).unwrap();
+    pub const HZ_88_2K: Self = Self::from_hz(88_200).unwrap();
+    pub const HZ_96K: Self = Self::from_hz(96_000).unwrap();
+    pub const HZ_176_4K: Self = Self::from_hz(176_400).unwrap();
+    pub const HZ_192K: Self = Self::from_hz(192_000).unwrap();
+
+    /// The rate of single-rate DSD (as on SACDs), 64 times 44.1 kHz.
+    ///
+    /// <https://en.wikipedia.org/wiki/Direct_Stream_Digital>
+    pub const DSD64: Self = Self::from_hz(2_822_400).unwrap();
+    /// The rate of double-rate DSD, 128 times 44.1 kHz.
+    pub const DSD128: Self = Self::from_hz(5_644_800).unwrap();
+
+    /// All the standard sample rates, in ascending order.
+    pub const STANDARD: [Self; 9] = [
+        Self::HZ_8K,
+        Self::HZ_16K,
+        Self::HZ_22_05K,
+        Self::HZ_44_1K,
+        Self::HZ_48K,
+        Self::HZ_88_2K,
+        Self::HZ_96K,
+        Self::HZ_176_4K,
+        Self::HZ_192K,
+    ];
+
+    /// A sample rate of `hz` hertz, or `None` if `hz` is zero.
+    #[inline]
+    pub const fn from_hz(hz: u32) -> Option<Self> {
+        match NonZeroU32::new(hz) {
+            Some(hz) => Some(Self::new(hz)),
+            None => None,
+        }
+    }
+
+    /// The default tolerance for [`from_hz_f32`](Self::from_hz_f32), enough to
+    /// absorb `f32` rounding error at all the [standard](Self::STANDARD)
+    /// rates.
+    pub const DEFAULT_HZ_TOLERANCE: f32 = 0.01;
+
+    /// A sample rate from a floating-point number of hertz (e.g. WebAudio's
+    /// `AudioContext.sampleRate`), snapped to the nearest whole number of hertz
+    /// if it is within `tolerance_hz` of it.
+    ///
+    /// Fails if the rate is not within tolerance of a whole number, or if that
+    /// number is zero or doesn't fit in a `u32` (including for NaN and
+    /// negative rates).
+    ///
+    /// ```
+    /// # use audio_time::SampleRate;
+    /// assert_eq!(
+    ///     Ok(SampleRate::HZ_44_1K),
+    ///     SampleRate::from_hz_f32(44_100.000_02, SampleRate::DEFAULT_HZ_TOLERANCE)
+    /// );
+    /// assert!(SampleRate::from_hz_f32(44_100.5, SampleRate::DEFAULT_HZ_TOLERANCE).is_err());
+    /// ```
+    #[inline]
+    pub const fn from_hz_f32(hz: f32, tolerance_hz: f32) -> Result<Self, NonIntegralRateError> {
+        // `!(hz < ...)` also rejects NaN
+        if !(hz >= 0.5 && hz < u32::MAX as f32) {
+            return Err(NonIntegralRateError(hz));
+        }
+
+        let snapped = (hz + 0.5) as u32;
+        match Self::from_hz(snapped) {
+            Some(rate) if (hz - snapped as f32).abs() <= tolerance_hz => Ok(rate),
+            _ => Err(NonIntegralRateError(hz)),
+        }
+    }
+
+    /// Whether this is one of the [standard](Self::STANDARD) sample rates.
+    #[inline]
+    pub const fn is_standard(&self) -> bool {
+        let hz = self.get().get();
+        let mut i = 0;
+        while i < Self::STANDARD.len() {
+            if Self::STANDARD[i].get().get() == hz {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// The [`RateFamily`] this sample rate belongs to, if it is an integer
+    /// multiple or integer fraction of exactly one of 44.1 kHz and 48 kHz.
+    ///
+    /// Returns `None` for unrelated rates (e.g. 32 kHz), and for the few tiny
+    /// rates that evenly divide both base rates.
+    #[inline]
+    pub const fn family(&self) -> Option<RateFamily> {
+        const fn related(hz: u32, base: u32) -> bool {
+            hz % base == 0 || base % hz == 0
+        }
+
+        let hz = self.get().get();
+        match (
+            related(hz, RateFamily::Hz44_1k.base().get().get()),
+            related(hz, RateFamily::Hz48k.base().get().get()),
+        ) {
+            (true, false) => Some(RateFamily::Hz44_1k),
+            (false, true) => Some(RateFamily::Hz48k),
+            _ => None,
+        }
+    }
+
+    /// The [standard](Self::STANDARD) sample rate closest to this one, useful
+    /// for snapping slightly-off rates reported by devices.
+    ///
+    /// Ties are broken towards the lower rate.
+    #[inline]
+    pub const fn nearest_standard(&self) -> Self {
+        let hz = self.get().get();
+        let mut nearest = Self::STANDARD[0];
+        let mut i = 1;
+        while i < Self::STANDARD.len() {
+            let candidate = Self::STANDARD[i];
+            if candidate.get().get().abs_diff(hz) < nearest.get().get().abs_diff(hz) {
+                nearest = candidate;
+            }
+            i += 1;
+        }
+        nearest
+    }
+
+    /// This sample rate multiplied by `k` (e.g. when oversampling), or `None`
+    /// on overflow.
+    #[inline]
+    pub const fn checked_mul(self, k: NonZeroU32) -> Option<Self> {
+        match self.get().checked_mul(k) {
+            Some(hz) => Some(Self::new(hz)),
+            None => None,
+        }
+    }
+
+    /// This sample rate divided by `k` (e.g. when decimating), or `None` if it
+    /// isn't exactly divisible by `k`.
+    #[inline]
+    pub const fn checked_div_exact(self, k: NonZeroU32) -> Option<Self> {
+        let (hz, k) = (self.get().get(), k.get());
+        if hz % k != 0 {
+            return None;
+        }
+
+        match NonZeroU32::new(hz / k) {
+            Some(hz) => Some(Self::new(hz)),
+            None => None,
+        }
+    }
+
+    /// This sample rate doubled, or `None` on overflow.
+    #[inline]
+    pub const fn doubled(self) -> Option<Self> {
+        self.checked_mul(NonZeroU32::new(2).unwrap())
+    }
+
+    /// This sample rate halved, or `None` if it is odd.
+    #[inline]
+    pub const fn halved(self) -> Option<Self> {
+        self.checked_div_exact(NonZeroU32::new(2).unwrap())
+    }
+
+    /// The greatest common divisor of both sample rates.
+    #[inline]
+    pub const fn gcd_with(&self, other: &Self) -> NonZeroU32 {
+        let gcd = gcd(self.get().get() as u128, other.get().get() as u128);
+        NonZeroU32::new(gcd as u32).unwrap()
+    }
+
+    /// The ratio between this sample rate and `other`, as a `(numerator,
+    /// denominator)` pair in lowest terms, e.g. `(147, 160)` for 44.1 kHz to
+    /// 48 kHz.
+    #[inline]
+    pub const fn ratio_to(&self, other: &Self) -> (NonZeroU32, NonZeroU32) {
+        let gcd = self.gcd_with(other).get();
+
+        (
+            NonZeroU32::new(self.get().get() / gcd).unwrap(),
+            NonZeroU32::new(other.get().get() / gcd).unwrap(),
+        )
+    }
+
+    /// The duration of a single frame at this rate, **truncated** to whole
+    /// nanoseconds, e.g. 20 833 ns at 48 kHz (rather than 20 833.33...).
+    ///
+    /// See [`frame_period_exact`](Self::frame_period_exact) for the exact
+    /// value.
+    #[inline]
+    pub const fn frame_period(&self) -> Duration {
+        let (secs, nanos, _) = self.frame_period_exact();
+        Duration::new(secs, nanos)
+    }
+
+    /// The exact duration of a single frame at this rate, as a
+    /// `(secs, nanos, remainder)` triple: the period is
+    /// `secs + (nanos + remainder / rate) / 10⁹` seconds.
+    ///
+    /// ```
+    /// # use audio_time::SampleRate;
+    /// #
+    /// // 22 675.736... ns
+    /// assert_eq!(
+    ///     (0, 22_675, 32_500),
+    ///     SampleRate::HZ_44_1K.frame_period_exact()
+    /// );
+    /// ```
+    #[inline]
+    pub const fn frame_period_exact(&self) -> (u64, u32, u32) {
+        let rate = self.get().get() as u64;
+        let nanos = NANOS_PER_SEC / rate;
+
+        (
+            nanos / NANOS_PER_SEC,
+            (nanos % NANOS_PER_SEC) as u32,
+            (NANOS_PER_SEC % rate) as u32,
+        )
+    }
+
+    /// The duration of `frames` frames at this rate, truncated to whole
+    /// nanoseconds.
+    ///
+    /// Computed exactly, and can't overflow (a `u64` of frames at 1 Hz is the
+    /// longest duration, which still fits in a [`Duration`]).
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use audio_time::SampleRate;
+    /// #
+    /// assert_eq!(
+    ///     Duration::from_nanos(22_675),
+    ///     SampleRate::HZ_44_1K.duration_of(1)
+    /// );
+    /// assert_eq!(
+    ///     Duration::from_secs(1),
+    ///     SampleRate::HZ_44_1K.duration_of(44_100)
+    /// );
+    /// ```
+    #[inline]
+    pub const fn duration_of(&self, frames: u64) -> Duration {
+        duration_of_ticks(frames, self.get().get() as u64)
+    }
+
+    /// The number of whole frame periods that fit in `dur`.
+    #[inline]
+    pub const fn periods_in(&self, dur: Duration) -> u128 {
+        self.periods_in_rounded(dur, Rounding::Down)
+    }
+
+    /// The number of frame periods in `dur`, rounded to a whole period in the
+    /// direction of `rounding`.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use audio_time::{Rounding, SampleRate};
+    /// #
+    /// let dur = Duration::from_nanos(20_834);
+    /// assert_eq!(
+    ///     1,
+    ///     SampleRate::HZ_48K.periods_in_rounded(dur, Rounding::Down)
+    /// );
+    /// assert_eq!(2, SampleRate::HZ_48K.periods_in_rounded(dur, Rounding::Up));
+    /// ```
+    #[inline]
+    pub const fn periods_in_rounded(&self, dur: Duration, rounding: Rounding) -> u128 {
+        ticks_in(dur, self.get().get(), rounding)
+    }
+}
+
+/// The duration of `ticks` ticks of a clock running at `rate` ticks per
+/// second, truncated to whole nanoseconds.
+#[inline]
+pub(crate) const fn duration_of_ticks(ticks: u64, rate: u64) -> Duration {
+    let nanos = (ticks % rate) as u128 * NANOS_PER_SEC as u128 / rate as u128;
+
+    Duration::new(ticks / rate, nanos as u32)
+}
+
+/// The number of ticks of a clock running at `rate` ticks per second in
+/// `dur`, rounded in the direction of `rounding` (can't overflow).
+#[inline]
+pub(crate) const fn ticks_in(dur: Duration, rate: u32, rounding: Rounding) -> u128 {
+    rounding.div(dur.as_nanos() * rate as u128, NANOS_PER_SEC as u128)
+}
+
+/// `nanos` split into whole seconds and the nanoseconds left over.
+#[inline]
+pub(crate) const fn split_nanos(nanos: u128) -> (u128, u32) {
+    (
+        nanos / NANOS_PER_SEC as u128,
+        (nanos % NANOS_PER_SEC as u128) as u32,
+    )
+}
+
+/// The duration of `nanos` nanoseconds, or `None` if it doesn't fit in a
+/// [`Duration`].
+#[inline]
+pub(crate) const fn duration_from_nanos(nanos: u128) -> Option<Duration> {
+    let (secs, nanos) = split_nanos(nanos);
+    if secs <= u64::MAX as u128 {
+        Some(Duration::new(secs as u64, nanos))
+    } else {
+        None
+    }
+}
+
 impl fmt::Display for SampleRate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.get(), f)
     }
 }
 
+/// Formats the sample rate in kilohertz when it is a "nice" kHz value (a whole
+/// multiple of 25 Hz, e.g. `22.05 kHz` or `11.025 kHz`), printing as many
+/// decimals as needed to be exact, and in plain hertz otherwise (e.g.
+/// `44099 Hz`).
+///
+/// The alternate form (`{:#?}`) always prints the exact rate in hertz.
 impl fmt::Debug for SampleRate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let hz = self.get().get();
-        if hz > 1_000 {
-            let khz = hz as f32 / 1_000.;
-            write!(f, "{khz:.1} kHz")
-        } else {
-            write!(f, "{hz} Hz")
+        if f.alternate() || hz < 1_000 || hz % 25 != 0 {
+            return write!(f, "{hz} Hz");
         }
+
+        let (khz, mut frac) = (hz / 1_000, hz % 1_000);
+        if frac == 0 {
+            return write!(f, "{khz} kHz");
+        }
+
+        let mut width = 3;
+        while frac % 10 == 0 {
+            frac /= 10;
+            width -= 1;
+        }
+        write!(f, "{khz}.{frac:0width$} kHz")
     }
 }
 
@@ -57,9 +410,391 @@ impl From<SampleRate> for NonZeroU32 {
     }
 }
 
+/// Error returned when converting a zero `u32` into a [`SampleRate`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Sample rate must be non-zero")]
+pub struct ZeroRateError(());
+
+/// Error returned from [`SampleRate::from_hz_f32`] when the rate isn't close
+/// enough to a whole (non-zero) number of hertz.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+#[error("Sample rate of {0} Hz is not a whole number of hertz")]
+pub struct NonIntegralRateError(pub f32);
+
+impl TryFrom<u32> for SampleRate {
+    type Error = ZeroRateError;
+
+    #[inline]
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::from_hz(value).ok_or(ZeroRateError(()))
+    }
+}
+
+/// Error returned when parsing a [`SampleRate`] from a string.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseSampleRateError {
+    #[error("Invalid sample rate, expected e.g. `48000`, `48k` or `44.1 kHz`")]
+    Invalid,
+    #[error("Sample rate must be non-zero")]
+    Zero,
+    #[error("Sample rate must not be negative")]
+    Negative,
+    #[error("Sample rate is not a whole number of hertz")]
+    NonIntegral,
+    #[error("Sample rate is too high")]
+    Overflow,
+}
+
+/// Parses a decimal number of hertz, optionally followed by a unit of `Hz` or
+/// `k`/`kHz` (case-insensitive, and optionally separated by whitespace), e.g.
+/// `48000`, `48k`, `44.1kHz` or `44100 Hz`.
+///
+/// The number is parsed exactly, so it may have a fractional part as long as
+/// the result is a whole number of hertz (e.g. `44.1k`, but not `44.1005k`).
+///
+/// ```
+/// # use audio_time::SampleRate;
+/// #
+/// assert_eq!(Ok(SampleRate::HZ_44_1K), "44.1 kHz".parse());
+/// assert_eq!(Ok(SampleRate::HZ_48K), "48k".parse());
+/// ```
+impl FromStr for SampleRate {
+    type Err = ParseSampleRateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+
+        let scale: u64 = if unit.is_empty() || unit.eq_ignore_ascii_case("hz") {
+            1
+        } else if unit.eq_ignore_ascii_case("k") || unit.eq_ignore_ascii_case("khz") {
+            1_000
+        } else {
+            return Err(ParseSampleRateError::Invalid);
+        };
+
+        let number = number.trim_end();
+        let (negative, number) = match number.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, number),
+        };
+        let (whole, fraction) = match number.split_once('.') {
+            Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+            Some(_) => return Err(ParseSampleRateError::Invalid),
+            None => (number, ""),
+        };
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(ParseSampleRateError::Invalid);
+        }
+
+        let mut fraction_hz = 0;
+        let mut digit_scale = scale;
+        for digit in fraction.bytes() {
+            digit_scale /= 10;
+            match (digit - b'0') as u64 {
+                0 => {}
+                _ if digit_scale == 0 => return Err(ParseSampleRateError::NonIntegral),
+                digit => fraction_hz += digit * digit_scale,
+            }
+        }
+
+        let hz = whole
+            .parse::<u64>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(scale))
+            .and_then(|hz| hz.checked_add(fraction_hz))
+            .ok_or(ParseSampleRateError::Overflow)?;
+        match hz {
+            0 => Err(ParseSampleRateError::Zero),
+            _ if negative => Err(ParseSampleRateError::Negative),
+            hz => u32::try_from(hz)
+                .ok()
+                .and_then(Self::from_hz)
+                .ok_or(ParseSampleRateError::Overflow),
+        }
+    }
+}
+
+/// Macro for creating a [`SampleRate`] from any const expression evaluating to
+/// a `u32`.
+///
+/// ```
+/// use audio_time::{sample_rate, SampleRate};
+///
+/// const BASE_HZ: u32 = 44_100;
+/// const RATE: SampleRate = sample_rate!(BASE_HZ * 2);
+/// assert_eq!(88_200, RATE.get().get());
+/// ```
+///
+/// Panics if the rate is zero, so it fails to compile in const context:
+/// ```compile_fail
+/// use audio_time::{sample_rate, SampleRate};
+///
+/// const RATE: SampleRate = sample_rate!(0);
+/// ```
 #[macro_export]
 macro_rules! sample_rate {
-    ($hz:literal) => {
-        ::audio_time::SampleRate::new(::std::num::NonZeroU32::new($hz).unwrap())
+    ($hz:expr) => {
+        match ::core::num::NonZeroU32::new($hz) {
+            Some(hz) => ::audio_time::SampleRate::new(hz),
+            None => panic!("Sample rate must be non-zero"),
+        }
     };
 }
+
+/// Serialized as a plain `u32`, measured in hertz.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SampleRate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.get().get())
+    }
+}
+
+/// Fails if the sample rate is zero.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SampleRate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hz = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        NonZeroU32::new(hz)
+            .map(Self::new)
+            .ok_or_else(|| serde::de::Error::custom("sample rate must be non-zero"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_to() {
+        let ratio = |a, b| {
+            let (num, den) = SampleRate::new(NonZeroU32::new(a).unwrap())
+                .ratio_to(&SampleRate::new(NonZeroU32::new(b).unwrap()));
+            (num.get(), den.get())
+        };
+
+        assert_eq!((147, 160), ratio(44_100, 48_000));
+        assert_eq!((160, 147), ratio(48_000, 44_100));
+        assert_eq!((1, 2), ratio(48_000, 96_000));
+        assert_eq!((1, 1), ratio(44_100, 44_100));
+    }
+
+    #[test]
+    fn test_standard() {
+        let rate = |hz| SampleRate::new(NonZeroU32::new(hz).unwrap());
+
+        assert!(SampleRate::STANDARD.iter().all(SampleRate::is_standard));
+        assert!(!rate(44_099).is_standard());
+        assert!(!rate(32_000).is_standard());
+
+        assert_eq!(Some(RateFamily::Hz44_1k), SampleRate::HZ_88_2K.family());
+        assert_eq!(Some(RateFamily::Hz48k), SampleRate::HZ_96K.family());
+        assert_eq!(Some(RateFamily::Hz44_1k), rate(11_025).family());
+        assert_eq!(Some(RateFamily::Hz48k), SampleRate::HZ_8K.family());
+        assert_eq!(None, rate(32_000).family());
+        assert_eq!(None, rate(100).family());
+
+        assert_eq!(SampleRate::HZ_44_1K, rate(44_099).nearest_standard());
+        assert_eq!(SampleRate::HZ_48K, rate(47_999).nearest_standard());
+        assert_eq!(SampleRate::HZ_8K, rate(1).nearest_standard());
+        assert_eq!(SampleRate::HZ_192K, rate(u32::MAX).nearest_standard());
+        // exactly halfway between 44.1 kHz and 48 kHz
+        assert_eq!(SampleRate::HZ_44_1K, rate(46_050).nearest_standard());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let k = |k| NonZeroU32::new(k).unwrap();
+
+        const OVERSAMPLED: SampleRate = SampleRate::HZ_48K
+            .checked_mul(NonZeroU32::new(4).unwrap())
+            .unwrap();
+        assert_eq!(192_000, OVERSAMPLED.get().get());
+        assert_eq!(Some(SampleRate::HZ_96K), OVERSAMPLED.halved());
+        assert_eq!(Some(SampleRate::HZ_88_2K), SampleRate::HZ_44_1K.doubled());
+        assert_eq!(
+            Some(SampleRate::HZ_8K),
+            SampleRate::HZ_48K.checked_div_exact(k(6))
+        );
+
+        // 44100 / 8 = 5512.5
+        assert_eq!(None, SampleRate::HZ_44_1K.checked_div_exact(k(8)));
+        assert_eq!(None, SampleRate::HZ_8K.checked_div_exact(k(16_000)));
+        assert_eq!(None, SampleRate::new(k(11_025)).halved());
+
+        let max = SampleRate::new(k(u32::MAX - 1));
+        assert_eq!(None, max.doubled());
+        assert_eq!(None, max.checked_mul(k(3)));
+        assert_eq!(Some(max), max.checked_mul(k(1)));
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Ok(SampleRate::HZ_48K), SampleRate::try_from(48_000));
+        let err = SampleRate::try_from(0).unwrap_err();
+        assert_eq!("Sample rate must be non-zero", err.to_string());
+
+        const BASE_HZ: u32 = 22_050;
+        const RATE: SampleRate = crate::sample_rate!(BASE_HZ * 2);
+        assert_eq!(SampleRate::HZ_44_1K, RATE);
+        assert_eq!(Some(RATE), SampleRate::from_hz(44_100));
+        assert_eq!(None, SampleRate::from_hz(0));
+    }
+
+    #[test]
+    fn test_from_hz_f32() {
+        let tolerance = SampleRate::DEFAULT_HZ_TOLERANCE;
+
+        for (hz, expected) in [
+            (44_100.000_02, SampleRate::HZ_44_1K),
+            (47_999.996, SampleRate::HZ_48K),
+            (44_150., SampleRate::from_hz(44_150).unwrap()),
+            (1., SampleRate::from_hz(1).unwrap()),
+        ] {
+            assert_eq!(Ok(expected), SampleRate::from_hz_f32(hz, tolerance), "{hz}");
+        }
+        assert_eq!(
+            Ok(SampleRate::HZ_44_1K),
+            SampleRate::from_hz_f32(44_100.4, 0.5)
+        );
+
+        for hz in [
+            44_100.5,
+            44_100.1,
+            0.,
+            0.4,
+            -44_100.,
+            f32::NAN,
+            f32::INFINITY,
+            1e10,
+        ] {
+            assert!(SampleRate::from_hz_f32(hz, tolerance).is_err(), "{hz}");
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let debug = |hz| format!("{:?}", SampleRate::new(NonZeroU32::new(hz).unwrap()));
+        let precise = |hz| format!("{:#?}", SampleRate::new(NonZeroU32::new(hz).unwrap()));
+
+        assert_eq!("8 kHz", debug(8_000));
+        assert_eq!("22.05 kHz", debug(22_050));
+        assert_eq!("11.025 kHz", debug(11_025));
+        assert_eq!("44.1 kHz", debug(44_100));
+        assert_eq!("48 kHz", debug(48_000));
+        assert_eq!("1 kHz", debug(1_000));
+        assert_eq!("999 Hz", debug(999));
+        assert_eq!("44099 Hz", debug(44_099));
+        assert_eq!("1001 Hz", debug(1_001));
+
+        assert_eq!("44100 Hz", precise(44_100));
+        assert_eq!("1000 Hz", precise(1_000));
+        assert_eq!("999 Hz", precise(999));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let parse = |s: &str| s.parse::<SampleRate>().map(|rate| rate.get().get());
+
+        for s in [
+            "44100", "44.1k", "44.1K", "44.1kHz", "44.1 kHz", "44.1 KHZ", "44100 Hz", "44100hz",
+            " 44100 ", "44.100k", "44.1000k", "44100.0",
+        ] {
+            assert_eq!(Ok(44_100), parse(s), "{s}");
+        }
+        assert_eq!(Ok(48_000), parse("48000"));
+        assert_eq!(Ok(48_000), parse("48k"));
+        assert_eq!(Ok(44_560), parse("44.56k"));
+        assert_eq!(Ok(11_025), parse("11.025 kHz"));
+        assert_eq!(Ok(1), parse("0.001k"));
+        assert_eq!(Ok(u32::MAX), parse(&u32::MAX.to_string()));
+
+        assert_eq!(Err(ParseSampleRateError::Zero), parse("0"));
+        assert_eq!(Err(ParseSampleRateError::Zero), parse("0.000 kHz"));
+        assert_eq!(Err(ParseSampleRateError::Negative), parse("-48k"));
+        assert_eq!(Err(ParseSampleRateError::NonIntegral), parse("44.1005k"));
+        assert_eq!(Err(ParseSampleRateError::NonIntegral), parse("44100.5"));
+        assert_eq!(Err(ParseSampleRateError::NonIntegral), parse("0.0001k"));
+        assert_eq!(Err(ParseSampleRateError::Overflow), parse("4294967296"));
+        assert_eq!(Err(ParseSampleRateError::Overflow), parse("4294967.296k"));
+        assert_eq!(
+            Err(ParseSampleRateError::Overflow),
+            parse("18446744073709551.999k")
+        );
+        assert_eq!(
+            Err(ParseSampleRateError::Overflow),
+            parse("99999999999999999999999")
+        );
+
+        for s in [
+            "", "k", "kHz", "abc", "48 kbps", "48 MHz", "48k Hz", ".5k", "48.k", "4.4.1k", "+48k",
+            "48_000", "1e5", "- 48k",
+        ] {
+            assert_eq!(Err(ParseSampleRateError::Invalid), parse(s), "{s}");
+        }
+
+        for rate in SampleRate::STANDARD.into_iter().chain([SampleRate::HZ_8K]) {
+            assert_eq!(Ok(rate), rate.to_string().parse());
+            assert_eq!(Ok(rate), format!("{rate:?}").parse());
+        }
+    }
+
+    #[test]
+    fn test_frame_period() {
+        use crate::{system, Frames, System};
+
+        assert_eq!(
+            Duration::from_nanos(20_833),
+            SampleRate::HZ_48K.frame_period()
+        );
+        assert_eq!((0, 20_833, 16_000), SampleRate::HZ_48K.frame_period_exact());
+        assert_eq!(
+            Duration::from_nanos(22_675),
+            SampleRate::HZ_44_1K.frame_period()
+        );
+        assert_eq!(
+            (0, 22_675, 32_500),
+            SampleRate::HZ_44_1K.frame_period_exact()
+        );
+        assert_eq!(
+            (1, 0, 0),
+            SampleRate::new(NonZeroU32::MIN).frame_period_exact()
+        );
+        assert_eq!(
+            Duration::from_nanos(1),
+            SampleRate::from_hz(1_000_000_000).unwrap().frame_period()
+        );
+        assert_eq!(
+            (0, 0, 1_000_000_000),
+            SampleRate::new(NonZeroU32::MAX).frame_period_exact()
+        );
+
+        const SYS: System = system!(48_000, Stereo, f32);
+        let frame = Frames::<SYS>::new(1);
+        assert_eq!(
+            SampleRate::HZ_48K.frame_period(),
+            Duration::from_nanos(frame.to_nanos_u64().unwrap())
+        );
+        let second = Frames::<SYS>::new(48_000);
+        assert_eq!(Duration::from_secs(1), second.into_duration());
+
+        assert_eq!(
+            48_000,
+            SampleRate::HZ_48K.periods_in(Duration::from_secs(1))
+        );
+        assert_eq!(
+            0,
+            SampleRate::HZ_48K.periods_in(Duration::from_nanos(20_833))
+        );
+        assert_eq!(
+            1,
+            SampleRate::HZ_48K.periods_in(Duration::from_nanos(20_834))
+        );
+        assert_eq!(
+            u64::MAX as u128 * 48_000,
+            SampleRate::HZ_48K.periods_in(Duration::from_secs(u64::MAX))
+        );
+    }
+}
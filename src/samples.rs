@@ -1,4 +1,4 @@
-use std::{ops::Mul, time::Duration};
+use core::{ops::Mul, time::Duration};
 
 use crate::{
     convert::{bytes_to_samples, frames_to_samples, samples_to_bytes, samples_to_frames},
@@ -16,7 +16,6 @@ mod sealed {
     /// ([`SYS.channel_layout.channels()`](crate::ChannelLayout::channels)).
 
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct Samples<const SYS: System>(usize);
 
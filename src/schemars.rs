@@ -0,0 +1,253 @@
+//! [`JsonSchema`] implementations, describing the same representations as the
+//! serde implementations (in human-readable formats such as JSON).
+
+use std::borrow::Cow;
+
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{Bytes, ChannelLayout, Frames, SampleRate, SampleType, Samples, System};
+
+/// Duration strings accepted by the spans' serde implementations, e.g. `250ms`
+/// or `1.5s`.
+const DURATION_PATTERN: &str = r"^([0-9]+\.?[0-9]*|\.[0-9]+)(s|ms|us|µs|ns)$";
+
+/// A count of `unit` that must be a multiple of `divisor`, or a duration
+/// string.
+fn span_schema(unit: &str, divisor: usize) -> Schema {
+    let mut count = json_schema!({
+        "type": "integer",
+        "format": "uint",
+        "minimum": 0,
+    });
+    if divisor > 1 {
+        count.insert("multipleOf".to_owned(), divisor.into());
+    }
+
+    json_schema!({
+        "description": format!("A number of {unit}, or a duration string such as `250ms`"),
+        "anyOf": [
+            count,
+            {
+                "type": "string",
+                "pattern": DURATION_PATTERN,
+            },
+        ],
+    })
+}
+
+macro_rules! impl_span_schema {
+    ($name:ident, $unit:literal, $divisor:expr) => {
+        /// Depends on `SYS`, so it is always inlined.
+        impl<const SYS: System> JsonSchema for $name<SYS> {
+            fn inline_schema() -> bool {
+                true
+            }
+
+            fn schema_name() -> Cow<'static, str> {
+                stringify!($name).into()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                format!(concat!("audio_time::", stringify!($name), "<{:?}>"), SYS).into()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                span_schema($unit, $divisor)
+            }
+        }
+    };
+}
+
+impl_span_schema!(Frames, "frames", 1);
+impl_span_schema!(
+    Samples,
+    "samples",
+    SYS.channel_layout.channels().get() as usize
+);
+impl_span_schema!(Bytes, "bytes", SYS.frame_size().get() as usize);
+
+impl JsonSchema for SampleRate {
+    fn schema_name() -> Cow<'static, str> {
+        "SampleRate".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "audio_time::SampleRate".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A sample rate, in hertz",
+            "type": "integer",
+            "format": "uint32",
+            "minimum": 1,
+            "maximum": u32::MAX,
+        })
+    }
+}
+
+impl JsonSchema for ChannelLayout {
+    fn schema_name() -> Cow<'static, str> {
+        "ChannelLayout".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "audio_time::ChannelLayout".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A channel layout name, or a non-zero number of channels",
+            "anyOf": [
+                {
+                    "type": "string",
+                    "enum": ["mono", "stereo", "quad", "5.1", "7.1"],
+                },
+                {
+                    "type": "string",
+                    "pattern": "^ambisonics([0-9]|1[0-4])$",
+                },
+                {
+                    "type": "string",
+                    "pattern": "^[0-9]+(ch)?$",
+                },
+                {
+                    "type": "integer",
+                    "format": "uint8",
+                    "minimum": 1,
+                    "maximum": 255,
+                },
+            ],
+        })
+    }
+}
+
+impl JsonSchema for SampleType {
+    fn schema_name() -> Cow<'static, str> {
+        "SampleType".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "audio_time::SampleType".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A sample type tag, e.g. `i16`, `f32le` or `i32le/24`",
+            "type": "string",
+            "pattern": "^([ui](8|16|24|32|64)|f(16|32|64))(le|be)?(/[0-9]+)?$",
+        })
+    }
+}
+
+impl JsonSchema for System {
+    fn schema_name() -> Cow<'static, str> {
+        "System".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "audio_time::System".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "properties": {
+                "sample_rate": generator.subschema_for::<SampleRate>(),
+                "channel_layout": generator.subschema_for::<ChannelLayout>(),
+                "sample_type": generator.subschema_for::<SampleType>(),
+            },
+            "required": ["sample_rate", "channel_layout", "sample_type"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::schemars::schema_for;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    /// The integer branch of a span's schema.
+    fn count_schema(schema: &Schema) -> &Value {
+        schema.pointer("/anyOf/0").unwrap()
+    }
+
+    #[test]
+    fn test_spans() {
+        let schema = schema_for!(Bytes<AUDIO_CD>);
+        assert_eq!(Some(&json!(4)), count_schema(&schema).get("multipleOf"));
+        assert_eq!(Some(&json!(0)), count_schema(&schema).get("minimum"));
+
+        let schema = schema_for!(Samples<AUDIO_CD>);
+        assert_eq!(Some(&json!(2)), count_schema(&schema).get("multipleOf"));
+
+        let schema = schema_for!(Frames<AUDIO_CD>);
+        assert_eq!(None, count_schema(&schema).get("multipleOf"));
+
+        const MONO_U8: System = system!(8_000, Mono, u8);
+        let schema = schema_for!(Bytes<MONO_U8>);
+        assert_eq!(None, count_schema(&schema).get("multipleOf"));
+
+        let schema = schema_for!(Frames<AUDIO_CD>);
+        assert_eq!(
+            Some(&json!(DURATION_PATTERN)),
+            schema.pointer("/anyOf/1/pattern")
+        );
+    }
+
+    #[test]
+    fn test_inlined_per_system() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct Config {
+            buffer: Bytes<AUDIO_CD>,
+            other: Bytes<{ system!(48_000, Stereo, f32) }>,
+            system: System,
+        }
+
+        let schema = schema_for!(Config);
+        assert_eq!(
+            Some(&json!(4)),
+            schema.pointer("/properties/buffer/anyOf/0/multipleOf")
+        );
+        assert_eq!(
+            Some(&json!(8)),
+            schema.pointer("/properties/other/anyOf/0/multipleOf")
+        );
+        assert_eq!(
+            Some(&json!("#/$defs/System")),
+            schema.pointer("/properties/system/$ref")
+        );
+        assert_eq!(
+            Some(&json!(["mono", "stereo", "quad", "5.1", "7.1"])),
+            schema.pointer("/$defs/ChannelLayout/anyOf/0/enum")
+        );
+    }
+
+    #[test]
+    fn test_matches_serde() {
+        // the serialized forms match their schemas' types
+        assert!(
+            serde_json::to_value(Bytes::<AUDIO_CD>::new(4_410 * 4).unwrap())
+                .unwrap()
+                .is_string()
+        );
+        assert_eq!(
+            json!({ "sample_rate": 44_100, "channel_layout": "stereo", "sample_type": "i16" }),
+            serde_json::to_value(AUDIO_CD).unwrap()
+        );
+
+        let schema = schema_for!(System);
+        assert_eq!(
+            Some(&json!("integer")),
+            schema.pointer("/$defs/SampleRate/type")
+        );
+        assert_eq!(
+            Some(&json!("string")),
+            schema.pointer("/$defs/SampleType/type")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Seeking within streams of raw audio by time.
+
+use std::{
+    io::{self, Seek, SeekFrom},
+    time::Duration,
+};
+
+use crate::{wav::seek_offset, DynSystem, FramesDelta, Rounding, System};
+
+fn invalid_input(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+/// Extension methods for seeking within raw audio in `SYS` (e.g. a headerless
+/// PCM file, or a WAV file's `data` chunk) whose samples start at
+/// `base_offset`.
+///
+/// Every seek lands on a frame boundary relative to `base_offset`, and
+/// positions before `base_offset` are errors.
+pub trait SeekTimeExt: Seek {
+    /// Seek to `t` into the audio, rounded to a whole frame in the direction
+    /// of `rounding`, returning the new absolute position.
+    ///
+    /// ```
+    /// # use std::{io::Cursor, time::Duration};
+    /// # use audio_time::{Rounding, SeekTimeExt, AUDIO_CD};
+    /// #
+    /// let mut cursor = Cursor::new(vec![0; 44 + 176_400]);
+    /// let pos = cursor
+    ///     .seek_to_duration::<AUDIO_CD>(44, Duration::from_millis(500), Rounding::Down)
+    ///     .unwrap();
+    /// assert_eq!(44 + 88_200, pos);
+    /// ```
+    fn seek_to_duration<const SYS: System>(
+        &mut self,
+        base_offset: u64,
+        t: Duration,
+        rounding: Rounding,
+    ) -> io::Result<u64> {
+        let offset = seek_offset(&DynSystem::from_system(SYS), base_offset, t, rounding)
+            .map_err(|_| invalid_input("seek position overflowed"))?;
+        self.seek(SeekFrom::Start(offset))
+    }
+
+    /// Seek `delta` frames forwards or backwards, returning the new absolute
+    /// position.
+    ///
+    /// The move is relative to the start of the current frame, so seeking
+    /// from a position in the middle of a frame also realigns it.
+    fn seek_by_frames<const SYS: System>(
+        &mut self,
+        base_offset: u64,
+        delta: FramesDelta<SYS>,
+    ) -> io::Result<u64> {
+        let frame_size = SYS.frame_size().get() as u64;
+        let current = current_offset(self, base_offset)? / frame_size;
+
+        let offset = current
+            .checked_add_signed(delta.get() as i64)
+            .ok_or_else(|| invalid_input("seek position is before the start of the audio"))?
+            .checked_mul(frame_size)
+            .and_then(|offset| offset.checked_add(base_offset))
+            .ok_or_else(|| invalid_input("seek position overflowed"))?;
+        self.seek(SeekFrom::Start(offset))
+    }
+
+    /// The current position in the audio, as the duration of the whole frames
+    /// before it (truncated to whole nanoseconds).
+    fn tell_duration<const SYS: System>(&mut self, base_offset: u64) -> io::Result<Duration> {
+        let offset = current_offset(self, base_offset)?;
+        Ok(SYS.duration_for_bytes(offset))
+    }
+}
+
+impl<S: Seek + ?Sized> SeekTimeExt for S {}
+
+/// The current position relative to `base_offset`.
+fn current_offset<S: Seek + ?Sized>(stream: &mut S, base_offset: u64) -> io::Result<u64> {
+    stream
+        .stream_position()?
+        .checked_sub(base_offset)
+        .ok_or_else(|| invalid_input("stream is positioned before the start of the audio"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    const BASE: u64 = 44;
+
+    fn cursor() -> Cursor<Vec<u8>> {
+        Cursor::new(vec![0; BASE as usize + 176_400])
+    }
+
+    #[test]
+    fn test_seek_mid_frame() -> io::Result<()> {
+        let mut cursor = cursor();
+
+        // 1.50002 frames
+        let t = Duration::from_nanos(34_014);
+        for (rounding, frames) in [
+            (Rounding::Down, 1),
+            (Rounding::Up, 2),
+            (Rounding::Nearest, 2),
+        ] {
+            let pos = cursor.seek_to_duration::<AUDIO_CD>(BASE, t, rounding)?;
+            assert_eq!(BASE + frames * 4, pos, "{rounding:?}");
+            assert_eq!(pos, cursor.position());
+        }
+
+        for nanos in (0..1_000_000).step_by(7_919) {
+            let t = Duration::from_nanos(nanos);
+            let pos = cursor.seek_to_duration::<AUDIO_CD>(BASE, t, Rounding::Nearest)?;
+            assert_eq!(0, (pos - BASE) % 4, "{nanos}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_by_frames() -> io::Result<()> {
+        let mut cursor = cursor();
+        cursor.seek_to_duration::<AUDIO_CD>(BASE, Duration::from_millis(10), Rounding::Down)?;
+        assert_eq!(BASE + 441 * 4, cursor.position());
+
+        let pos = cursor.seek_by_frames::<AUDIO_CD>(BASE, FramesDelta::new(-41))?;
+        assert_eq!(BASE + 400 * 4, pos);
+        let pos = cursor.seek_by_frames::<AUDIO_CD>(BASE, FramesDelta::new(100))?;
+        assert_eq!(BASE + 500 * 4, pos);
+
+        // realigned to the start of the current frame
+        cursor.set_position(BASE + 10 * 4 + 3);
+        let pos = cursor.seek_by_frames::<AUDIO_CD>(BASE, FramesDelta::new(1))?;
+        assert_eq!(BASE + 11 * 4, pos);
+
+        let err = cursor
+            .seek_by_frames::<AUDIO_CD>(BASE, FramesDelta::new(-12))
+            .unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidInput, err.kind());
+        // failed seeks don't move the stream
+        assert_eq!(BASE + 11 * 4, cursor.position());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tell_duration() -> io::Result<()> {
+        let mut cursor = cursor();
+        cursor.seek_to_duration::<AUDIO_CD>(BASE, Duration::from_millis(250), Rounding::Down)?;
+        assert_eq!(
+            Duration::from_millis(250),
+            cursor.tell_duration::<AUDIO_CD>(BASE)?
+        );
+
+        // partial frames are truncated
+        cursor.set_position(BASE + 4 * 441 + 2);
+        assert_eq!(
+            Duration::from_millis(10),
+            cursor.tell_duration::<AUDIO_CD>(BASE)?
+        );
+
+        cursor.set_position(BASE - 1);
+        let err = cursor.tell_duration::<AUDIO_CD>(BASE).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidInput, err.kind());
+        let err = cursor
+            .seek_by_frames::<AUDIO_CD>(BASE, FramesDelta::new(1))
+            .unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidInput, err.kind());
+
+        let err = cursor
+            .seek_to_duration::<AUDIO_CD>(BASE, Duration::MAX, Rounding::Down)
+            .unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidInput, err.kind());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+//! Conversions shared by the signed duration types of other crates (chrono's
+//! `TimeDelta` and the `time` crate's `Duration`), which go through their
+//! (signed) number of nanoseconds.
+
+use crate::{Frames, FramesDelta, OverflowError, System};
+
+/// Error returned when converting a signed duration (chrono's `TimeDelta` or
+/// the `time` crate's `Duration`) into an unsigned span, e.g. [`Frames`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedDurationError {
+    #[error("Duration is negative")]
+    Negative,
+    #[error("Overflow error")]
+    Overflow,
+}
+
+impl From<OverflowError> for SignedDurationError {
+    #[inline]
+    fn from(_: OverflowError) -> Self {
+        Self::Overflow
+    }
+}
+
+/// The number of whole frames in `nanos` nanoseconds, which must not be
+/// negative.
+///
+/// Fails on magnitudes that don't fit in a `u64` (~584 years), see
+/// [`Frames::from_nanos_u64`].
+#[inline]
+pub(crate) fn frames_from_nanos<const SYS: System>(
+    nanos: i128,
+) -> Result<Frames<SYS>, SignedDurationError> {
+    if nanos < 0 {
+        return Err(SignedDurationError::Negative);
+    }
+    let nanos = u64::try_from(nanos).map_err(|_| SignedDurationError::Overflow)?;
+    Ok(Frames::from_nanos_u64(nanos)?)
+}
+
+/// The number of frames in `nanos` nanoseconds, truncating any partial frame
+/// (i.e. rounding towards zero).
+#[inline]
+pub(crate) fn frames_delta_from_nanos<const SYS: System>(
+    nanos: i128,
+) -> Result<FramesDelta<SYS>, OverflowError> {
+    let abs = u64::try_from(nanos.unsigned_abs()).map_err(|_| OverflowError(()))?;
+    let abs = Frames::<SYS>::from_nanos_u64(abs)?;
+    let abs = isize::try_from(abs.get()).map_err(|_| OverflowError(()))?;
+
+    Ok(FramesDelta::new(if nanos < 0 { -abs } else { abs }))
+}
+
+/// The duration of `delta` in nanoseconds, truncating any partial nanosecond
+/// (i.e. rounding towards zero).
+#[inline]
+pub(crate) fn frames_delta_to_nanos<const SYS: System>(
+    delta: FramesDelta<SYS>,
+) -> Result<i128, OverflowError> {
+    let abs = delta.abs().to_nanos_u64()? as i128;
+    Ok(if delta.is_negative() { -abs } else { abs })
+}
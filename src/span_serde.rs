@@ -0,0 +1,370 @@
+//! Serde implementations for [`Frames`], [`Samples`] and [`Bytes`].
+//!
+//! Binary formats get the span's bare count (as a newtype struct, same as a
+//! derived implementation would), while human-readable formats get a duration
+//! string, e.g. `"250ms"`. Human-readable formats accept both forms when
+//! deserializing.
+
+use core::{fmt, marker::PhantomData, time::Duration};
+
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    sample_rate::{duration_from_nanos, NANOS_PER_SEC},
+    Bytes, Frames, Rounding, Samples, System,
+};
+
+/// Error returned when parsing a duration string.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+enum ParseDurationError<'a> {
+    #[error("Invalid duration `{0}`, expected e.g. `250ms` or `1.5s`")]
+    Invalid(&'a str),
+    #[error("Duration `{0}` is too long")]
+    Overflow(&'a str),
+}
+
+/// Parses a non-negative decimal number followed by one of the units `s`,
+/// `ms`, `us` (or `µs`) and `ns`, e.g. `1.5s`. Digits beyond nanosecond
+/// precision are truncated.
+fn parse_duration(s: &str) -> Result<Duration, ParseDurationError<'_>> {
+    let invalid = || ParseDurationError::Invalid(s);
+
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(invalid)?;
+    let (number, unit) = s.split_at(split);
+    let unit_nanos: u128 = match unit {
+        "s" => NANOS_PER_SEC as u128,
+        "ms" => 1_000_000,
+        "us" | "µs" => 1_000,
+        "ns" => 1,
+        _ => return Err(invalid()),
+    };
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.contains('.') {
+        return Err(invalid());
+    }
+
+    let whole = match whole {
+        "" => 0,
+        whole => whole
+            .parse::<u128>()
+            .map_err(|_| ParseDurationError::Overflow(s))?,
+    };
+    // `fraction` is all digits, so its value is less than `unit_nanos` once
+    // truncated to the unit's precision (at most 9 digits)
+    let mut fraction_nanos = 0;
+    let mut scale = unit_nanos;
+    for digit in fraction.bytes() {
+        scale /= 10;
+        fraction_nanos += (digit - b'0') as u128 * scale;
+    }
+
+    let nanos = whole
+        .checked_mul(unit_nanos)
+        .and_then(|nanos| nanos.checked_add(fraction_nanos))
+        .ok_or(ParseDurationError::Overflow(s))?;
+    duration_from_nanos(nanos).ok_or(ParseDurationError::Overflow(s))
+}
+
+/// Formats a duration using the largest of the units accepted by
+/// [`parse_duration`] that represents it exactly, e.g. `250ms`.
+struct DisplayDuration(Duration);
+
+impl fmt::Display for DisplayDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        match nanos {
+            _ if nanos % NANOS_PER_SEC as u128 == 0 => {
+                write!(f, "{}s", nanos / NANOS_PER_SEC as u128)
+            }
+            _ if nanos % 1_000_000 == 0 => write!(f, "{}ms", nanos / 1_000_000),
+            _ if nanos % 1_000 == 0 => write!(f, "{}us", nanos / 1_000),
+            _ => write!(f, "{nanos}ns"),
+        }
+    }
+}
+
+/// The duration of `frames`, truncated to whole nanoseconds.
+///
+/// Unlike [`Frames::into_duration`], this is precise to the nanosecond, and
+/// can't overflow.
+fn frames_to_duration<const SYS: System>(frames: Frames<SYS>) -> Duration {
+    SYS.sample_rate.duration_of(frames.get() as u64)
+}
+
+/// The number of frames in `dur`, rounded to the nearest frame.
+///
+/// Since [`frames_to_duration`] is off by less than a nanosecond, this
+/// recovers the original frames for any rate below 500 MHz.
+fn duration_to_frames<const SYS: System>(dur: Duration) -> Option<Frames<SYS>> {
+    let frames = SYS.sample_rate.periods_in_rounded(dur, Rounding::Nearest);
+    usize::try_from(frames).ok().map(Frames::new)
+}
+
+/// Error for a count of `n` samples or bytes that isn't a whole number of
+/// frames, i.e. not divisible by `divisor`.
+pub(crate) fn misaligned<E: de::Error>(n: usize, divisor: usize) -> E {
+    E::custom(format_args!(
+        "{n} is not a whole number of frames (not divisible by {divisor}, remainder {})",
+        n % divisor
+    ))
+}
+
+/// A span type that is serialized by this module.
+trait Span<const SYS: System>: Copy + Into<Frames<SYS>> {
+    const NAME: &'static str;
+    /// [`NAME`](Span::NAME), in lowercase.
+    const UNIT: &'static str;
+
+    fn get(self) -> usize;
+
+    /// Fails if `n` breaks the span's invariants.
+    fn from_count<E: de::Error>(n: usize) -> Result<Self, E>;
+
+    fn from_frames<E: de::Error>(frames: Frames<SYS>) -> Result<Self, E>;
+}
+
+impl<const SYS: System> Span<SYS> for Frames<SYS> {
+    const NAME: &'static str = "Frames";
+    const UNIT: &'static str = "frames";
+
+    fn get(self) -> usize {
+        Frames::get(&self)
+    }
+
+    fn from_count<E: de::Error>(n: usize) -> Result<Self, E> {
+        Ok(Self::new(n))
+    }
+
+    fn from_frames<E: de::Error>(frames: Frames<SYS>) -> Result<Self, E> {
+        Ok(frames)
+    }
+}
+
+impl<const SYS: System> Span<SYS> for Samples<SYS> {
+    const NAME: &'static str = "Samples";
+    const UNIT: &'static str = "samples";
+
+    fn get(self) -> usize {
+        Samples::get(&self)
+    }
+
+    fn from_count<E: de::Error>(n: usize) -> Result<Self, E> {
+        Self::new(n).ok_or_else(|| misaligned(n, SYS.channel_layout.channels().get() as usize))
+    }
+
+    fn from_frames<E: de::Error>(frames: Frames<SYS>) -> Result<Self, E> {
+        frames.try_into().map_err(E::custom)
+    }
+}
+
+impl<const SYS: System> Span<SYS> for Bytes<SYS> {
+    const NAME: &'static str = "Bytes";
+    const UNIT: &'static str = "bytes";
+
+    fn get(self) -> usize {
+        Bytes::get(&self)
+    }
+
+    fn from_count<E: de::Error>(n: usize) -> Result<Self, E> {
+        Self::new(n).ok_or_else(|| misaligned(n, SYS.frame_size().get() as usize))
+    }
+
+    fn from_frames<E: de::Error>(frames: Frames<SYS>) -> Result<Self, E> {
+        frames.try_into().map_err(E::custom)
+    }
+}
+
+fn serialize<T: Span<SYS>, S: Serializer, const SYS: System>(
+    span: T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.collect_str(&DisplayDuration(frames_to_duration(span.into())))
+    } else {
+        serializer.serialize_newtype_struct(T::NAME, &span.get())
+    }
+}
+
+fn deserialize<'de, T: Span<SYS>, D: Deserializer<'de>, const SYS: System>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    struct SpanVisitor<T, const SYS: System>(PhantomData<T>);
+
+    impl<'de, T: Span<SYS>, const SYS: System> Visitor<'de> for SpanVisitor<T, SYS> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a number of {}, or a duration string", T::UNIT)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            let dur = parse_duration(v).map_err(E::custom)?;
+            let frames = duration_to_frames(dur)
+                .ok_or_else(|| E::custom(ParseDurationError::Overflow(v)))?;
+            T::from_frames(frames)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            let n =
+                usize::try_from(v).map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))?;
+            T::from_count(n)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            u64::try_from(v)
+                .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+                .and_then(|v| self.visit_u64(v))
+        }
+
+        fn visit_newtype_struct<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            T::from_count(usize::deserialize(deserializer)?)
+        }
+    }
+
+    let visitor = SpanVisitor::<T, SYS>(PhantomData);
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_newtype_struct(T::NAME, visitor)
+    }
+}
+
+macro_rules! impl_serde {
+    ($name:ident) => {
+        impl<const SYS: System> Serialize for $name<SYS> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize(*self, serializer)
+            }
+        }
+
+        impl<'de, const SYS: System> Deserialize<'de> for $name<SYS> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize(deserializer)
+            }
+        }
+    };
+}
+
+impl_serde!(Frames);
+impl_serde!(Samples);
+impl_serde!(Bytes);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, AUDIO_CD};
+
+    #[test]
+    fn test_parse_duration() {
+        for (s, dur) in [
+            ("250ms", Duration::from_millis(250)),
+            ("0.25s", Duration::from_millis(250)),
+            (".25s", Duration::from_millis(250)),
+            ("2s", Duration::from_secs(2)),
+            ("1.5us", Duration::from_nanos(1_500)),
+            ("1.5µs", Duration::from_nanos(1_500)),
+            ("22675ns", Duration::from_nanos(22_675)),
+            ("1.0000000009s", Duration::from_secs(1)),
+        ] {
+            assert_eq!(Ok(dur), parse_duration(s), "{s}");
+        }
+
+        for invalid in ["", "s", ".s", "1", "1.2.3s", "-1s", "1 s", "1m", "1sec"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid}");
+        }
+        assert!(matches!(
+            parse_duration("18446744073709551616s"),
+            Err(ParseDurationError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_json() {
+        let frames = Frames::<AUDIO_CD>::new(11_025);
+        assert_eq!(r#""250ms""#, serde_json::to_string(&frames).unwrap());
+        assert_eq!(frames, serde_json::from_str(r#""250ms""#).unwrap());
+        assert_eq!(frames, serde_json::from_str(r#""0.25s""#).unwrap());
+        assert_eq!(frames, serde_json::from_str("11025").unwrap());
+
+        let samples = Samples::<AUDIO_CD>::new(22_050).unwrap();
+        assert_eq!(r#""250ms""#, serde_json::to_string(&samples).unwrap());
+        assert_eq!(samples, serde_json::from_str(r#""250ms""#).unwrap());
+        assert_eq!(samples, serde_json::from_str("22050").unwrap());
+
+        let bytes = Bytes::<AUDIO_CD>::new(44_100).unwrap();
+        assert_eq!(r#""250ms""#, serde_json::to_string(&bytes).unwrap());
+        assert_eq!(bytes, serde_json::from_str(r#""250ms""#).unwrap());
+        assert_eq!(bytes, serde_json::from_str("44100").unwrap());
+
+        // spans that aren't a whole number of nanoseconds round-trip too
+        for n in [1, 2, 44_099, 1 << 40] {
+            let frames = Frames::<AUDIO_CD>::new(n);
+            let json = serde_json::to_string(&frames).unwrap();
+            assert_eq!(frames, serde_json::from_str(&json).unwrap(), "{json}");
+        }
+        let frames = Frames::<{ system!(48_000, Mono, f32) }>::new(1);
+        assert_eq!(r#""20833ns""#, serde_json::to_string(&frames).unwrap());
+
+        for invalid in [r#""-1s""#, r#""1""#, "-1", "1.5", "null"] {
+            assert!(
+                serde_json::from_str::<Frames<AUDIO_CD>>(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_misaligned() {
+        let err = serde_json::from_str::<Bytes<AUDIO_CD>>("3").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("3 is not a whole number of frames (not divisible by 4, remainder 3)"));
+        let err = serde_json::from_str::<Samples<AUDIO_CD>>("3").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("3 is not a whole number of frames (not divisible by 2, remainder 1)"));
+
+        let bin = bincode::serialize(&3_usize).unwrap();
+        assert!(bincode::deserialize::<Bytes<AUDIO_CD>>(&bin).is_err());
+        assert!(bincode::deserialize::<Samples<AUDIO_CD>>(&bin).is_err());
+        assert_eq!(
+            Frames::<AUDIO_CD>::new(3),
+            bincode::deserialize(&bin).unwrap()
+        );
+
+        for n in [0, 4, 44_100] {
+            let bytes = Bytes::<AUDIO_CD>::new(n).unwrap();
+            let json = n.to_string();
+            assert_eq!(bytes, serde_json::from_str(&json).unwrap());
+            let bin = bincode::serialize(&bytes).unwrap();
+            assert_eq!(bytes, bincode::deserialize(&bin).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_binary() {
+        // same representation as a bare `usize`
+        let frames = Frames::<AUDIO_CD>::new(11_025);
+        let bin = bincode::serialize(&frames).unwrap();
+        assert_eq!(bincode::serialize(&11_025_usize).unwrap(), bin);
+        assert_eq!(frames, bincode::deserialize(&bin).unwrap());
+
+        let bytes = Bytes::<AUDIO_CD>::new(44_100).unwrap();
+        let bin = bincode::serialize(&bytes).unwrap();
+        assert_eq!(bincode::serialize(&44_100_usize).unwrap(), bin);
+        assert_eq!(bytes, bincode::deserialize(&bin).unwrap());
+
+        let samples = Samples::<AUDIO_CD>::new(22_050).unwrap();
+        let bin = bincode::serialize(&samples).unwrap();
+        assert_eq!(samples, bincode::deserialize(&bin).unwrap());
+    }
+}
@@ -0,0 +1,144 @@
+//! [Proptest](proptest) strategies for generating spans.
+//!
+//! All strategies shrink towards zero.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use audio_time::{strategies::frames_up_to, AUDIO_CD};
+//! # use proptest::prelude::*;
+//! #
+//! proptest!(|(frames in frames_up_to::<AUDIO_CD>(Duration::from_secs(3_600)))| {
+//!     prop_assert!(frames.get() <= 158_760_000);
+//! });
+//! ```
+
+use core::{ops::RangeInclusive, time::Duration};
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    strategy::{Map, Strategy},
+};
+
+use crate::{rounding::gcd, sample_rate::NANOS_PER_SEC, Bytes, Frames, Samples, System};
+
+/// The number of whole frames in `max`, capped so that that many frames'
+/// worth of bytes fit in a `usize`.
+fn max_frames<const SYS: System>(max: Duration) -> usize {
+    let frames = SYS.sample_rate.periods_in(max);
+    let cap = usize::MAX / SYS.frame_size().get() as usize;
+    frames.min(cap as u128) as usize
+}
+
+/// Any number of frames spanning at most `max`.
+pub fn frames_up_to<const SYS: System>(max: Duration) -> impl Strategy<Value = Frames<SYS>> {
+    (0..=max_frames::<SYS>(max)).prop_map(Frames::new)
+}
+
+/// Any number of (frame-aligned) samples spanning at most `max`.
+pub fn aligned_samples<const SYS: System>(max: Duration) -> impl Strategy<Value = Samples<SYS>> {
+    frames_up_to(max).prop_map(Frames::into_samples)
+}
+
+/// Any number of (frame-aligned) bytes spanning at most `max`.
+pub fn aligned_bytes<const SYS: System>(max: Duration) -> impl Strategy<Value = Bytes<SYS>> {
+    frames_up_to(max).prop_map(Frames::into_bytes)
+}
+
+/// Any duration of at most `max` that is exactly representable in `SYS`, i.e.
+/// that is both a whole number of frames and a whole number of nanoseconds.
+///
+/// E.g. at 44.1 kHz, these are the multiples of 10ms (441 frames).
+pub fn on_grid_duration<const SYS: System>(max: Duration) -> impl Strategy<Value = Duration> {
+    let rate = SYS.sample_rate.get().get() as u128;
+    // the smallest number of frames that is a whole number of nanoseconds
+    let step = (rate / gcd(rate, NANOS_PER_SEC as u128)) as u64;
+    let steps = max_frames::<SYS>(max) as u64 / step;
+
+    (0..=steps).prop_map(move |n| SYS.sample_rate.duration_of(n * step))
+}
+
+impl<const SYS: System> Arbitrary for Frames<SYS> {
+    type Parameters = ();
+    type Strategy = Map<<usize as Arbitrary>::Strategy, fn(usize) -> Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any::<usize>().prop_map(Self::new as fn(_) -> _)
+    }
+}
+
+/// Any frame-aligned number of samples.
+impl<const SYS: System> Arbitrary for Samples<SYS> {
+    type Parameters = ();
+    type Strategy = Map<RangeInclusive<usize>, fn(usize) -> Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        let channels = SYS.channel_layout.channels().get() as usize;
+        (0..=usize::MAX / channels)
+            .prop_map((|frames| Frames::new(frames).into_samples()) as fn(_) -> _)
+    }
+}
+
+/// Any frame-aligned number of bytes.
+impl<const SYS: System> Arbitrary for Bytes<SYS> {
+    type Parameters = ();
+    type Strategy = Map<RangeInclusive<usize>, fn(usize) -> Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        let frame_size = SYS.frame_size().get() as usize;
+        (0..=usize::MAX / frame_size)
+            .prop_map((|frames| Frames::new(frames).into_bytes()) as fn(_) -> _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+
+    use super::*;
+    use crate::{presets::DVD_AUDIO_96K_24, AUDIO_CD};
+
+    const HOUR: Duration = Duration::from_secs(3_600);
+
+    proptest! {
+        #[test]
+        fn frames_bytes_round_trip(frames in frames_up_to::<AUDIO_CD>(HOUR)) {
+            prop_assert!(frames.get() <= 158_760_000);
+            let bytes = frames.into_bytes();
+            prop_assert_eq!(frames, Frames::from(bytes));
+            prop_assert_eq!(bytes, Frames::from(bytes).into_bytes());
+        }
+
+        #[test]
+        fn aligned_round_trip(
+            bytes in aligned_bytes::<DVD_AUDIO_96K_24>(HOUR),
+            samples in any::<Samples<AUDIO_CD>>(),
+        ) {
+            prop_assert_eq!(0, bytes.get() % DVD_AUDIO_96K_24.frame_size().get() as usize);
+            prop_assert_eq!(bytes, Frames::from(bytes).into_bytes());
+            prop_assert_eq!(samples, Frames::from(samples).into_samples());
+        }
+
+        #[test]
+        fn on_grid_is_lossless(dur in on_grid_duration::<AUDIO_CD>(HOUR)) {
+            prop_assert_eq!(0, dur.as_millis() % 10);
+            let frames = Frames::<AUDIO_CD>::from_duration(dur);
+            prop_assert_eq!(dur, frames.into_duration());
+        }
+    }
+
+    #[test]
+    fn test_shrink_to_zero() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = aligned_bytes::<AUDIO_CD>(HOUR)
+            .new_tree(&mut runner)
+            .unwrap();
+        while tree.simplify() {}
+        assert_eq!(0, tree.current().get());
+
+        let mut tree = on_grid_duration::<AUDIO_CD>(HOUR)
+            .new_tree(&mut runner)
+            .unwrap();
+        while tree.simplify() {}
+        assert_eq!(Duration::ZERO, tree.current());
+    }
+}
@@ -0,0 +1,113 @@
+//! Building blocks shared by the conversions from the stream formats of audio
+//! APIs and file headers (e.g. ALSA's hardware parameters or a WAV header).
+
+use core::num::NonZeroU8;
+
+use crate::{ChannelLayout, DynSystem, SampleRate, SampleType};
+
+/// Error returned when the sample rate or number of channels of a stream
+/// format can't be described by a [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamParamsError {
+    #[error("Sample rate must be non-zero")]
+    ZeroRate,
+    #[error("Unsupported number of channels ({0})")]
+    UnsupportedChannels(u32),
+}
+
+/// Error returned when a sample type has no corresponding format in an audio
+/// API (e.g. a `SND_PCM_FORMAT_*` in ALSA).
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Sample type `{0}` has no corresponding sample format")]
+pub struct UnsupportedSampleTypeError(pub SampleType);
+
+/// The sample rate of a stream format's rate in hertz.
+#[inline]
+pub(crate) const fn sample_rate_of(hz: u32) -> Result<SampleRate, StreamParamsError> {
+    match SampleRate::from_hz(hz) {
+        Some(rate) => Ok(rate),
+        None => Err(StreamParamsError::ZeroRate),
+    }
+}
+
+/// The layout of a stream format's number of channels, see
+/// [`ChannelLayout::from_channels`].
+#[inline]
+pub(crate) const fn channel_layout_of(channels: u32) -> Result<ChannelLayout, StreamParamsError> {
+    match channels {
+        1..=255 => Ok(ChannelLayout::from_channels(
+            NonZeroU8::new(channels as u8).unwrap(),
+        )),
+        _ => Err(StreamParamsError::UnsupportedChannels(channels)),
+    }
+}
+
+impl DynSystem {
+    /// The system of a stream format with the given rate (in hertz), number
+    /// of channels and sample type, whose layout is derived from the number of
+    /// channels alone.
+    #[inline]
+    pub(crate) const fn from_stream_params(
+        rate: u32,
+        channels: u32,
+        sample_type: SampleType,
+    ) -> Result<Self, StreamParamsError> {
+        let sample_rate = match sample_rate_of(rate) {
+            Ok(rate) => rate,
+            Err(err) => return Err(err),
+        };
+        let channel_layout = match channel_layout_of(channels) {
+            Ok(layout) => layout,
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+/// A 32-bit sample type narrowed to 24 meaningful bits, in the low bits of its
+/// container.
+pub(crate) const fn s24_32(sample_type: SampleType) -> SampleType {
+    sample_type
+        .with_bit_depth(NonZeroU8::new(24).unwrap())
+        .unwrap()
+}
+
+/// Defines `sample_type_of` and `format_of`, looking up the sample type of a
+/// format (of type `$format`) in the module's `FORMATS` table, and vice versa.
+macro_rules! format_lookups {
+    ($format:ty) => {
+        /// The sample type of `format`, or `None` for unsupported formats.
+        const fn sample_type_of(format: $format) -> Option<SampleType> {
+            let mut i = 0;
+            while i < FORMATS.len() {
+                if FORMATS[i].0 == format {
+                    return Some(FORMATS[i].1);
+                }
+                i += 1;
+            }
+            None
+        }
+
+        /// The format of `sample_type`, where native-endian sample types get
+        /// the format of the target's byte order.
+        const fn format_of(sample_type: &SampleType) -> Option<$format> {
+            let sample_type = sample_type.with_resolved_endianness();
+
+            let mut i = 0;
+            while i < FORMATS.len() {
+                if FORMATS[i].1.const_eq(&sample_type) {
+                    return Some(FORMATS[i].0);
+                }
+                i += 1;
+            }
+            None
+        }
+    };
+}
+
+pub(crate) use format_lookups;
@@ -0,0 +1,327 @@
+//! Conversions between [`DynSystem`]s and spans, and [`symphonia`]'s signal
+//! specs, decoded audio buffers, time bases and times.
+
+use core::{num::NonZeroU8, time::Duration};
+
+use ::symphonia::core::{
+    audio::{AudioBufferRef, Channels, SignalSpec},
+    units::{Time, TimeBase},
+};
+
+use crate::{
+    stream_format::sample_rate_of, ChannelLayout, DynSampleType, DynSystem, Frames, OverflowError,
+    Rounding, SampleRate, StreamParamsError, System,
+};
+
+/// Error returned when a `symphonia` signal spec can't be described by a
+/// [`DynSystem`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalSpecError {
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+    #[error("Unsupported channel layout `{0}`")]
+    UnsupportedLayout(Channels),
+    #[error("Unsupported sample type `{0}`")]
+    UnsupportedSampleType(DynSampleType),
+}
+
+impl DynSystem {
+    /// The system of a decoded `symphonia` signal with the given spec, whose
+    /// samples are of type `sample_type` (`symphonia` decouples the two, since
+    /// decoded buffers can be converted to any sample format).
+    ///
+    /// A single channel is always [mono](ChannelLayout::Mono), whatever its
+    /// position. Otherwise, the channels must form one of the named
+    /// [`ChannelLayout`]s: `symphonia` positions each channel, so a layout
+    /// this crate can't describe (e.g. 2.1) is an error rather than a bare
+    /// channel count.
+    pub fn from_signal_spec(
+        spec: &SignalSpec,
+        sample_type: DynSampleType,
+    ) -> Result<Self, SignalSpecError> {
+        let sample_rate = sample_rate_of(spec.rate)?;
+        let channel_layout =
+            layout_of(spec.channels).ok_or(SignalSpecError::UnsupportedLayout(spec.channels))?;
+        let sample_type = sample_type
+            .to_sample_type()
+            .ok_or(SignalSpecError::UnsupportedSampleType(sample_type))?;
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+/// The named layout made up of exactly `channels`.
+///
+/// `symphonia`'s channel bits share their positions with WAV channel masks.
+fn layout_of(channels: Channels) -> Option<ChannelLayout> {
+    let count = NonZeroU8::new(u8::try_from(channels.count()).ok()?)?;
+    if count.get() == 1 {
+        return Some(ChannelLayout::Mono);
+    }
+
+    match ChannelLayout::from_wave_mask(channels.bits(), count) {
+        Ok(layout) if layout.to_wave_mask() == Some(channels.bits()) => Some(layout),
+        _ => None,
+    }
+}
+
+/// The number of frames in a decoded buffer, or `None` if the buffer's spec
+/// has a different sample rate or number of channels than `SYS`.
+pub fn frames_of_audio_buffer<const SYS: System>(buf: &AudioBufferRef) -> Option<Frames<SYS>> {
+    let spec = buf.spec();
+    (spec.rate == SYS.sample_rate.get().get()
+        && spec.channels.count() == SYS.channel_layout.channels().get() as usize)
+        .then(|| Frames::new(buf.frames()))
+}
+
+/// The duration of the audio in a decoded buffer, truncated to whole
+/// nanoseconds, or `None` if the buffer's spec has a zero sample rate.
+pub fn duration_of_audio_buffer(buf: &AudioBufferRef) -> Option<Duration> {
+    Some(SampleRate::from_hz(buf.spec().rate)?.duration_of(buf.frames() as u64))
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The position of a timestamp of `ts` ticks of the time base `tb` (e.g. a
+    /// packet's `ts`), rounded in the direction of `rounding`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tb`'s denominator is zero.
+    #[inline]
+    pub const fn from_time_base_ticks(
+        ts: u64,
+        tb: TimeBase,
+        rounding: Rounding,
+    ) -> Result<Self, OverflowError> {
+        let frames = rounding.div(
+            ts as u128 * tb.numer as u128 * SYS.sample_rate.get().get() as u128,
+            tb.denom as u128,
+        );
+
+        if frames <= usize::MAX as u128 {
+            Ok(Self::new(frames as usize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// The timestamp of this position in ticks of the time base `tb` (e.g. for
+    /// seeking), rounded in the direction of `rounding`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tb`'s numerator is zero.
+    #[inline]
+    pub const fn to_time_base_ticks(
+        &self,
+        tb: TimeBase,
+        rounding: Rounding,
+    ) -> Result<u64, OverflowError> {
+        let ticks = rounding.div(
+            self.get() as u128 * tb.denom as u128,
+            tb.numer as u128 * SYS.sample_rate.get().get() as u128,
+        );
+
+        if ticks <= u64::MAX as u128 {
+            Ok(ticks as u64)
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// The position of a `symphonia` time, with its fractional second rounded
+    /// to a whole frame in the direction of `rounding`.
+    pub fn from_time(time: Time, rounding: Rounding) -> Result<Self, OverflowError> {
+        let rate = SYS.sample_rate.get().get();
+        let frac = time.frac * rate as f64;
+        let frac = match rounding {
+            Rounding::Down => frac.floor(),
+            Rounding::Up => frac.ceil(),
+            Rounding::Nearest => (frac + 0.5).floor(),
+        };
+
+        match (time.seconds as u128 * rate as u128).checked_add(frac as u128) {
+            Some(frames) if frames <= usize::MAX as u128 => Ok(Self::new(frames as usize)),
+            _ => Err(OverflowError(())),
+        }
+    }
+
+    /// This position as a `symphonia` time.
+    ///
+    /// The whole seconds are exact, but the fractional second is only as
+    /// precise as an `f64`.
+    #[inline]
+    pub fn to_time(&self) -> Time {
+        let rate = SYS.sample_rate.get().get() as u64;
+        let frames = self.get() as u64;
+
+        Time::new(frames / rate, (frames % rate) as f64 / rate as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use ::symphonia::core::audio::{AudioBuffer, Layout, Signal};
+
+    use super::*;
+    use crate::{SampleKind, AUDIO_CD};
+
+    const S16: DynSampleType = DynSampleType::new(SampleKind::SignedInt, 2, 16).unwrap();
+
+    #[test]
+    fn test_signal_spec() {
+        let spec = SignalSpec::new_with_layout(44_100, Layout::Stereo);
+        assert!(DynSystem::from_signal_spec(&spec, S16)
+            .unwrap()
+            .is::<AUDIO_CD>());
+
+        for (channels, layout) in [
+            (Layout::Mono.into_channels(), ChannelLayout::Mono),
+            (Channels::FRONT_CENTRE, ChannelLayout::Mono),
+            (
+                Layout::FivePointOne.into_channels(),
+                ChannelLayout::Surround51,
+            ),
+            (
+                Layout::FivePointOne.into_channels() | Channels::SIDE_LEFT | Channels::SIDE_RIGHT,
+                ChannelLayout::Surround71,
+            ),
+        ] {
+            let system =
+                DynSystem::from_signal_spec(&SignalSpec::new(48_000, channels), S16).unwrap();
+            assert_eq!(layout, system.channel_layout);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_spec() {
+        let two_one = Layout::TwoPointOne.into_channels();
+        assert_eq!(
+            Err(SignalSpecError::UnsupportedLayout(two_one)),
+            DynSystem::from_signal_spec(&SignalSpec::new(48_000, two_one), S16)
+        );
+        // `LFE2` has no WAV channel mask counterpart
+        let lfe2 = Channels::FRONT_LEFT | Channels::LFE2;
+        assert_eq!(
+            Err(SignalSpecError::UnsupportedLayout(lfe2)),
+            DynSystem::from_signal_spec(&SignalSpec::new(48_000, lfe2), S16)
+        );
+        assert_eq!(
+            Err(SignalSpecError::UnsupportedLayout(Channels::empty())),
+            DynSystem::from_signal_spec(&SignalSpec::new(48_000, Channels::empty()), S16)
+        );
+        assert_eq!(
+            Err(SignalSpecError::Params(StreamParamsError::ZeroRate)),
+            DynSystem::from_signal_spec(&SignalSpec::new_with_layout(0, Layout::Stereo), S16)
+        );
+
+        let int40 = DynSampleType::new(SampleKind::SignedInt, 5, 40).unwrap();
+        assert_eq!(
+            Err(SignalSpecError::UnsupportedSampleType(int40)),
+            DynSystem::from_signal_spec(
+                &SignalSpec::new_with_layout(48_000, Layout::Stereo),
+                int40
+            )
+        );
+    }
+
+    #[test]
+    fn test_audio_buffer() {
+        let mut buf =
+            AudioBuffer::<i16>::new(4_410, SignalSpec::new_with_layout(44_100, Layout::Stereo));
+        buf.render_reserved(Some(2_205));
+        let buf = AudioBufferRef::S16(Cow::Owned(buf));
+
+        assert_eq!(
+            Some(Frames::<AUDIO_CD>::new(2_205)),
+            frames_of_audio_buffer(&buf)
+        );
+        assert_eq!(
+            None,
+            frames_of_audio_buffer::<{ crate::presets::DAT_48K }>(&buf)
+        );
+        assert_eq!(
+            Some(Duration::from_millis(50)),
+            duration_of_audio_buffer(&buf)
+        );
+    }
+
+    #[test]
+    fn test_time_base() -> Result<(), OverflowError> {
+        const SYS: System = crate::presets::DAT_48K;
+        let tb = TimeBase::new(1, 90_000);
+
+        let hour = Frames::<SYS>::new(48_000 * 3_600);
+        assert_eq!(90_000 * 3_600, hour.to_time_base_ticks(tb, Rounding::Down)?);
+        assert_eq!(
+            hour,
+            Frames::from_time_base_ticks(90_000 * 3_600, tb, Rounding::Down)?
+        );
+
+        // ticks are finer than frames, so positions round-trip when rounding
+        // to the nearest tick and frame
+        for n in [1, 2, 3, 47_999, 48_001, 1 << 40] {
+            let frames = Frames::<SYS>::new(n);
+            let ticks = frames.to_time_base_ticks(tb, Rounding::Nearest)?;
+            assert_eq!(
+                frames,
+                Frames::from_time_base_ticks(ticks, tb, Rounding::Nearest)?,
+                "{n}"
+            );
+        }
+
+        // 1 tick is 0.533 frames, 3 ticks are 1.6 frames
+        for (ts, down, up, nearest) in [(1, 0, 1, 1), (3, 1, 2, 2), (90, 48, 48, 48)] {
+            for (rounding, expected) in [
+                (Rounding::Down, down),
+                (Rounding::Up, up),
+                (Rounding::Nearest, nearest),
+            ] {
+                assert_eq!(
+                    expected,
+                    Frames::<SYS>::from_time_base_ticks(ts, tb, rounding)?.get(),
+                    "{ts} {rounding:?}"
+                );
+            }
+        }
+        // 1 frame is 1.875 ticks
+        let frame = Frames::<SYS>::new(1);
+        assert_eq!(1, frame.to_time_base_ticks(tb, Rounding::Down)?);
+        assert_eq!(2, frame.to_time_base_ticks(tb, Rounding::Up)?);
+        assert_eq!(2, frame.to_time_base_ticks(tb, Rounding::Nearest)?);
+
+        assert!(
+            Frames::<SYS>::from_time_base_ticks(u64::MAX, TimeBase::new(1, 1), Rounding::Down)
+                .is_err()
+        );
+        assert!(Frames::<SYS>::new(usize::MAX)
+            .to_time_base_ticks(TimeBase::new(1, u32::MAX), Rounding::Down)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time() -> Result<(), OverflowError> {
+        const SYS: System = crate::presets::DAT_48K;
+
+        let frames = Frames::<SYS>::from_time(Time::new(1, 0.5), Rounding::Down)?;
+        assert_eq!(72_000, frames.get());
+        assert_eq!(Time::new(1, 0.5), frames.to_time());
+
+        let time = Time::new(0, 1.5 / 48_000.);
+        assert_eq!(1, Frames::<SYS>::from_time(time, Rounding::Down)?.get());
+        assert_eq!(2, Frames::<SYS>::from_time(time, Rounding::Up)?.get());
+        assert_eq!(2, Frames::<SYS>::from_time(time, Rounding::Nearest)?.get());
+
+        assert!(Frames::<SYS>::from_time(Time::new(u64::MAX, 0.), Rounding::Down).is_err());
+
+        Ok(())
+    }
+}
@@ -1,11 +1,21 @@
-use std::{marker::ConstParamTy, num::NonZeroU8};
+use core::{
+    fmt,
+    marker::ConstParamTy,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+    time::Duration,
+};
 
-use crate::{ChannelLayout, SampleRate, SampleType};
+use crate::{ByteRate, ChannelLayout, OverflowError, Rounding, SampleRate, SampleType};
 
 /// A struct that encodes all parameters that are needed to interpret an audio
 /// time span as number of samples and/or the number of bytes needed to
 /// represent it.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ConstParamTy)]
+///
+/// Systems are ordered by sample rate, then channel layout, then sample type
+/// (see [`SampleType`]'s ordering), so they can be used as keys in ordered
+/// collections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ConstParamTy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct System {
     pub sample_rate: SampleRate,
     pub channel_layout: ChannelLayout,
@@ -13,46 +23,704 @@ pub struct System {
 }
 
 impl System {
+    /// Create a new system, or `None` if `sample_rate_hz` is zero.
+    ///
+    /// ```
+    /// # #![feature(const_option)]
+    /// use audio_time::{system, ChannelLayout, SampleType, System};
+    ///
+    /// const SYS: System =
+    ///     System::new(44_100, ChannelLayout::Stereo, SampleType::new::<i16>()).unwrap();
+    /// assert_eq!(system!(44_100, Stereo, i16), SYS);
+    /// ```
+    #[inline]
+    pub const fn new(
+        sample_rate_hz: u32,
+        channel_layout: ChannelLayout,
+        sample_type: SampleType,
+    ) -> Option<Self> {
+        match NonZeroU32::new(sample_rate_hz) {
+            Some(hz) => Some(Self {
+                sample_rate: SampleRate::new(hz),
+                channel_layout,
+                sample_type,
+            }),
+            None => None,
+        }
+    }
+
+    /// Like [`System::new`], but panics if `sample_rate_hz` is zero (so fails
+    /// to compile when used to define a const item).
+    #[inline]
+    #[track_caller]
+    pub const fn new_or_panic(
+        sample_rate_hz: u32,
+        channel_layout: ChannelLayout,
+        sample_type: SampleType,
+    ) -> Self {
+        match Self::new(sample_rate_hz, channel_layout, sample_type) {
+            Some(system) => system,
+            None => panic!("Sample rate must be non-zero"),
+        }
+    }
+
+    /// This system, with its sample rate replaced by `sample_rate`.
+    ///
+    /// ```
+    /// # use audio_time::{system, SampleRate, System, AUDIO_CD};
+    /// #
+    /// const DAT: System = AUDIO_CD.with_sample_rate(SampleRate::HZ_48K);
+    /// assert_eq!(system!(48_000, Stereo, i16), DAT);
+    /// ```
+    #[inline]
+    pub const fn with_sample_rate(self, sample_rate: SampleRate) -> Self {
+        Self {
+            sample_rate,
+            ..self
+        }
+    }
+
+    /// This system, with its channel layout replaced by `channel_layout`.
+    ///
+    /// ```
+    /// # use audio_time::{system, ChannelLayout, System, AUDIO_CD};
+    /// #
+    /// const MONO_CD: System = AUDIO_CD.with_channel_layout(ChannelLayout::Mono);
+    /// assert_eq!(system!(44_100, Mono, i16), MONO_CD);
+    /// ```
+    #[inline]
+    pub const fn with_channel_layout(self, channel_layout: ChannelLayout) -> Self {
+        Self {
+            channel_layout,
+            ..self
+        }
+    }
+
+    /// This system, with its sample type replaced by `sample_type`.
+    #[inline]
+    pub const fn with_sample_type(self, sample_type: SampleType) -> Self {
+        Self {
+            sample_type,
+            ..self
+        }
+    }
+
+    /// This system, with its sample rate halved (e.g. when decimating by a
+    /// factor of 2).
+    ///
+    /// Returns `None` if the sample rate is odd, and so can't be halved
+    /// exactly.
+    #[inline]
+    pub const fn halved_rate(self) -> Option<Self> {
+        match self.sample_rate.halved() {
+            Some(rate) => Some(self.with_sample_rate(rate)),
+            None => None,
+        }
+    }
+
+    /// This system, with its sample rate doubled (e.g. when oversampling by a
+    /// factor of 2).
+    ///
+    /// Returns `None` on overflow.
+    #[inline]
+    pub const fn doubled_rate(self) -> Option<Self> {
+        match self.sample_rate.doubled() {
+            Some(rate) => Some(self.with_sample_rate(rate)),
+            None => None,
+        }
+    }
+
     /// The number of bytes used to represent a single frame.
     ///
     /// Equal to the sample's byte depth
     /// ([`self.sample_type.byte_depth()`](crate::SampleType::byte_depth)) times
     /// the number of channels
     /// ([`self.channel_layout.channels()`](crate::ChannelLayout::channels)).
+    /// Since both factors are at most `u8::MAX`, the product always fits in a
+    /// `u16`.
+    #[inline]
+    pub const fn frame_size(&self) -> NonZeroU16 {
+        NonZeroU16::new(
+            self.channel_layout.channels().get() as u16
+                * self.sample_type.byte_depth().get() as u16,
+        )
+        .unwrap()
+    }
+
+    /// The [frame size](System::frame_size), narrowed to a `NonZeroU8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame size is larger than `u8::MAX`.
+    #[deprecated(note = "use `frame_size`, which can't overflow")]
     #[inline]
     #[track_caller]
-    pub const fn frame_size(&self) -> NonZeroU8 {
-        self.channel_layout
-            .channels()
-            .checked_mul(self.sample_type.byte_depth())
-            .expect("Overflow trying to calculate system's frame size")
+    pub const fn frame_size_u8(&self) -> NonZeroU8 {
+        let size = self.frame_size().get();
+        assert!(
+            size <= u8::MAX as u16,
+            "Overflow trying to calculate system's frame size"
+        );
+
+        NonZeroU8::new(size as u8).unwrap()
+    }
+
+    /// The number of meaningful bits in a single sample.
+    ///
+    /// Usually equal to `8 * self.sample_type.byte_depth()`, but may be
+    /// narrower for sample types created using
+    /// [`SampleType::with_bit_depth`](crate::SampleType::with_bit_depth).
+    #[inline]
+    pub const fn bits_per_sample(&self) -> NonZeroU8 {
+        self.sample_type.bit_depth()
+    }
+
+    /// The number of bytes needed to represent a single second of audio.
+    ///
+    /// Equal to the sample rate times
+    /// [`self.frame_size()`](System::frame_size). The result is widened to
+    /// `u64`, since it may not fit in a `u32` for systems with high sample
+    /// rates.
+    #[inline]
+    pub const fn bytes_per_second(&self) -> u64 {
+        self.sample_rate.get().get() as u64 * self.frame_size().get() as u64
+    }
+
+    /// The number of bytes needed to represent `dur`, truncated to a whole
+    /// number of frames.
+    ///
+    /// Computed exactly, so e.g. `bytes_for(Duration::from_secs(3_600))` is
+    /// always `3_600 * self.bytes_per_second()`.
+    #[inline]
+    pub const fn bytes_for(&self, dur: Duration) -> Result<u64, OverflowError> {
+        let frames = self.sample_rate.periods_in(dur);
+
+        match frames.checked_mul(self.frame_size().get() as u128) {
+            Some(bytes) if bytes <= u64::MAX as u128 => Ok(bytes as u64),
+            _ => Err(OverflowError(())),
+        }
+    }
+
+    /// The number of bytes needed to represent `dur` when each sample's
+    /// [meaningful bits](System::bits_per_sample) are tightly packed, as in
+    /// DSD streams (see [`DSD64_STEREO`](crate::presets::DSD64_STEREO)).
+    ///
+    /// The duration is first truncated to a whole number of frames, as in
+    /// [`bytes_for`](System::bytes_for); the bits of those frames are then
+    /// rounded **up** to a whole byte, since a trailing partial byte still
+    /// has to be stored (padded). This matches
+    /// [`Bits::to_bytes_ceil`](crate::Bits::to_bytes_ceil).
+    #[inline]
+    pub const fn packed_bytes_for(&self, dur: Duration) -> Result<u64, OverflowError> {
+        let frames = self.sample_rate.periods_in(dur);
+        let frame_bits =
+            self.channel_layout.channels().get() as u128 * self.bits_per_sample().get() as u128;
+
+        match frames.checked_mul(frame_bits) {
+            Some(bits) if bits.div_ceil(8) <= u64::MAX as u128 => Ok(bits.div_ceil(8) as u64),
+            _ => Err(OverflowError(())),
+        }
+    }
+
+    /// The duration represented by `bytes` bytes, truncated to whole
+    /// nanoseconds.
+    ///
+    /// `bytes` doesn't need to be a whole number of frames (e.g. for a
+    /// truncated file), any trailing partial frame is ignored.
+    #[inline]
+    pub const fn duration_for_bytes(&self, bytes: u64) -> Duration {
+        self.sample_rate
+            .duration_of(bytes / self.frame_size().get() as u64)
+    }
+
+    /// The smallest number of frames spanning at least `target`, e.g. for
+    /// choosing a buffer size that meets a latency target.
+    ///
+    /// Unlike [`Frames::from_duration`](crate::Frames::from_duration), this
+    /// rounds up, so the buffer is never under-provisioned.
+    #[inline]
+    pub const fn frames_for_latency(&self, target: Duration) -> Result<usize, OverflowError> {
+        let frames = self.sample_rate.periods_in_rounded(target, Rounding::Up);
+
+        if frames <= usize::MAX as u128 {
+            Ok(frames as usize)
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// Like [`frames_for_latency`](System::frames_for_latency), but
+    /// additionally rounds up to a multiple of `granule` (e.g. a hardware
+    /// period granularity).
+    #[inline]
+    pub const fn frames_for_latency_multiple_of(
+        &self,
+        target: Duration,
+        granule: NonZeroUsize,
+    ) -> Result<usize, OverflowError> {
+        let granule = granule.get();
+
+        match self.frames_for_latency(target) {
+            Ok(frames) => match frames.checked_next_multiple_of(granule) {
+                Some(frames) => Ok(frames),
+                None => Err(OverflowError(())),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The number of bits needed to represent a single second of audio, i.e.
+    /// `8 * self.bytes_per_second()`.
+    #[inline]
+    pub const fn bit_rate(&self) -> u64 {
+        self.bytes_per_second() * 8
+    }
+
+    /// Whether both systems have the same sample rate.
+    #[inline]
+    pub const fn same_rate(&self, other: &Self) -> bool {
+        self.sample_rate.get().get() == other.sample_rate.get().get()
+    }
+
+    /// Whether both systems have the same channel layout.
+    #[inline]
+    pub const fn same_layout(&self, other: &Self) -> bool {
+        self.channel_layout.const_eq(&other.channel_layout)
+    }
+
+    /// Whether both systems have the same sample type.
+    #[inline]
+    pub const fn same_sample_type(&self, other: &Self) -> bool {
+        self.sample_type.const_eq(&other.sample_type)
+    }
+
+    /// Whether both systems have the same [frame size](System::frame_size).
+    #[inline]
+    pub const fn same_frame_size(&self, other: &Self) -> bool {
+        self.frame_size().get() == other.frame_size().get()
+    }
+
+    /// If this system's sample rate is a whole multiple of `other`'s, returns
+    /// the factor between them.
+    ///
+    /// ```
+    /// # #![feature(const_option)]
+    /// # use audio_time::system;
+    /// #
+    /// assert_eq!(
+    ///     Some(2),
+    ///     system!(96_000, Stereo, f32).rate_is_multiple_of(&system!(48_000, Mono, i16))
+    /// );
+    /// assert_eq!(
+    ///     None,
+    ///     system!(48_000, Stereo, f32).rate_is_multiple_of(&system!(44_100, Mono, i16))
+    /// );
+    /// ```
+    #[inline]
+    pub const fn rate_is_multiple_of(&self, other: &Self) -> Option<u32> {
+        let (rate, other_rate) = (self.sample_rate.get().get(), other.sample_rate.get().get());
+
+        if rate % other_rate == 0 {
+            Some(rate / other_rate)
+        } else {
+            None
+        }
+    }
+
+    /// The ratio between this system's sample rate and `other`'s, in lowest
+    /// terms.
+    ///
+    /// See [`SampleRate::ratio_to`].
+    #[inline]
+    pub const fn resample_ratio(&self, other: &Self) -> (NonZeroU32, NonZeroU32) {
+        self.sample_rate.ratio_to(&other.sample_rate)
+    }
+
+    /// Equivalent to [`self.bytes_per_second()`](System::bytes_per_second),
+    /// wrapped in a [`ByteRate`].
+    #[inline]
+    pub const fn byte_rate(&self) -> ByteRate {
+        ByteRate::new(NonZeroU64::new(self.bytes_per_second()).unwrap())
+    }
+}
+
+/// Formats the system in a human readable form, e.g. `44.1 kHz, stereo, 16-bit
+/// int`, or in a compact form when using the alternate flag (`{:#}`), e.g.
+/// `44100Hz/2ch/16bit`.
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bits = self.bits_per_sample();
+
+        if f.alternate() {
+            return write!(
+                f,
+                "{}Hz/{}ch/{bits}bit",
+                self.sample_rate,
+                self.channel_layout.channels()
+            );
+        }
+
+        write!(
+            f,
+            "{:?}, {}, {bits}-bit",
+            self.sample_rate, self.channel_layout
+        )?;
+        match self.sample_type.kind() {
+            Some(kind) => write!(f, " {}", kind.name()),
+            None => Ok(()),
+        }
     }
 }
 
 /// Macro for easily creating a [`System`].
 ///
+/// The sample rate may be any const expression evaluating to a `u32`, and the
+/// channel layout may be either the name of a [`ChannelLayout`] variant, a
+/// channel count literal (see [`ChannelLayout::from_channels`]), or any other
+/// expression evaluating to a layout (e.g. a path, or a const wrapped in
+/// braces to distinguish it from a variant name).
+///
 /// # Example
 /// ```
-/// use audio_time::system;
+/// use audio_time::{system, ChannelLayout, System};
 ///
 /// let _ = system!(44_100, Mono, i16);
 /// let _ = system!(8_000, Stereo, f64);
+/// let _ = system!(48_000, 16, f32);
+///
+/// const SAMPLE_RATE_HZ: u32 = 22_050;
+/// const LAYOUT: ChannelLayout = ChannelLayout::Stereo;
+/// const SYSTEM: System = system!(SAMPLE_RATE_HZ, Mono, u8);
+/// const DOUBLED: System = system!({ SAMPLE_RATE_HZ * 2 }, { LAYOUT }, u8);
+/// assert_eq!(44_100, DOUBLED.sample_rate.get().get());
+/// assert_eq!(
+///     system!(22_050, Mono, u8),
+///     system!(SAMPLE_RATE_HZ, ChannelLayout::Mono, u8)
+/// );
+/// ```
+///
+/// A sample rate of zero fails to compile when evaluated in const context:
+/// ```compile_fail
+/// use audio_time::{system, System};
+///
+/// const SYSTEM: System = system!(0, Mono, i16);
 /// ```
 #[macro_export]
 macro_rules! system {
-    ($sample_rate:literal, $channel_layout:ident, $sample:ty) => {
-        ::audio_time::System {
-            sample_rate: ::audio_time::sample_rate!($sample_rate),
-            channel_layout: ::audio_time::ChannelLayout::$channel_layout,
-            sample_type: ::audio_time::SampleType::new::<$sample>(),
-        }
+    ($sample_rate:expr, $channel_layout:ident, $sample:ty) => {
+        $crate::system!(
+            $sample_rate,
+            ::audio_time::ChannelLayout::$channel_layout,
+            $sample
+        )
+    };
+    ($sample_rate:expr, $channels:literal, $sample:ty) => {
+        $crate::system!(
+            $sample_rate,
+            match ::core::num::NonZeroU8::new($channels) {
+                Some(channels) => ::audio_time::ChannelLayout::from_channels(channels),
+                None => panic!("Number of channels must be non-zero"),
+            },
+            $sample
+        )
+    };
+    ($sample_rate:expr, $channel_layout:expr, $sample:ty) => {
+        ::audio_time::System::new_or_panic(
+            $sample_rate,
+            $channel_layout,
+            ::audio_time::SampleType::new::<$sample>(),
+        )
     };
 }
 
-/// Audio CD encoding system.
+/// Assert at compile time that two systems satisfy the given predicates (any of
+/// [`same_rate`](System::same_rate), [`same_layout`](System::same_layout),
+/// [`same_sample_type`](System::same_sample_type) and
+/// [`same_frame_size`](System::same_frame_size)).
 ///
-/// <https://en.wikipedia.org/wiki/Compact_Disc_Digital_Audio>:
-/// ```text
-/// 2 channels of LPCM audio, each signed 16-bit values sampled at 44100 Hz
+/// # Example
+/// ```
+/// # #![feature(const_option)]
+/// use audio_time::{const_assert_compatible, system, System, AUDIO_CD};
+///
+/// const MONO_CD: System = system!(44_100, Mono, i16);
+/// const_assert_compatible!(AUDIO_CD, MONO_CD, same_rate, same_sample_type);
+/// ```
+///
+/// Systems that don't satisfy the predicates fail to compile:
+/// ```compile_fail
+/// # #![feature(const_option)]
+/// use audio_time::{const_assert_compatible, system, System, AUDIO_CD};
+///
+/// const DAT: System = system!(48_000, Stereo, i16);
+/// const_assert_compatible!(AUDIO_CD, DAT, same_rate);
 /// ```
-pub const AUDIO_CD: System = system!(44_100, Stereo, i16);
+#[macro_export]
+macro_rules! const_assert_compatible {
+    ($a:expr, $b:expr, $($predicate:ident),+ $(,)?) => {
+        $(
+            const _: () = ::core::assert!(
+                $a.$predicate(&$b),
+                ::core::concat!(
+                    "Systems `",
+                    ::core::stringify!($a),
+                    "` and `",
+                    ::core::stringify!($b),
+                    "` do not satisfy `",
+                    ::core::stringify!($predicate),
+                    "`"
+                )
+            );
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    const MONO_CD: System = system!(44_100, Mono, i16);
+    const_assert_compatible!(AUDIO_CD, MONO_CD, same_rate, same_sample_type);
+    const _: () = assert!(system!(88_200, Mono, f32)
+        .rate_is_multiple_of(&AUDIO_CD)
+        .is_some());
+
+    #[test]
+    fn test_predicates() {
+        assert!(AUDIO_CD.same_rate(&MONO_CD));
+        assert!(!AUDIO_CD.same_layout(&MONO_CD));
+        assert!(AUDIO_CD.same_sample_type(&MONO_CD));
+        assert!(!AUDIO_CD.same_frame_size(&MONO_CD));
+        assert!(AUDIO_CD.same_frame_size(&system!(8_000, Mono, u32)));
+        assert!(!AUDIO_CD.same_sample_type(&system!(44_100, Stereo, u16)));
+
+        assert_eq!(Some(1), AUDIO_CD.rate_is_multiple_of(&MONO_CD));
+        assert_eq!(
+            Some(4),
+            system!(176_400, Mono, f32).rate_is_multiple_of(&AUDIO_CD)
+        );
+        assert_eq!(
+            None,
+            AUDIO_CD.rate_is_multiple_of(&system!(88_200, Mono, f32))
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        const SYS: System =
+            System::new_or_panic(44_100, ChannelLayout::Stereo, SampleType::new::<i16>());
+        assert_eq!(AUDIO_CD, SYS);
+        assert_eq!(
+            Some(AUDIO_CD),
+            System::new(44_100, ChannelLayout::Stereo, SampleType::new::<i16>())
+        );
+        assert!(System::new(0, ChannelLayout::Mono, SampleType::new::<i16>()).is_none());
+
+        // same const-param identity
+        let _: crate::Frames<SYS> = crate::Frames::<AUDIO_CD>::new(1);
+    }
+
+    #[test]
+    fn test_throughput() {
+        assert_eq!(176_400, AUDIO_CD.bytes_per_second());
+
+        const HIRES: System = system!(192_000, Stereo, f32);
+        let hour = Duration::from_secs(3_600);
+        assert_eq!(5_529_600_000, HIRES.bytes_for(hour).unwrap());
+        assert_eq!(hour, HIRES.duration_for_bytes(5_529_600_000));
+        assert!(HIRES.bytes_for(Duration::MAX).is_err());
+
+        // truncated to a whole frame
+        assert_eq!(4, AUDIO_CD.bytes_for(Duration::from_micros(30)).unwrap());
+        // a trailing partial frame is floored away
+        assert_eq!(
+            AUDIO_CD.duration_for_bytes(176_400),
+            AUDIO_CD.duration_for_bytes(176_403)
+        );
+        assert_eq!(Duration::from_nanos(22_675), AUDIO_CD.duration_for_bytes(7));
+    }
+
+    #[test]
+    fn test_frames_for_latency() {
+        let target = Duration::from_millis(5);
+        assert_eq!(221, AUDIO_CD.frames_for_latency(target).unwrap());
+        assert_eq!(
+            256,
+            AUDIO_CD
+                .frames_for_latency_multiple_of(target, NonZeroUsize::new(64).unwrap())
+                .unwrap()
+        );
+        assert_eq!(
+            240,
+            system!(48_000, Mono, f32)
+                .frames_for_latency(target)
+                .unwrap()
+        );
+        assert_eq!(0, AUDIO_CD.frames_for_latency(Duration::ZERO).unwrap());
+        assert!(AUDIO_CD.frames_for_latency(Duration::MAX).is_err());
+
+        assert_eq!(
+            crate::Frames::<AUDIO_CD>::new(221),
+            crate::Frames::for_latency(target).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with() {
+        const MONO_CD_F32: System = AUDIO_CD
+            .with_channel_layout(ChannelLayout::Mono)
+            .with_sample_type(SampleType::new::<f32>());
+        const OVERSAMPLED: System = MONO_CD_F32.doubled_rate().unwrap();
+        const DECIMATED: System = OVERSAMPLED.halved_rate().unwrap().halved_rate().unwrap();
+
+        assert_eq!(4, MONO_CD_F32.frame_size().get());
+        assert_eq!(176_400, MONO_CD_F32.bytes_per_second());
+        assert_eq!(352_800, OVERSAMPLED.bytes_per_second());
+        assert_eq!(88_200, DECIMATED.bytes_per_second());
+        assert_eq!(
+            system!(48_000, Mono, f32),
+            MONO_CD_F32.with_sample_rate(crate::sample_rate!(48_000))
+        );
+
+        assert!(DECIMATED.halved_rate().unwrap().halved_rate().is_none());
+        assert!(system!(1, Mono, i16).halved_rate().is_none());
+        assert!(system!(0x8000_0000, Mono, i16).doubled_rate().is_none());
+    }
+
+    #[test]
+    fn test_ord() {
+        use std::collections::BTreeSet;
+
+        let systems = [
+            system!(48_000, Stereo, f32),
+            AUDIO_CD,
+            system!(8_000, Mono, i16),
+            system!(44_100, Mono, i16),
+            system!(44_100, Stereo, f64),
+        ];
+        let forward: Vec<_> = systems
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let backward: Vec<_> = systems
+            .into_iter()
+            .rev()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        assert_eq!(forward, backward);
+        assert_eq!(
+            [
+                system!(8_000, Mono, i16),
+                system!(44_100, Mono, i16),
+                AUDIO_CD
+            ],
+            forward[..3]
+        );
+        assert_eq!(system!(48_000, Stereo, f32), forward[4]);
+    }
+
+    #[test]
+    fn test_multichannel() {
+        use crate::{Bytes, Frames, Samples};
+
+        const STEMS: System = system!(48_000, 16, f32);
+        assert_eq!(64, STEMS.frame_size().get());
+        assert_eq!(
+            System {
+                channel_layout: ChannelLayout::from_channels(NonZeroU8::new(16).unwrap()),
+                ..system!(48_000, Mono, f32)
+            },
+            STEMS
+        );
+
+        let second = Duration::from_secs(1);
+        let frames = Frames::<STEMS>::from_duration(second);
+        let samples = frames.into_samples();
+        let bytes = samples.into_bytes();
+        assert_eq!(48_000 * 16, samples.get());
+        assert_eq!(48_000 * 64, bytes.get());
+        assert_eq!(frames, Frames::from_bytes(bytes));
+        assert_eq!(second, bytes.into_duration());
+        assert_eq!(second, samples.into_duration());
+        assert!(Samples::<STEMS>::new(17).is_none());
+        assert!(Bytes::<STEMS>::new(32).is_none());
+
+        // 256 bytes per frame doesn't fit in a `u8`
+        const WIDE: System = system!(48_000, 32, f64);
+        assert_eq!(256, WIDE.frame_size().get());
+        let bytes = Bytes::<WIDE>::from_duration(second);
+        assert_eq!(48_000 * 256, bytes.get());
+        assert_eq!(second, bytes.into_duration());
+    }
+
+    #[test]
+    fn test_ambisonics() {
+        use crate::{Bytes, Samples};
+
+        const THIRD_ORDER: System = system!(48_000, ChannelLayout::ambisonics(3).unwrap(), f32);
+        assert_eq!(64, THIRD_ORDER.frame_size().get());
+        assert_eq!(
+            3_072_000,
+            Bytes::<THIRD_ORDER>::from_duration(Duration::from_secs(1)).get()
+        );
+        assert!(Samples::<THIRD_ORDER>::new(32).is_some());
+        assert!(Samples::<THIRD_ORDER>::new(36).is_none());
+        assert!(Bytes::<THIRD_ORDER>::new(64 * 3).is_some());
+        assert!(Bytes::<THIRD_ORDER>::new(36 * 4).is_none());
+
+        const SECOND_ORDER: System =
+            THIRD_ORDER.with_channel_layout(ChannelLayout::ambisonics(2).unwrap());
+        assert!(Samples::<SECOND_ORDER>::new(27).is_some());
+        assert!(Samples::<SECOND_ORDER>::new(32).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("44.1 kHz, stereo, 16-bit int", AUDIO_CD.to_string());
+        assert_eq!("44100Hz/2ch/16bit", format!("{AUDIO_CD:#}"));
+
+        const PHONE: System = system!(8_000, Mono, f64);
+        assert_eq!("8 kHz, mono, 64-bit float", PHONE.to_string());
+        assert_eq!("8000Hz/1ch/64bit", format!("{PHONE:#}"));
+
+        assert_eq!(
+            "48 kHz, stereo, 16-bit",
+            system!(48_000, Stereo, [u8; 2]).to_string()
+        );
+        assert_eq!(
+            "48 kHz, 16ch, 32-bit float",
+            system!(48_000, 16, f32).to_string()
+        );
+        assert_eq!(
+            "48000Hz/16ch/32bit",
+            format!("{:#}", system!(48_000, 16, f32))
+        );
+    }
+
+    #[test]
+    fn test_resample_ratio() {
+        const RATIO: (NonZeroU32, NonZeroU32) =
+            AUDIO_CD.resample_ratio(&system!(48_000, Mono, f32));
+        assert_eq!((147, 160), (RATIO.0.get(), RATIO.1.get()));
+    }
+
+    #[test]
+    fn test_wide_frame_size() {
+        use std::time::Duration;
+
+        use crate::Bytes;
+
+        // the widest sample type whose bit depth still fits in a `u8`
+        const WIDE: System = system!(48_000, Stereo, [u8; 31]);
+        assert_eq!(62, WIDE.frame_size().get());
+
+        let bytes = Bytes::<WIDE>::from_duration(Duration::from_secs(1));
+        assert_eq!(48_000 * 62, bytes.get());
+        assert_eq!(Duration::from_secs(1), bytes.into_duration());
+        assert!(Bytes::<WIDE>::new(31).is_none());
+    }
+}
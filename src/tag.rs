@@ -0,0 +1,277 @@
+use core::num::{NonZeroU32, NonZeroU8};
+
+use crate::{ChannelLayout, DynSystem, Endianness, SampleKind, SampleRate, SampleType, System};
+
+/// Error returned when decoding a [`System`] from a tag.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum TagError {
+    #[error("Sample rate must be non-zero")]
+    ZeroRate,
+    #[error("Number of channels must be non-zero")]
+    ZeroChannels,
+    #[error("Unknown sample kind: {0}")]
+    UnknownKind(u8),
+    #[error("Channel layout {layout} can't have {channels} channels")]
+    InvalidLayout { layout: u8, channels: u8 },
+    #[error(
+        "Unsupported sample type (kind {kind}, byte depth {byte_depth}, bit depth {bit_depth})"
+    )]
+    UnsupportedSampleType {
+        kind: u8,
+        byte_depth: u8,
+        bit_depth: u8,
+    },
+}
+
+impl System {
+    /// Encode this system's identity into a `u64`, e.g. for embedding in a
+    /// binary protocol header.
+    ///
+    /// The layout of the tag, from the least significant bit, is:
+    ///
+    /// | bits    | field                                                  |
+    /// |---------|--------------------------------------------------------|
+    /// | 0..32   | sample rate, in hertz                                  |
+    /// | 32..40  | number of channels                                     |
+    /// | 40..48  | byte depth                                             |
+    /// | 48..52  | sample kind: 1 = unsigned int, 2 = signed int, 3 = float |
+    /// | 52..54  | [endianness](crate::Endianness): 0 = native, 1 = little, 2 = big |
+    /// | 54..56  | channel layout: 0 = [from the number of channels](ChannelLayout::from_channels), 1 = quad/5.1/7.1, 2 = ambisonics |
+    /// | 56..64  | bit depth if [narrowed](SampleType::with_bit_depth), else 0 |
+    ///
+    /// Named layouts and ambisonics orders are identified by their number of
+    /// channels, so they are encoded as a flag on top of it.
+    ///
+    /// This layout is stable, and will not change across versions of this
+    /// crate. Sample types that weren't created from one of the built-in
+    /// primitives are encoded with a kind of 0, and can't be decoded. These
+    /// include `bf16`, which has the same kind and depth as `f16`, and
+    /// fixed-point types (e.g. Q15), which have the same kind and depth as the
+    /// integers they wrap.
+    ///
+    /// ```
+    /// # use audio_time::{System, AUDIO_CD};
+    /// #
+    /// assert_eq!(0x0002_0202_0000_ac44, AUDIO_CD.to_tag());
+    /// assert!(System::from_tag(AUDIO_CD.to_tag())
+    ///     .unwrap()
+    ///     .is::<AUDIO_CD>());
+    /// ```
+    #[inline]
+    pub const fn to_tag(&self) -> u64 {
+        let sample_type = &self.sample_type;
+        let byte_depth = sample_type.byte_depth().get();
+        let bit_depth = match sample_type.is_full_width() {
+            true => 0,
+            false => sample_type.bit_depth().get(),
+        };
+        let kind = sample_type.packed_kind() | layout_kind(&self.channel_layout) << 6;
+
+        self.sample_rate.get().get() as u64
+            | (self.channel_layout.channels().get() as u64) << 32
+            | (byte_depth as u64) << 40
+            | (kind as u64) << 48
+            | (bit_depth as u64) << 56
+    }
+
+    /// Decode a tag created using [`System::to_tag`].
+    ///
+    /// Since a [`System`] can't be created at runtime for use as a const
+    /// generic, the tag is decoded into a [`DynSystem`], which can be checked
+    /// against const systems using [`DynSystem::matches`] or
+    /// [`DynSystem::is`].
+    pub const fn from_tag(tag: u64) -> Result<DynSystem, TagError> {
+        let [r0, r1, r2, r3, channels, byte_depth, kind, bit_depth] = tag.to_le_bytes();
+
+        let Some(sample_rate) = NonZeroU32::new(u32::from_le_bytes([r0, r1, r2, r3])) else {
+            return Err(TagError::ZeroRate);
+        };
+        let Some(channels) = NonZeroU8::new(channels) else {
+            return Err(TagError::ZeroChannels);
+        };
+        let Some(channel_layout) = layout_from_kind(kind >> 6, channels) else {
+            return Err(TagError::InvalidLayout {
+                layout: kind >> 6,
+                channels: channels.get(),
+            });
+        };
+        let kind = kind & 0x3f;
+        if SampleKind::from_repr(kind & 0xf).is_none() || Endianness::from_repr(kind >> 4).is_none()
+        {
+            return Err(TagError::UnknownKind(kind));
+        }
+
+        let unsupported = TagError::UnsupportedSampleType {
+            kind,
+            byte_depth,
+            bit_depth,
+        };
+        let Some(sample_type) = SampleType::from_packed_kind(kind, byte_depth) else {
+            return Err(unsupported);
+        };
+        let sample_type = match NonZeroU8::new(bit_depth) {
+            None => sample_type,
+            Some(bits) => match sample_type.with_bit_depth(bits) {
+                Some(sample_type) => sample_type,
+                None => return Err(unsupported),
+            },
+        };
+
+        Ok(DynSystem {
+            sample_rate: SampleRate::new(sample_rate),
+            channel_layout,
+            sample_type,
+        })
+    }
+}
+
+/// The channel layout flag of a tag, see [`System::to_tag`].
+const fn layout_kind(layout: &ChannelLayout) -> u8 {
+    match layout {
+        ChannelLayout::Mono | ChannelLayout::Stereo | ChannelLayout::Other(_) => 0,
+        ChannelLayout::Quad | ChannelLayout::Surround51 | ChannelLayout::Surround71 => 1,
+        ChannelLayout::Ambisonics(_) => 2,
+    }
+}
+
+/// The inverse of [`layout_kind`].
+const fn layout_from_kind(layout: u8, channels: NonZeroU8) -> Option<ChannelLayout> {
+    match (layout, channels.get()) {
+        (0, _) => Some(ChannelLayout::from_channels(channels)),
+        (1, 4) => Some(ChannelLayout::Quad),
+        (1, 6) => Some(ChannelLayout::Surround51),
+        (1, 8) => Some(ChannelLayout::Surround71),
+        (2, channels) => {
+            let mut n: u16 = 1;
+            while n * n < channels as u16 {
+                n += 1;
+            }
+            if n * n == channels as u16 {
+                ChannelLayout::ambisonics((n - 1) as u8)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+impl DynSystem {
+    /// Whether this system is equal to `system`.
+    ///
+    /// Unlike [`DynSystem::is`], `system` does not need to be known at compile
+    /// time.
+    #[inline]
+    pub const fn matches(&self, system: &System) -> bool {
+        let this = self.to_system();
+        this.same_rate(system) && this.same_layout(system) && this.same_sample_type(system)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{presets, system, AUDIO_CD};
+
+    #[test]
+    fn test_round_trip() {
+        for &(name, system) in presets::ALL.iter().chain([
+            &("16 channels", system!(48_000, 16, f32)),
+            &("packed 24-bit", system!(48_000, Stereo, crate::I24)),
+            &(
+                "big-endian",
+                AUDIO_CD.with_sample_type(SampleType::new_be::<i16>()),
+            ),
+            &("quad", system!(48_000, Quad, i16)),
+            &("5.1", system!(48_000, Surround51, f32)),
+            &("7.1", system!(48_000, Surround71, crate::I24)),
+            &(
+                "zeroth-order ambisonics",
+                system!(48_000, ChannelLayout::ambisonics(0).unwrap(), f32),
+            ),
+            &(
+                "first-order ambisonics",
+                system!(48_000, ChannelLayout::ambisonics(1).unwrap(), f32),
+            ),
+            &(
+                "14th-order ambisonics",
+                system!(48_000, ChannelLayout::ambisonics(14).unwrap(), f32),
+            ),
+            &("4 channels", system!(48_000, 4, f32)),
+        ]) {
+            let decoded = System::from_tag(system.to_tag()).unwrap();
+            assert!(decoded.matches(&system), "{name}");
+            assert_eq!(system.to_tag(), decoded.to_system().to_tag());
+            assert_eq!(system.channel_layout, decoded.channel_layout, "{name}");
+        }
+
+        assert_eq!(0x1802_0402_0001_7700, presets::DVD_AUDIO_96K_24.to_tag());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let cd = presets::AUDIO_CD.to_tag();
+
+        assert_eq!(Err(TagError::ZeroRate), System::from_tag(cd & !0xffff_ffff));
+        assert_eq!(
+            Err(TagError::ZeroChannels),
+            System::from_tag(cd & !(0xff << 32))
+        );
+        assert_eq!(
+            Err(TagError::UnknownKind(0)),
+            System::from_tag(system!(8_000, Mono, [u8; 3]).to_tag())
+        );
+        #[cfg(feature = "half")]
+        {
+            let f16 = AUDIO_CD.with_sample_type(SampleType::f16());
+            let bf16 = AUDIO_CD.with_sample_type(SampleType::bf16());
+            assert_ne!(f16.to_tag(), bf16.to_tag());
+            assert!(System::from_tag(f16.to_tag()).unwrap().matches(&f16));
+            assert_eq!(
+                Err(TagError::UnknownKind(0)),
+                System::from_tag(bf16.to_tag())
+            );
+        }
+        assert_eq!(
+            Err(TagError::UnknownKind(4)),
+            System::from_tag(cd & !(0xff << 48) | 4 << 48)
+        );
+        assert_eq!(
+            Err(TagError::UnknownKind(0x32)),
+            System::from_tag(cd & !(0xff << 48) | 0x32 << 48)
+        );
+        assert!(matches!(
+            System::from_tag(cd & !(0xff << 40) | 5 << 40),
+            Err(TagError::UnsupportedSampleType { byte_depth: 5, .. })
+        ));
+        assert_eq!(
+            Err(TagError::InvalidLayout {
+                layout: 1,
+                channels: 2
+            }),
+            System::from_tag(cd | 1 << 54)
+        );
+        assert_eq!(
+            Err(TagError::InvalidLayout {
+                layout: 2,
+                channels: 2
+            }),
+            System::from_tag(cd | 2 << 54)
+        );
+        assert_eq!(
+            Err(TagError::InvalidLayout {
+                layout: 3,
+                channels: 2
+            }),
+            System::from_tag(cd | 3 << 54)
+        );
+        assert!(matches!(
+            System::from_tag(cd & !(0xff << 32) | 255 << 32 | 2 << 54),
+            Err(TagError::InvalidLayout { layout: 2, .. })
+        ));
+        assert!(matches!(
+            System::from_tag(cd | 17 << 56),
+            Err(TagError::UnsupportedSampleType { bit_depth: 17, .. })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use core::{fmt, num::NonZeroU32};
+
+use crate::{
+    rounding::{gcd, Rounding},
+    Frames, OverflowError, System,
+};
+
+/// A musical tempo, in beats per minute.
+///
+/// The tempo is stored as an exact ratio, so that conversions at integral
+/// tempos (e.g. 120 BPM) are exact.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Tempo {
+    num: NonZeroU32,
+    den: NonZeroU32,
+}
+
+impl Tempo {
+    /// Create a `Tempo` of a whole number of beats per minute, or `None` if
+    /// `bpm` is zero.
+    #[inline]
+    pub const fn from_bpm(bpm: u32) -> Option<Self> {
+        Self::from_ratio(bpm, 1)
+    }
+
+    /// Create a `Tempo` of `num / den` beats per minute, or `None` if either
+    /// is zero.
+    #[inline]
+    pub const fn from_ratio(num: u32, den: u32) -> Option<Self> {
+        if num == 0 || den == 0 {
+            return None;
+        }
+
+        let gcd = gcd(num as u128, den as u128) as u32;
+
+        match (NonZeroU32::new(num / gcd), NonZeroU32::new(den / gcd)) {
+            (Some(num), Some(den)) => Some(Self { num, den }),
+            _ => None,
+        }
+    }
+
+    /// Create a `Tempo` from a fractional number of beats per minute, which is
+    /// rounded to the nearest thousandth of a beat.
+    ///
+    /// Returns `None` if `bpm` is not positive, is not finite, or is too large
+    /// to represent.
+    pub fn from_bpm_f64(bpm: f64) -> Option<Self> {
+        let millis = (bpm * 1_000.).round();
+
+        if !millis.is_finite() || millis < 1. || millis > u32::MAX as f64 {
+            return None;
+        }
+
+        Self::from_ratio(millis as u32, 1_000)
+    }
+
+    /// The tempo in beats per minute, as a `(numerator, denominator)` pair in
+    /// lowest terms.
+    #[inline]
+    pub const fn ratio(&self) -> (NonZeroU32, NonZeroU32) {
+        (self.num, self.den)
+    }
+
+    /// The tempo in beats per minute.
+    #[inline]
+    pub fn bpm(&self) -> f64 {
+        self.num.get() as f64 / self.den.get() as f64
+    }
+
+    /// The number of frames spanned by `beats_num / beats_den` beats, computed
+    /// exactly before rounding.
+    pub(crate) const fn frames_for_beats<const SYS: System>(
+        &self,
+        beats_num: u128,
+        beats_den: u128,
+        rounding: Rounding,
+    ) -> Result<Frames<SYS>, OverflowError> {
+        // frames = beats * 60 * rate / bpm
+        let num = match beats_num.checked_mul(60 * SYS.sample_rate.get().get() as u128) {
+            Some(n) => n.checked_mul(self.den.get() as u128),
+            None => None,
+        };
+        let den = beats_den.checked_mul(self.num.get() as u128);
+
+        match (num, den) {
+            (Some(num), Some(den)) => {
+                let frames = rounding.div(num, den);
+                if frames <= usize::MAX as u128 {
+                    Ok(Frames::new(frames as usize))
+                } else {
+                    Err(OverflowError(()))
+                }
+            }
+            _ => Err(OverflowError(())),
+        }
+    }
+}
+
+impl fmt::Display for Tempo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} BPM", self.bpm())
+    }
+}
+
+/// Error returned when converting a number of beats into [`Frames`].
+#[derive(thiserror::Error, Debug)]
+pub enum BeatsError {
+    #[error("Number of beats must be finite and non-negative")]
+    Invalid,
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The number of frames in a single beat at the given tempo, rounded to
+    /// the nearest frame.
+    ///
+    /// Exact whenever `60 * sample_rate` is divisible by the tempo, e.g. 24 000
+    /// frames for 120 BPM at 48 kHz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows (only possible for extremely slow
+    /// tempos).
+    #[inline]
+    #[track_caller]
+    pub const fn per_beat(tempo: Tempo) -> Self {
+        match tempo.frames_for_beats(1, 1, Rounding::Nearest) {
+            Ok(frames) => frames,
+            Err(_) => {
+                panic!("Overflowed trying to calculate frames per beat")
+            }
+        }
+    }
+
+    /// The number of beats at the given tempo spanned by these frames.
+    #[inline]
+    pub fn to_beats(self, tempo: Tempo) -> f64 {
+        let (num, den) = tempo.ratio();
+        (self.get() as f64 * num.get() as f64)
+            / (60. * SYS.sample_rate.get().get() as f64 * den.get() as f64)
+    }
+
+    /// The number of frames spanned by `beats` beats at the given tempo.
+    ///
+    /// Whole numbers of beats are converted using exact integer math, so the
+    /// result is off by at most one frame from the exact product (and only in
+    /// the direction of `rounding`). Fractional beats are converted using
+    /// floating point math before rounding.
+    pub fn from_beats(tempo: Tempo, beats: f64, rounding: Rounding) -> Result<Self, BeatsError> {
+        if !beats.is_finite() || beats < 0. {
+            return Err(BeatsError::Invalid);
+        }
+
+        if beats.fract() == 0. && beats <= u64::MAX as f64 {
+            return Ok(tempo.frames_for_beats(beats as u128, 1, rounding)?);
+        }
+
+        let (num, den) = tempo.ratio();
+        let frames = rounding.round(
+            beats * 60. * SYS.sample_rate.get().get() as f64 * den.get() as f64 / num.get() as f64,
+        );
+
+        if frames <= usize::MAX as f64 {
+            Ok(Self::new(frames as usize))
+        } else {
+            Err(OverflowError(()).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    #[test]
+    fn test_tempo() {
+        assert!(Tempo::from_bpm(0).is_none());
+        assert!(Tempo::from_bpm_f64(0.).is_none());
+        assert!(Tempo::from_bpm_f64(-120.).is_none());
+        assert!(Tempo::from_bpm_f64(f64::NAN).is_none());
+
+        assert_eq!(Tempo::from_bpm(120), Tempo::from_ratio(240, 2));
+        assert_eq!(Tempo::from_bpm(120), Tempo::from_bpm_f64(120.));
+        assert_eq!(Tempo::from_ratio(1_205, 10), Tempo::from_bpm_f64(120.5));
+    }
+
+    #[test]
+    fn test_per_beat() {
+        const SYS: System = system!(48_000, Stereo, i16);
+
+        let tempo = Tempo::from_bpm(120).unwrap();
+        assert_eq!(24_000, Frames::<SYS>::per_beat(tempo).get());
+        assert_eq!(0.5, Frames::<SYS>::new(12_000).to_beats(tempo));
+        assert_eq!(
+            48_000,
+            Frames::<SYS>::from_beats(tempo, 2., Rounding::Down)
+                .unwrap()
+                .get()
+        );
+        assert_eq!(
+            36_000,
+            Frames::<SYS>::from_beats(tempo, 1.5, Rounding::Down)
+                .unwrap()
+                .get()
+        );
+
+        assert!(matches!(
+            Frames::<SYS>::from_beats(tempo, -1., Rounding::Down),
+            Err(BeatsError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn test_no_drift() {
+        const SYS: System = system!(44_100, Stereo, i16);
+
+        // 20 671.875 frames per beat
+        let tempo = Tempo::from_bpm(128).unwrap();
+        let exact = 44_100. * 60. / 128.;
+
+        for beats in 1..=1_000 {
+            let frames = Frames::<SYS>::from_beats(tempo, beats as f64, Rounding::Nearest).unwrap();
+            assert!((frames.get() as f64 - exact * beats as f64).abs() <= 1.);
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        const SYS: System = system!(4_000_000_000, Mono, i16);
+
+        let slow = Tempo::from_ratio(1, u32::MAX).unwrap();
+        assert!(matches!(
+            Frames::<SYS>::from_beats(slow, 1., Rounding::Down),
+            Err(BeatsError::Overflow(_))
+        ));
+        assert!(matches!(
+            Frames::<SYS>::from_beats(slow, 1e30, Rounding::Down),
+            Err(BeatsError::Overflow(_))
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+use core::num::NonZeroU16;
+
+use crate::{
+    rounding::{gcd, Rounding},
+    Frames, OverflowError, System, Tempo,
+};
+
+/// A MIDI sequencer time position or span, measured in ticks (pulses).
+///
+/// The duration of a single tick is defined by a [`Ppq`] resolution and a
+/// [`Tempo`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Ticks(u64);
+
+impl Ticks {
+    #[inline]
+    pub const fn new(n: u64) -> Self {
+        Self(n)
+    }
+
+    #[inline]
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for Ticks {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Ticks> for u64 {
+    #[inline]
+    fn from(value: Ticks) -> Self {
+        value.get()
+    }
+}
+
+/// A MIDI timing resolution, in pulses (ticks) per quarter note.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Ppq(pub NonZeroU16);
+
+/// Convert a number of ticks into frames at a constant tempo, rounding to the
+/// nearest frame.
+pub const fn ticks_to_frames<const SYS: System>(
+    ticks: Ticks,
+    ppq: Ppq,
+    tempo: Tempo,
+) -> Result<Frames<SYS>, OverflowError> {
+    tempo.frames_for_beats(ticks.get() as u128, ppq.0.get() as u128, Rounding::Nearest)
+}
+
+/// Convert a number of frames into ticks at a constant tempo.
+pub const fn frames_to_ticks<const SYS: System>(
+    frames: Frames<SYS>,
+    ppq: Ppq,
+    tempo: Tempo,
+    rounding: Rounding,
+) -> Result<Ticks, OverflowError> {
+    let (bpm_num, bpm_den) = tempo.ratio();
+
+    // ticks = frames * ppq * bpm / (60 * rate)
+    let num = match (frames.get() as u128).checked_mul(ppq.0.get() as u128) {
+        Some(n) => n.checked_mul(bpm_num.get() as u128),
+        None => None,
+    };
+    let den = 60 * SYS.sample_rate.get().get() as u128 * bpm_den.get() as u128;
+
+    match num {
+        Some(num) => {
+            let ticks = rounding.div(num, den);
+            if ticks <= u64::MAX as u128 {
+                Ok(Ticks::new(ticks as u64))
+            } else {
+                Err(OverflowError(()))
+            }
+        }
+        None => Err(OverflowError(())),
+    }
+}
+
+/// Convert a sequence of consecutive segments, each lasting a number of ticks
+/// at its own tempo, into their total length in frames.
+///
+/// The total is computed exactly and rounded to the nearest frame only once, so
+/// splitting a span into several segments of the same tempo does not change
+/// the result.
+pub fn segments_to_frames<const SYS: System>(
+    segments: &[(Ticks, Tempo)],
+    ppq: Ppq,
+) -> Result<Frames<SYS>, OverflowError> {
+    let rate = SYS.sample_rate.get().get() as u128;
+
+    // running total, in frames, as an exact fraction
+    let (mut num, mut den) = (0u128, 1u128);
+
+    for &(ticks, tempo) in segments {
+        let (bpm_num, bpm_den) = tempo.ratio();
+
+        // frames = ticks * 60 * rate / (ppq * bpm)
+        let seg_num = (ticks.get() as u128)
+            .checked_mul(60 * rate * bpm_den.get() as u128)
+            .ok_or(OverflowError(()))?;
+        let seg_den = ppq.0.get() as u128 * bpm_num.get() as u128;
+
+        let common = gcd(den, seg_den);
+        let (scale, seg_scale) = (seg_den / common, den / common);
+
+        num = num
+            .checked_mul(scale)
+            .and_then(|num| num.checked_add(seg_num.checked_mul(seg_scale)?))
+            .ok_or(OverflowError(()))?;
+        den = den.checked_mul(scale).ok_or(OverflowError(()))?;
+
+        let common = gcd(num, den);
+        (num, den) = (num / common, den / common);
+    }
+
+    let frames = Rounding::Nearest.div(num, den);
+    usize::try_from(frames)
+        .map(Frames::new)
+        .map_err(|_| OverflowError(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    const SYS: System = system!(44_100, Stereo, i16);
+    const PPQ: Ppq = Ppq(match NonZeroU16::new(960) {
+        Some(n) => n,
+        None => panic!(),
+    });
+
+    #[test]
+    fn test_song() -> Result<(), OverflowError> {
+        let tempo = Tempo::from_bpm(174).unwrap();
+
+        // four minutes at 174 BPM
+        let song = Ticks::new(4 * 174 * 960);
+        let frames = ticks_to_frames::<SYS>(song, PPQ, tempo)?;
+        assert_eq!(Frames::new(4 * 60 * 44_100), frames);
+        assert_eq!(song, frames_to_ticks(frames, PPQ, tempo, Rounding::Down)?);
+
+        for ticks in (0..song.get()).step_by(7_919) {
+            let frames = ticks_to_frames::<SYS>(Ticks::new(ticks), PPQ, tempo)?;
+            for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+                let round_trip = frames_to_ticks(frames, PPQ, tempo, rounding)?;
+                assert!(round_trip.get().abs_diff(ticks) <= 1);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segments() -> Result<(), OverflowError> {
+        let slow = Tempo::from_bpm(90).unwrap();
+        let fast = Tempo::from_bpm(174).unwrap();
+
+        assert_eq!(
+            ticks_to_frames::<SYS>(Ticks::new(1_000), PPQ, fast)?,
+            segments_to_frames::<SYS>(&[(Ticks::new(333), fast), (Ticks::new(667), fast)], PPQ)?
+        );
+
+        let frames = segments_to_frames::<SYS>(
+            &[(Ticks::new(960 * 90), slow), (Ticks::new(960 * 174), fast)],
+            PPQ,
+        )?;
+        assert_eq!(Frames::new(2 * 60 * 44_100), frames);
+
+        assert_eq!(Frames::new(0), segments_to_frames::<SYS>(&[], PPQ)?);
+
+        Ok(())
+    }
+}
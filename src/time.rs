@@ -0,0 +1,179 @@
+//! Conversions between audio spans and the `time` crate's (signed)
+//! [`Duration`], with nanosecond precision (limited to durations of ±584
+//! years).
+//!
+//! Note that the `time` crate implements `PartialEq<time::Duration>` for
+//! [`core::time::Duration`], so once it is a dependency, untyped `try_into()`s
+//! whose result is compared against a `core::time::Duration` (e.g.
+//! `assert_eq!(dur, frames.try_into()?)`) are ambiguous, and need their target
+//! type spelled out.
+
+use ::time::Duration;
+
+use crate::{
+    sample_rate::split_nanos,
+    signed_duration::{frames_delta_from_nanos, frames_delta_to_nanos, frames_from_nanos},
+    Bytes, Frames, FramesDelta, OverflowError, Samples, SignedDurationError, System,
+};
+
+/// The duration of `frames`, truncated to whole nanoseconds.
+#[inline]
+fn frames_to_duration<const SYS: System>(frames: Frames<SYS>) -> Result<Duration, OverflowError> {
+    Ok(nanos_to_duration(frames.to_nanos_u64()?.into()))
+}
+
+#[inline]
+fn nanos_to_duration(nanos: i128) -> Duration {
+    // can't overflow: the magnitude of `nanos` fits in a `u64`
+    let (secs, subsec_nanos) = split_nanos(nanos.unsigned_abs());
+    let (secs, subsec_nanos) = (secs as i64, subsec_nanos as i32);
+    if nanos < 0 {
+        Duration::new(-secs, -subsec_nanos)
+    } else {
+        Duration::new(secs, subsec_nanos)
+    }
+}
+
+/// Truncates any partial frame.
+impl<const SYS: System> TryFrom<Duration> for Frames<SYS> {
+    type Error = SignedDurationError;
+
+    #[inline]
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        frames_from_nanos(value.whole_nanoseconds())
+    }
+}
+
+/// Truncates any partial nanosecond.
+impl<const SYS: System> TryFrom<Frames<SYS>> for Duration {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Frames<SYS>) -> Result<Self, Self::Error> {
+        frames_to_duration(value)
+    }
+}
+
+/// Truncates any partial frame.
+impl<const SYS: System> TryFrom<Duration> for Samples<SYS> {
+    type Error = SignedDurationError;
+
+    #[inline]
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        Ok(Frames::<SYS>::try_from(value)?.try_into()?)
+    }
+}
+
+/// Truncates any partial nanosecond.
+impl<const SYS: System> TryFrom<Samples<SYS>> for Duration {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Samples<SYS>) -> Result<Self, Self::Error> {
+        frames_to_duration(Frames::from(value))
+    }
+}
+
+/// Truncates any partial frame.
+impl<const SYS: System> TryFrom<Duration> for Bytes<SYS> {
+    type Error = SignedDurationError;
+
+    #[inline]
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        Ok(Frames::<SYS>::try_from(value)?.try_into()?)
+    }
+}
+
+/// Truncates any partial nanosecond.
+impl<const SYS: System> TryFrom<Bytes<SYS>> for Duration {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Bytes<SYS>) -> Result<Self, Self::Error> {
+        frames_to_duration(Frames::from(value))
+    }
+}
+
+/// Truncates any partial frame, i.e. rounds towards zero.
+impl<const SYS: System> TryFrom<Duration> for FramesDelta<SYS> {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        frames_delta_from_nanos(value.whole_nanoseconds())
+    }
+}
+
+/// Truncates any partial nanosecond, i.e. rounds towards zero.
+impl<const SYS: System> TryFrom<FramesDelta<SYS>> for Duration {
+    type Error = OverflowError;
+
+    #[inline]
+    fn try_from(value: FramesDelta<SYS>) -> Result<Self, Self::Error> {
+        Ok(nanos_to_duration(frames_delta_to_nanos(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[test]
+    fn test_duration() -> Result<(), OverflowError> {
+        let dur = Duration::milliseconds(1_500);
+        let frames = Frames::<AUDIO_CD>::try_from(dur).unwrap();
+        assert_eq!(66_150, frames.get());
+        assert_eq!(dur, Duration::try_from(frames)?);
+
+        let samples = Samples::<AUDIO_CD>::try_from(dur).unwrap();
+        assert_eq!(132_300, samples.get());
+        assert_eq!(dur, Duration::try_from(samples)?);
+
+        let bytes = Bytes::<AUDIO_CD>::try_from(dur).unwrap();
+        assert_eq!(264_600, bytes.get());
+        assert_eq!(dur, Duration::try_from(bytes)?);
+
+        // 1 frame at 44.1 kHz is 22_675.736... ns
+        let frame = Duration::try_from(Frames::<AUDIO_CD>::new(1))?;
+        assert_eq!(22_675, frame.whole_nanoseconds());
+
+        for dur in [-dur, Duration::new(0, -500_000)] {
+            assert_eq!(
+                Err(SignedDurationError::Negative),
+                Frames::<AUDIO_CD>::try_from(dur)
+            );
+            assert_eq!(
+                Err(SignedDurationError::Negative),
+                Bytes::<AUDIO_CD>::try_from(dur)
+            );
+        }
+        assert_eq!(
+            Err(SignedDurationError::Overflow),
+            Frames::<AUDIO_CD>::try_from(Duration::MAX)
+        );
+        assert!(Duration::try_from(Frames::<AUDIO_CD>::new(usize::MAX)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration_signed() -> Result<(), OverflowError> {
+        for millis in [-1_500, 0, 1_500] {
+            let dur = Duration::milliseconds(millis);
+            let frames = FramesDelta::<AUDIO_CD>::try_from(dur)?;
+            assert_eq!(millis as isize * 441 / 10, frames.get());
+            assert_eq!(dur, Duration::try_from(frames)?);
+        }
+
+        // partial frames are truncated towards zero
+        let frames = FramesDelta::<AUDIO_CD>::try_from(Duration::new(0, -500_000))?;
+        assert_eq!(-22, frames.get());
+        assert_eq!(Duration::new(0, -498_866), Duration::try_from(frames)?);
+
+        assert!(FramesDelta::<AUDIO_CD>::try_from(Duration::MIN).is_err());
+        assert!(Duration::try_from(FramesDelta::<AUDIO_CD>::new(isize::MIN)).is_err());
+
+        Ok(())
+    }
+}
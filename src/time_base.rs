@@ -0,0 +1,220 @@
+use core::num::NonZeroU32;
+
+use crate::{Frames, FramesDelta, OverflowError, Rounding, System};
+
+/// A time base, as used for timestamps by libav (`AVRational`) and most
+/// container formats: the duration of a single tick, `num / den` seconds (e.g.
+/// `1/90000` for MPEG-TS, or `1001/30000` for NTSC video frames).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimeBase {
+    pub num: u32,
+    pub den: NonZeroU32,
+}
+
+impl TimeBase {
+    /// The 90 kHz time base of MPEG-TS timestamps.
+    pub const MPEG_TS: Self = Self::new(1, NonZeroU32::new(90_000).unwrap());
+
+    #[inline]
+    pub const fn new(num: u32, den: NonZeroU32) -> Self {
+        Self { num, den }
+    }
+
+    /// The time base of a single frame at `SYS`'s sample rate, e.g. `1/48000`.
+    #[inline]
+    pub const fn of_frames<const SYS: System>() -> Self {
+        Self::new(1, SYS.sample_rate.get())
+    }
+}
+
+/// Multiply `value` by `mul / div`, rounding its magnitude in the direction of
+/// `rounding` (so e.g. `Rounding::Nearest` rounds halfway cases away from zero,
+/// like libav's `AV_ROUND_NEAR_INF`).
+#[inline]
+pub(crate) const fn rescale(value: i128, mul: u128, div: u128, rounding: Rounding) -> i128 {
+    let magnitude = rounding.div(value.unsigned_abs() * mul, div) as i128;
+
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// The position of a timestamp of `value` ticks of `tb`, rounded in the
+    /// direction of `rounding`. Equivalent to libav's `av_rescale_q`, computed
+    /// without intermediate overflow.
+    ///
+    /// Timestamps may be negative (e.g. for pre-roll), so the result is a
+    /// [`FramesDelta`] relative to the zero timestamp.
+    #[inline]
+    pub const fn from_timebase(
+        value: i64,
+        tb: TimeBase,
+        rounding: Rounding,
+    ) -> Result<FramesDelta<SYS>, OverflowError> {
+        let frames = rescale(
+            value as i128,
+            tb.num as u128 * SYS.sample_rate.get().get() as u128,
+            tb.den.get() as u128,
+            rounding,
+        );
+
+        if isize::MIN as i128 <= frames && frames <= isize::MAX as i128 {
+            Ok(FramesDelta::new(frames as isize))
+        } else {
+            Err(OverflowError(()))
+        }
+    }
+
+    /// The timestamp of this position in ticks of `tb`, rounded in the
+    /// direction of `rounding`.
+    ///
+    /// Returns an error if the result does not fit in an `i64`, or if `tb` is
+    /// zero.
+    #[inline]
+    pub const fn to_timebase(
+        &self,
+        tb: TimeBase,
+        rounding: Rounding,
+    ) -> Result<i64, OverflowError> {
+        to_timebase::<SYS>(self.get() as i128, tb, rounding)
+    }
+}
+
+impl<const SYS: System> FramesDelta<SYS> {
+    /// See [`Frames::to_timebase`].
+    #[inline]
+    pub const fn to_timebase(
+        &self,
+        tb: TimeBase,
+        rounding: Rounding,
+    ) -> Result<i64, OverflowError> {
+        to_timebase::<SYS>(self.get() as i128, tb, rounding)
+    }
+}
+
+#[inline]
+const fn to_timebase<const SYS: System>(
+    frames: i128,
+    tb: TimeBase,
+    rounding: Rounding,
+) -> Result<i64, OverflowError> {
+    if tb.num == 0 {
+        return Err(OverflowError(()));
+    }
+
+    let ticks = rescale(
+        frames,
+        tb.den.get() as u128,
+        tb.num as u128 * SYS.sample_rate.get().get() as u128,
+        rounding,
+    );
+
+    if i64::MIN as i128 <= ticks && ticks <= i64::MAX as i128 {
+        Ok(ticks as i64)
+    } else {
+        Err(OverflowError(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    const fn tb(num: u32, den: u32) -> TimeBase {
+        TimeBase::new(num, NonZeroU32::new(den).unwrap())
+    }
+
+    #[test]
+    fn test_av_rescale_q() -> Result<(), OverflowError> {
+        const SYS: System = system!(48_000, Stereo, f32);
+
+        // (value, tb, av_rescale_q(value, tb, 1/48000))
+        for (value, tb, expected) in [
+            (90_000, TimeBase::MPEG_TS, 48_000),
+            (3, TimeBase::MPEG_TS, 2),
+            (-3, TimeBase::MPEG_TS, -2),
+            (-1, TimeBase::MPEG_TS, -1),
+            (1, tb(1, 96_000), 1),
+            (-1, tb(1, 96_000), -1),
+            (1_001, tb(1_001, 30_000), 1_603_202),
+            (-1_024, tb(1, 44_100), -1_115),
+            (i64::MAX, tb(1, 48_000), i64::MAX),
+            (i64::MIN, tb(1, 48_000), i64::MIN),
+            (i64::MAX / 2, tb(1, 96_000), i64::MAX / 4 + 1),
+        ] {
+            assert_eq!(
+                expected as isize,
+                Frames::<SYS>::from_timebase(value, tb, Rounding::Nearest)?.get(),
+                "{value} {tb:?}"
+            );
+        }
+
+        // (frames, tb, av_rescale_q(frames, 1/48000, tb))
+        for (frames, tb, expected) in [
+            (48_000, TimeBase::MPEG_TS, 90_000),
+            (1, TimeBase::MPEG_TS, 2),
+            (1_603_202, tb(1_001, 30_000), 1_001),
+            (-1_115, tb(1, 44_100), -1_024),
+            (-1, tb(1, 96_000), -2),
+        ] {
+            assert_eq!(
+                expected,
+                FramesDelta::<SYS>::new(frames).to_timebase(tb, Rounding::Nearest)?,
+                "{frames} {tb:?}"
+            );
+        }
+        assert_eq!(
+            90_000,
+            Frames::<SYS>::new(48_000).to_timebase(TimeBase::MPEG_TS, Rounding::Down)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rounding() -> Result<(), OverflowError> {
+        const SYS: System = system!(48_000, Stereo, f32);
+
+        // 3 ticks are 1.6 frames; rounding is symmetric around zero
+        for (rounding, expected) in [
+            (Rounding::Down, 1),
+            (Rounding::Up, 2),
+            (Rounding::Nearest, 2),
+        ] {
+            let from = |value| Frames::<SYS>::from_timebase(value, TimeBase::MPEG_TS, rounding);
+            assert_eq!(expected, from(3)?.get());
+            assert_eq!(-expected, from(-3)?.get());
+        }
+
+        assert_eq!(TimeBase::of_frames::<SYS>(), tb(1, 48_000));
+        assert_eq!(
+            -7,
+            FramesDelta::<SYS>::new(-7)
+                .to_timebase(TimeBase::of_frames::<SYS>(), Rounding::Down)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() {
+        const SYS: System = system!(48_000, Stereo, f32);
+
+        assert!(Frames::<SYS>::from_timebase(i64::MAX, tb(1, 1), Rounding::Down).is_err());
+        assert!(Frames::<SYS>::from_timebase(i64::MIN, tb(u32::MAX, 1), Rounding::Down).is_err());
+        assert!(Frames::<SYS>::new(usize::MAX)
+            .to_timebase(tb(1, u32::MAX), Rounding::Down)
+            .is_err());
+        assert!(Frames::<SYS>::new(1)
+            .to_timebase(tb(0, 1), Rounding::Down)
+            .is_err());
+        assert_eq!(
+            FramesDelta::new(0),
+            Frames::<SYS>::from_timebase(i64::MAX, tb(0, 1), Rounding::Down).unwrap()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{Frames, OverflowError, System};
+
+/// An audio time span (or position) decomposed into hours, minutes, seconds and
+/// the remaining frames, e.g. for displaying a transport position.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct TimeComponents {
+    pub hours: u64,
+    /// Always less than 60.
+    pub minutes: u8,
+    /// Always less than 60.
+    pub seconds: u8,
+    /// The sub-second remainder, in frames. Always less than the sample rate.
+    pub frames: u32,
+}
+
+/// Error returned when converting [`TimeComponents`] into [`Frames`].
+#[derive(thiserror::Error, Debug)]
+pub enum TimeComponentsError {
+    #[error("Time component is out of range")]
+    OutOfRange,
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+impl TimeComponents {
+    /// Recompose these components into [`Frames`].
+    ///
+    /// Fails if `minutes` or `seconds` are 60 or more, if `frames` is not less
+    /// than `SYS`'s sample rate, or on overflow.
+    pub const fn to_frames<const SYS: System>(&self) -> Result<Frames<SYS>, TimeComponentsError> {
+        let rate = SYS.sample_rate.get().get();
+
+        if self.minutes >= 60 || self.seconds >= 60 || self.frames >= rate {
+            return Err(TimeComponentsError::OutOfRange);
+        }
+
+        let secs = self.hours as u128 * 3_600 + self.minutes as u128 * 60 + self.seconds as u128;
+        let frames = secs * rate as u128 + self.frames as u128;
+
+        if frames <= usize::MAX as u128 {
+            Ok(Frames::new(frames as usize))
+        } else {
+            Err(TimeComponentsError::Overflow(OverflowError(())))
+        }
+    }
+}
+
+impl<const SYS: System> Frames<SYS> {
+    /// Decompose these frames into hours, minutes, seconds and remaining
+    /// frames.
+    ///
+    /// The decomposition is exact, and can be reversed using
+    /// [`TimeComponents::to_frames`].
+    #[inline]
+    pub const fn to_components(&self) -> TimeComponents {
+        let rate = SYS.sample_rate.get().get() as usize;
+        let secs = (self.get() / rate) as u64;
+
+        TimeComponents {
+            hours: secs / 3_600,
+            minutes: (secs / 60 % 60) as u8,
+            seconds: (secs % 60) as u8,
+            frames: (self.get() % rate) as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system;
+
+    fn assert_round_trip<const SYS: System>() {
+        let rate = SYS.sample_rate.get().get() as usize;
+        let end = 5 * 3_600 * rate;
+
+        for n in (0..end).step_by(rate / 7 + 1).chain([end - 1, end]) {
+            let frames = Frames::<SYS>::new(n);
+            assert_eq!(frames, frames.to_components().to_frames().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip::<{ system!(44_100, Stereo, i16) }>();
+        assert_round_trip::<{ system!(96_000, Stereo, f32) }>();
+    }
+
+    #[test]
+    fn test_components() {
+        const SYS: System = system!(44_100, Stereo, i16);
+
+        let components = Frames::<SYS>::new((3_600 + 2 * 60 + 3) * 44_100 + 100).to_components();
+        assert_eq!(
+            TimeComponents {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 100
+            },
+            components
+        );
+
+        for invalid in [
+            TimeComponents {
+                minutes: 60,
+                ..components
+            },
+            TimeComponents {
+                seconds: 60,
+                ..components
+            },
+            TimeComponents {
+                frames: 44_100,
+                ..components
+            },
+        ] {
+            assert!(matches!(
+                invalid.to_frames::<SYS>(),
+                Err(TimeComponentsError::OutOfRange)
+            ));
+        }
+
+        assert!(matches!(
+            TimeComponents {
+                hours: u64::MAX,
+                ..components
+            }
+            .to_frames::<SYS>(),
+            Err(TimeComponentsError::Overflow(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+//! [`tokio`] counterparts of the [`CountingReader`] and [`CountingWriter`]
+//! adapters.
+//!
+//! The same types implement tokio's [`AsyncRead`], [`AsyncBufRead`] and
+//! [`AsyncWrite`] when their inner type does, so they share the same
+//! accessors. Only [`Unpin`] inner types are supported; other ones can be
+//! wrapped in a `Pin<Box<_>>` first.
+
+use std::{
+    io::{self, IoSlice},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use ::tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{CountingReader, CountingWriter, System};
+
+/// A [`CountingReader`] wrapping a tokio [`AsyncRead`].
+pub type AsyncCountingReader<R, const SYS: System> = CountingReader<R, SYS>;
+
+/// A [`CountingWriter`] wrapping a tokio [`AsyncWrite`].
+pub type AsyncCountingWriter<W, const SYS: System> = CountingWriter<W, SYS>;
+
+impl<R: AsyncRead + Unpin, const SYS: System> AsyncRead for CountingReader<R, SYS> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+
+        ready!(Pin::new(this.get_mut()).poll_read(cx, buf))?;
+        this.count(buf.filled().len() - filled);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Bytes are counted once they are [consumed](AsyncBufRead::consume).
+impl<R: AsyncBufRead + Unpin, const SYS: System> AsyncBufRead for CountingReader<R, SYS> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(self.get_mut().get_mut()).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        Pin::new(this.get_mut()).consume(amt);
+        this.count(amt);
+    }
+}
+
+/// Like the [`Write`](std::io::Write) implementation, once a
+/// [limited](CountingWriter::limit) writer reaches its limit, writes of
+/// non-empty buffers complete with `Ok(0)`.
+impl<W: AsyncWrite + Unpin, const SYS: System> AsyncWrite for CountingWriter<W, SYS> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let buf = this.truncate(buf);
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let n = ready!(Pin::new(this.get_mut()).poll_write(cx, buf))?;
+        this.count(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        if self.is_limited() {
+            let buf = bufs.iter().find(|buf| !buf.is_empty());
+            return self.poll_write(cx, buf.map_or(&[], |buf| buf));
+        }
+
+        let this = self.get_mut();
+        let n = ready!(Pin::new(this.get_mut()).poll_write_vectored(cx, bufs))?;
+        this.count(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.get_ref().is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(self.get_mut().get_mut()).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(self.get_mut().get_mut()).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ::tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::AUDIO_CD;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_duplex() -> io::Result<()> {
+        // a small pipe, so that writes only make partial progress and reads
+        // end mid-frame
+        let (tx, rx) = duplex(61);
+        let mut writer = AsyncCountingWriter::<_, AUDIO_CD>::new(tx);
+        let mut reader = AsyncCountingReader::<_, AUDIO_CD>::new(rx);
+
+        let write = async {
+            let data = vec![0; 17_640];
+            let mut written = 0;
+            for size in [3, 7, 13, 1_009].into_iter().cycle() {
+                let end = (written + size).min(data.len());
+                writer.write_all(&data[written..end]).await?;
+                written = end;
+                if written == data.len() {
+                    break;
+                }
+            }
+            writer.shutdown().await?;
+            io::Result::Ok(writer)
+        };
+        let read = async {
+            let mut buf = [0; 11];
+            let mut partial_frames = 0;
+            while reader.read(&mut buf).await? != 0 {
+                partial_frames += (reader.partial_frame_bytes() != 0) as usize;
+            }
+            assert_ne!(0, partial_frames);
+            io::Result::Ok(reader)
+        };
+        let (writer, reader) = ::tokio::try_join!(write, read)?;
+
+        assert_eq!(17_640, writer.bytes_written());
+        assert_eq!(4_410, writer.whole_frames());
+        assert_eq!(Duration::from_millis(100), writer.elapsed());
+
+        assert_eq!(17_640, reader.bytes_read());
+        assert_eq!(4_410, reader.whole_frames());
+        assert_eq!(0, reader.partial_frame_bytes());
+        assert_eq!(Duration::from_millis(100), reader.elapsed());
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_limit() -> io::Result<()> {
+        let (tx, mut rx) = duplex(1_024);
+        // 1 ms is 44.1 frames, cut off at 44
+        let mut writer =
+            AsyncCountingWriter::<_, AUDIO_CD>::new(tx).limit(Duration::from_millis(1));
+
+        writer.write_all(&[1; 101]).await?;
+        assert_eq!(75, writer.write(&[2; 101]).await?);
+        assert_eq!(0, writer.write(&[3; 1]).await?);
+        assert_eq!(
+            io::ErrorKind::WriteZero,
+            writer.write_all(&[3; 1]).await.unwrap_err().kind()
+        );
+        assert_eq!(44, writer.whole_frames());
+        drop(writer);
+
+        let mut received = Vec::new();
+        rx.read_to_end(&mut received).await?;
+        assert_eq!(176, received.len());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{Endianness, SampleKind, System};
+
+/// Error returned when a [`System`] can't be described to an external tool.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Sample type is not supported by {tool}")]
+pub struct UnsupportedError {
+    /// The name of the tool, e.g. `"ffmpeg"`.
+    pub tool: &'static str,
+}
+
+impl System {
+    /// Arguments describing raw PCM audio in this system to ffmpeg, e.g. `-f
+    /// s16le -ar 44100 -ac 2`.
+    ///
+    /// The format's byte order follows the sample type's
+    /// [endianness](crate::SampleType::endianness), with native samples
+    /// described in the target's byte order. Fails for sample types that ffmpeg
+    /// has no raw format for (e.g. 64-bit integers).
+    ///
+    /// ```
+    /// # use audio_time::AUDIO_CD;
+    /// #
+    /// assert_eq!(
+    ///     ["-f", "s16le", "-ar", "44100", "-ac", "2"],
+    ///     &*AUDIO_CD.to_ffmpeg_args().unwrap()
+    /// );
+    /// ```
+    pub fn to_ffmpeg_args(&self) -> Result<Vec<String>, UnsupportedError> {
+        let format = match (self.sample_type.kind(), self.sample_type.byte_depth().get()) {
+            (Some(SampleKind::UnsignedInt), 1) => "u8",
+            (Some(SampleKind::SignedInt), 1) => "s8",
+            (Some(SampleKind::UnsignedInt), 2) => "u16",
+            (Some(SampleKind::SignedInt), 2) => "s16",
+            (Some(SampleKind::UnsignedInt), 3) => "u24",
+            (Some(SampleKind::SignedInt), 3) => "s24",
+            (Some(SampleKind::UnsignedInt), 4) => "u32",
+            (Some(SampleKind::SignedInt), 4) => "s32",
+            (Some(SampleKind::Float), 4) => "f32",
+            (Some(SampleKind::Float), 8) => "f64",
+            _ => return Err(UnsupportedError { tool: "ffmpeg" }),
+        };
+        let endianness = match self.sample_type.byte_depth().get() {
+            1 => "",
+            _ if self.sample_type.is_big_endian() => "be",
+            _ => "le",
+        };
+
+        Ok(vec![
+            "-f".to_owned(),
+            format!("{format}{endianness}"),
+            "-ar".to_owned(),
+            self.sample_rate.to_string(),
+            "-ac".to_owned(),
+            self.channel_layout.channels().to_string(),
+        ])
+    }
+
+    /// Arguments describing raw PCM audio in this system to SoX, e.g. `-t raw
+    /// -e signed -b 16 -r 44100 -c 2`.
+    ///
+    /// Sample types with an explicit
+    /// [endianness](crate::SampleType::endianness) are described using `-L` or
+    /// `-B`, while native ones are left to SoX's default. Fails for sample
+    /// types that SoX doesn't support (e.g. integers wider than 32 bits).
+    pub fn to_sox_args(&self) -> Result<Vec<String>, UnsupportedError> {
+        let encoding = match (self.sample_type.kind(), self.sample_type.byte_depth().get()) {
+            (Some(SampleKind::UnsignedInt), 1..=4) => "unsigned",
+            (Some(SampleKind::SignedInt), 1..=4) => "signed",
+            (Some(SampleKind::Float), 4 | 8) => "float",
+            _ => return Err(UnsupportedError { tool: "sox" }),
+        };
+
+        let mut args = vec![
+            "-t".to_owned(),
+            "raw".to_owned(),
+            "-e".to_owned(),
+            encoding.to_owned(),
+            "-b".to_owned(),
+            (self.sample_type.byte_depth().get() as u16 * 8).to_string(),
+        ];
+        match self.sample_type.endianness() {
+            Endianness::Native => {}
+            Endianness::Little => args.push("-L".to_owned()),
+            Endianness::Big => args.push("-B".to_owned()),
+        }
+        args.extend([
+            "-r".to_owned(),
+            self.sample_rate.to_string(),
+            "-c".to_owned(),
+            self.channel_layout.channels().to_string(),
+        ]);
+
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, Frames, SampleType, AUDIO_CD};
+
+    #[test]
+    fn test_ffmpeg_args() {
+        assert_eq!(
+            ["-f", "s16le", "-ar", "44100", "-ac", "2"],
+            &*AUDIO_CD.to_ffmpeg_args().unwrap()
+        );
+        assert_eq!(
+            ["-f", "f32le", "-ar", "48000", "-ac", "1"],
+            &*system!(48_000, Mono, f32).to_ffmpeg_args().unwrap()
+        );
+        assert_eq!(
+            Err(UnsupportedError { tool: "ffmpeg" }),
+            system!(48_000, Mono, u128).to_ffmpeg_args()
+        );
+
+        const CD_BE: System = AUDIO_CD.with_sample_type(SampleType::new_be::<i16>());
+        const CD_LE: System = AUDIO_CD.with_sample_type(SampleType::new_le::<i16>());
+        assert_eq!("s16be", CD_BE.to_ffmpeg_args().unwrap()[1]);
+        assert_eq!("s16le", CD_LE.to_ffmpeg_args().unwrap()[1]);
+        assert_eq!(
+            "u8",
+            system!(8_000, Mono, u8)
+                .with_sample_type(SampleType::new_be::<u8>())
+                .to_ffmpeg_args()
+                .unwrap()[1]
+        );
+
+        // byte order doesn't affect byte math, but the systems are not
+        // interchangeable
+        assert_ne!(CD_BE, CD_LE);
+        assert_ne!(AUDIO_CD, CD_LE);
+        assert_eq!(CD_BE.frame_size(), CD_LE.frame_size());
+        assert_eq!(
+            Frames::<CD_BE>::new(1_000).into_bytes().get(),
+            Frames::<CD_LE>::new(1_000).into_bytes().get()
+        );
+    }
+
+    #[test]
+    fn test_sox_args() {
+        assert_eq!(
+            ["-t", "raw", "-e", "signed", "-b", "16", "-r", "44100", "-c", "2"],
+            &*AUDIO_CD.to_sox_args().unwrap()
+        );
+        assert_eq!(
+            ["-t", "raw", "-e", "float", "-b", "32", "-r", "48000", "-c", "1"],
+            &*system!(48_000, Mono, f32).to_sox_args().unwrap()
+        );
+        assert_eq!(
+            Err(UnsupportedError { tool: "sox" }),
+            system!(48_000, Mono, u128).to_sox_args()
+        );
+        assert!(system!(48_000, Mono, i64).to_sox_args().is_err());
+        assert_eq!(
+            ["-t", "raw", "-e", "signed", "-b", "24", "-r", "96000", "-c", "2"],
+            &*system!(96_000, Stereo, crate::I24).to_sox_args().unwrap()
+        );
+        assert_eq!(
+            ["-t", "raw", "-e", "signed", "-b", "16", "-B", "-r", "44100", "-c", "2"],
+            &*AUDIO_CD
+                .with_sample_type(SampleType::new_be::<i16>())
+                .to_sox_args()
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,531 @@
+//! Conversions between [`DynSystem`]s and the `WAVEFORMATEX` and
+//! `WAVEFORMATEXTENSIBLE` headers that WASAPI clients negotiate formats with.
+//!
+//! [`WaveFormatEx`] and [`WaveFormatExtensible`] mirror the C structs' packed
+//! layouts, so no Windows bindings are needed. Only PCM and IEEE float formats
+//! are supported.
+
+use crate::{
+    stream_format::{channel_layout_of, sample_rate_of},
+    ChannelLayout, DynSampleType, DynSystem, Endianness, SampleKind, SampleType, StreamParamsError,
+    UnknownMaskError,
+};
+
+pub const WAVE_FORMAT_PCM: u16 = 0x1;
+pub const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x3;
+pub const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// The size of the fields following [`WaveFormatEx`] in a
+/// [`WaveFormatExtensible`], as stored in its `cb_size`.
+pub const EXTENSIBLE_CB_SIZE: u16 = 22;
+
+/// A Windows `GUID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Guid {
+    pub data1: u32,
+    pub data2: u16,
+    pub data3: u16,
+    pub data4: [u8; 8],
+}
+
+impl Guid {
+    /// The GUID of a `WAVE_FORMAT_*` tag in the `KSDATAFORMAT_SUBTYPE_*`
+    /// family, e.g. `00000001-0000-0010-8000-00aa00389b71` for PCM.
+    #[inline]
+    pub const fn from_format_tag(tag: u16) -> Self {
+        Self {
+            data1: tag as u32,
+            data2: 0x0000,
+            data3: 0x0010,
+            data4: [0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71],
+        }
+    }
+
+    /// The `WAVE_FORMAT_*` tag of a `KSDATAFORMAT_SUBTYPE_*` GUID, if it is
+    /// one.
+    #[inline]
+    pub const fn to_format_tag(&self) -> Option<u16> {
+        if self.data1 > u16::MAX as u32 {
+            return None;
+        }
+        let base = Self::from_format_tag(self.data1 as u16);
+        if self.data2 == base.data2
+            && self.data3 == base.data3
+            && u64::from_ne_bytes(self.data4) == u64::from_ne_bytes(base.data4)
+        {
+            Some(self.data1 as u16)
+        } else {
+            None
+        }
+    }
+}
+
+pub const KSDATAFORMAT_SUBTYPE_PCM: Guid = Guid::from_format_tag(WAVE_FORMAT_PCM);
+pub const KSDATAFORMAT_SUBTYPE_IEEE_FLOAT: Guid = Guid::from_format_tag(WAVE_FORMAT_IEEE_FLOAT);
+
+/// A `WAVEFORMATEX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C, packed(1))]
+pub struct WaveFormatEx {
+    /// One of the `WAVE_FORMAT_*` tags.
+    pub format_tag: u16,
+    pub channels: u16,
+    pub samples_per_sec: u32,
+    pub avg_bytes_per_sec: u32,
+    pub block_align: u16,
+    pub bits_per_sample: u16,
+    pub cb_size: u16,
+}
+
+/// A `WAVEFORMATEXTENSIBLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C, packed(1))]
+pub struct WaveFormatExtensible {
+    pub format: WaveFormatEx,
+    /// The number of meaningful bits in each sample, or 0 if they all are.
+    pub valid_bits_per_sample: u16,
+    pub channel_mask: u32,
+    pub sub_format: Guid,
+}
+
+/// Error returned when a wave format header can't be described by a
+/// [`DynSystem`].
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum WaveFormatError {
+    #[error("Unsupported format tag {0:#06x}")]
+    UnsupportedFormatTag(u16),
+    #[error("Unsupported sub-format {0:x?}")]
+    UnsupportedSubFormat(Guid),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+    #[error(
+        "Unsupported sample format of {valid_bits_per_sample} valid bits in \
+         {bits_per_sample}-bit containers"
+    )]
+    UnsupportedSampleFormat {
+        bits_per_sample: u16,
+        valid_bits_per_sample: u16,
+    },
+    #[error(
+        "nBlockAlign is {block_align}, but {channels} channels of {bits_per_sample}-bit samples \
+         take {expected} bytes"
+    )]
+    BlockAlignMismatch {
+        block_align: u16,
+        channels: u16,
+        bits_per_sample: u16,
+        expected: u32,
+    },
+    #[error(
+        "nAvgBytesPerSec is {avg_bytes_per_sec}, but the format takes {expected} bytes per second"
+    )]
+    AvgBytesPerSecMismatch {
+        avg_bytes_per_sec: u32,
+        expected: u64,
+    },
+    #[error(transparent)]
+    ChannelMask(#[from] UnknownMaskError),
+}
+
+/// Error returned when a [`DynSystem`] can't be described by a wave format
+/// header.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedSystemError {
+    #[error("Sample type `{0}` has no PCM or IEEE float wave format")]
+    SampleType(SampleType),
+    #[error("Byte rate does not fit in nAvgBytesPerSec")]
+    ByteRateOverflow,
+}
+
+impl WaveFormatEx {
+    /// The system of a stream with a plain (`WAVE_FORMAT_PCM` or
+    /// `WAVE_FORMAT_IEEE_FLOAT`) header.
+    ///
+    /// `WAVE_FORMAT_EXTENSIBLE` headers must be converted through
+    /// [`WaveFormatExtensible::to_system`] instead.
+    pub const fn to_system(&self) -> Result<DynSystem, WaveFormatError> {
+        match self.format_tag {
+            WAVE_FORMAT_PCM | WAVE_FORMAT_IEEE_FLOAT => {
+                system_of(self, self.format_tag, self.bits_per_sample, 0)
+            }
+            tag => Err(WaveFormatError::UnsupportedFormatTag(tag)),
+        }
+    }
+}
+
+impl WaveFormatExtensible {
+    /// The system of a stream with a `WAVE_FORMAT_EXTENSIBLE` header.
+    ///
+    /// `nBlockAlign` and `nAvgBytesPerSec` must agree with the number of
+    /// channels, container size and sample rate, and the layout is derived
+    /// from the channel mask (see [`ChannelLayout::from_wave_mask`]).
+    ///
+    /// `wValidBitsPerSample` becomes the sample type's
+    /// [bit depth](crate::SampleType::bit_depth), e.g. 24 valid bits in 32-bit
+    /// containers are read as `i32`s narrowed to 24 bits. The valid bits of a
+    /// wave format are the most significant ones of each container, with the
+    /// low bits as padding.
+    pub const fn to_system(&self) -> Result<DynSystem, WaveFormatError> {
+        let format = self.format;
+        if format.format_tag != WAVE_FORMAT_EXTENSIBLE {
+            return Err(WaveFormatError::UnsupportedFormatTag(format.format_tag));
+        }
+
+        let sub_format = self.sub_format;
+        let tag = match sub_format.to_format_tag() {
+            Some(tag @ (WAVE_FORMAT_PCM | WAVE_FORMAT_IEEE_FLOAT)) => tag,
+            _ => return Err(WaveFormatError::UnsupportedSubFormat(sub_format)),
+        };
+        let valid_bits = match self.valid_bits_per_sample {
+            0 => format.bits_per_sample,
+            bits => bits,
+        };
+
+        system_of(&format, tag, valid_bits, self.channel_mask)
+    }
+
+    /// The `WAVE_FORMAT_EXTENSIBLE` header of a stream in `system`, with
+    /// `nBlockAlign` and `nAvgBytesPerSec` computed from its frame size and
+    /// byte rate.
+    ///
+    /// Wave formats are always little-endian, so native-endian sample types
+    /// are only supported on little-endian targets. The sample type's bit depth
+    /// becomes `wValidBitsPerSample`, so narrowed samples are described as
+    /// occupying the high bits of their containers. Layouts without a channel
+    /// mask get a mask of 0.
+    ///
+    /// ```
+    /// # use audio_time::{wasapi::WaveFormatExtensible, system, DynSystem};
+    /// #
+    /// let system = DynSystem::from_system(system!(48_000, Stereo, f32));
+    /// let header = WaveFormatExtensible::from_system(&system).unwrap();
+    /// assert_eq!(8, { header.format.block_align });
+    /// assert_eq!(384_000, { header.format.avg_bytes_per_sec });
+    /// ```
+    pub const fn from_system(system: &DynSystem) -> Result<Self, UnsupportedSystemError> {
+        let sample_type = system.sample_type.with_resolved_endianness();
+        let byte_depth = sample_type.byte_depth().get();
+        let supported = match sample_type.kind() {
+            Some(SampleKind::Float) => byte_depth == 4 || byte_depth == 8,
+            Some(SampleKind::UnsignedInt) => byte_depth == 1,
+            Some(SampleKind::SignedInt) => byte_depth > 1,
+            None => false,
+        };
+        if !supported || matches!(sample_type.endianness(), Endianness::Big) {
+            return Err(UnsupportedSystemError::SampleType(system.sample_type));
+        }
+        let sub_format = if sample_type.is_float() {
+            KSDATAFORMAT_SUBTYPE_IEEE_FLOAT
+        } else {
+            KSDATAFORMAT_SUBTYPE_PCM
+        };
+
+        let sys = system.to_system();
+        let bytes_per_second = sys.bytes_per_second();
+        if bytes_per_second > u32::MAX as u64 {
+            return Err(UnsupportedSystemError::ByteRateOverflow);
+        }
+
+        Ok(Self {
+            format: WaveFormatEx {
+                format_tag: WAVE_FORMAT_EXTENSIBLE,
+                channels: system.channel_layout.channels().get() as u16,
+                samples_per_sec: system.sample_rate.get().get(),
+                avg_bytes_per_sec: bytes_per_second as u32,
+                block_align: sys.frame_size().get(),
+                bits_per_sample: byte_depth as u16 * 8,
+                cb_size: EXTENSIBLE_CB_SIZE,
+            },
+            valid_bits_per_sample: sample_type.bit_depth().get() as u16,
+            channel_mask: match system.channel_layout.to_wave_mask() {
+                Some(mask) => mask,
+                None => 0,
+            },
+            sub_format,
+        })
+    }
+}
+
+/// The system described by the common fields of a header, whose samples are
+/// of the format with the given tag.
+const fn system_of(
+    format: &WaveFormatEx,
+    tag: u16,
+    valid_bits: u16,
+    channel_mask: u32,
+) -> Result<DynSystem, WaveFormatError> {
+    let sample_rate = match sample_rate_of(format.samples_per_sec) {
+        Ok(rate) => rate,
+        Err(err) => return Err(WaveFormatError::Params(err)),
+    };
+    let channels = format.channels;
+    // the layout itself comes from the channel mask
+    let channels_u8 = match channel_layout_of(channels as u32) {
+        Ok(layout) => layout.channels(),
+        Err(err) => return Err(WaveFormatError::Params(err)),
+    };
+
+    let bits = format.bits_per_sample;
+    let byte_depth = bits / 8;
+    let kind = match tag {
+        WAVE_FORMAT_IEEE_FLOAT => SampleKind::Float,
+        _ if byte_depth == 1 => SampleKind::UnsignedInt,
+        _ => SampleKind::SignedInt,
+    };
+    let sample_type = if bits % 8 == 0 && byte_depth <= 8 && valid_bits <= bits {
+        match DynSampleType::new(kind, byte_depth as u8, valid_bits as u8) {
+            Some(sample_type) if byte_depth > 1 => sample_type
+                .with_endianness(Endianness::Little)
+                .to_sample_type(),
+            Some(sample_type) => sample_type.to_sample_type(),
+            None => None,
+        }
+    } else {
+        None
+    };
+    let Some(sample_type) = sample_type else {
+        return Err(WaveFormatError::UnsupportedSampleFormat {
+            bits_per_sample: bits,
+            valid_bits_per_sample: valid_bits,
+        });
+    };
+
+    let expected_block_align = channels as u32 * byte_depth as u32;
+    if format.block_align as u32 != expected_block_align {
+        return Err(WaveFormatError::BlockAlignMismatch {
+            block_align: format.block_align,
+            channels,
+            bits_per_sample: bits,
+            expected: expected_block_align,
+        });
+    }
+    let expected_bytes_per_sec = expected_block_align as u64 * sample_rate.get().get() as u64;
+    if format.avg_bytes_per_sec as u64 != expected_bytes_per_sec {
+        return Err(WaveFormatError::AvgBytesPerSecMismatch {
+            avg_bytes_per_sec: format.avg_bytes_per_sec,
+            expected: expected_bytes_per_sec,
+        });
+    }
+
+    let channel_layout = match ChannelLayout::from_wave_mask(channel_mask, channels_u8) {
+        Ok(layout) => layout,
+        Err(err) => return Err(WaveFormatError::ChannelMask(err)),
+    };
+
+    Ok(DynSystem {
+        sample_rate,
+        channel_layout,
+        sample_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{presets, system, I24};
+
+    const S24_IN_32: WaveFormatExtensible = WaveFormatExtensible {
+        format: WaveFormatEx {
+            format_tag: WAVE_FORMAT_EXTENSIBLE,
+            channels: 2,
+            samples_per_sec: 48_000,
+            avg_bytes_per_sec: 384_000,
+            block_align: 8,
+            bits_per_sample: 32,
+            cb_size: EXTENSIBLE_CB_SIZE,
+        },
+        valid_bits_per_sample: 24,
+        channel_mask: 0x3,
+        sub_format: KSDATAFORMAT_SUBTYPE_PCM,
+    };
+
+    #[test]
+    fn test_guids() {
+        assert_eq!(
+            Guid {
+                data1: 0x0000_0003,
+                data2: 0x0000,
+                data3: 0x0010,
+                data4: [0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71],
+            },
+            KSDATAFORMAT_SUBTYPE_IEEE_FLOAT
+        );
+        assert_eq!(
+            Some(WAVE_FORMAT_PCM),
+            KSDATAFORMAT_SUBTYPE_PCM.to_format_tag()
+        );
+
+        let mut guid = KSDATAFORMAT_SUBTYPE_PCM;
+        guid.data4[7] = 0;
+        assert_eq!(None, guid.to_format_tag());
+
+        assert_eq!(18, size_of::<WaveFormatEx>());
+        assert_eq!(40, size_of::<WaveFormatExtensible>());
+    }
+
+    #[test]
+    fn test_24_in_32() {
+        let system = S24_IN_32.to_system().unwrap();
+        assert_eq!(ChannelLayout::Stereo, system.channel_layout);
+        assert_eq!(4, system.sample_type.byte_depth().get());
+        assert_eq!(24, system.sample_type.bit_depth().get());
+        assert!(system.sample_type.is_signed());
+        assert_eq!(Ok(S24_IN_32), WaveFormatExtensible::from_system(&system));
+
+        for preset in [presets::DVD_AUDIO_96K_24, presets::HIRES_192K] {
+            let system = DynSystem::from_system(preset);
+            let header = WaveFormatExtensible::from_system(&system).unwrap();
+            assert_eq!(32, { header.format.bits_per_sample });
+            assert_eq!(24, { header.valid_bits_per_sample });
+            assert_eq!(
+                preset.sample_type.bit_depth(),
+                header.to_system().unwrap().sample_type.bit_depth()
+            );
+        }
+
+        // packed 24-bit samples are a different format
+        let system = DynSystem::from_system(system!(48_000, Stereo, I24));
+        let header = WaveFormatExtensible::from_system(&system).unwrap();
+        assert_eq!(6, { header.format.block_align });
+        assert_eq!(288_000, { header.format.avg_bytes_per_sec });
+        assert_eq!(24, { header.valid_bits_per_sample });
+    }
+
+    #[test]
+    fn test_float() {
+        let header = WaveFormatExtensible {
+            format: WaveFormatEx {
+                bits_per_sample: 32,
+                ..S24_IN_32.format
+            },
+            valid_bits_per_sample: 32,
+            sub_format: KSDATAFORMAT_SUBTYPE_IEEE_FLOAT,
+            ..S24_IN_32
+        };
+        let system = header.to_system().unwrap();
+        assert_eq!(SampleType::new_le::<f32>(), system.sample_type);
+        assert_eq!(Ok(header), WaveFormatExtensible::from_system(&system));
+
+        // wValidBitsPerSample of 0 means all of them
+        let unspecified = WaveFormatExtensible {
+            valid_bits_per_sample: 0,
+            ..header
+        };
+        assert_eq!(Ok(system), unspecified.to_system());
+
+        let plain = WaveFormatEx {
+            format_tag: WAVE_FORMAT_IEEE_FLOAT,
+            cb_size: 0,
+            ..header.format
+        };
+        assert_eq!(Ok(system), plain.to_system());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for sys in [
+            system!(44_100, Stereo, i16),
+            system!(8_000, Mono, u8),
+            system!(96_000, Surround51, f64),
+            system!(48_000, Surround71, i32),
+        ] {
+            let system = DynSystem::from_system(sys);
+            let header = WaveFormatExtensible::from_system(&system).unwrap();
+            let expected = DynSystem {
+                sample_type: system.sample_type.with_resolved_endianness(),
+                ..system
+            };
+            assert_eq!(Ok(expected), header.to_system(), "{sys:?}");
+            assert_eq!(sys.frame_size().get(), { header.format.block_align });
+            assert_eq!(sys.bytes_per_second(), { header.format.avg_bytes_per_sec }
+                as u64);
+        }
+    }
+
+    #[test]
+    fn test_inconsistent() {
+        let header = WaveFormatExtensible {
+            format: WaveFormatEx {
+                block_align: 6,
+                ..S24_IN_32.format
+            },
+            ..S24_IN_32
+        };
+        assert_eq!(
+            Err(WaveFormatError::BlockAlignMismatch {
+                block_align: 6,
+                channels: 2,
+                bits_per_sample: 32,
+                expected: 8,
+            }),
+            header.to_system()
+        );
+
+        let header = WaveFormatExtensible {
+            format: WaveFormatEx {
+                avg_bytes_per_sec: 192_000,
+                ..S24_IN_32.format
+            },
+            ..S24_IN_32
+        };
+        assert_eq!(
+            Err(WaveFormatError::AvgBytesPerSecMismatch {
+                avg_bytes_per_sec: 192_000,
+                expected: 384_000,
+            }),
+            header.to_system()
+        );
+
+        let header = WaveFormatExtensible {
+            channel_mask: 0x3f,
+            ..S24_IN_32
+        };
+        assert!(matches!(
+            header.to_system(),
+            Err(WaveFormatError::ChannelMask(_))
+        ));
+
+        let header = WaveFormatExtensible {
+            valid_bits_per_sample: 33,
+            ..S24_IN_32
+        };
+        assert!(matches!(
+            header.to_system(),
+            Err(WaveFormatError::UnsupportedSampleFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let header = WaveFormatExtensible {
+            sub_format: Guid::from_format_tag(0x7), // mu-law
+            ..S24_IN_32
+        };
+        assert_eq!(
+            Err(WaveFormatError::UnsupportedSubFormat(
+                Guid::from_format_tag(0x7)
+            )),
+            header.to_system()
+        );
+        assert_eq!(
+            Err(WaveFormatError::UnsupportedFormatTag(
+                WAVE_FORMAT_EXTENSIBLE
+            )),
+            S24_IN_32.format.to_system()
+        );
+
+        for sys in [system!(48_000, Stereo, u16), system!(48_000, Stereo, i8)] {
+            let system = DynSystem::from_system(sys);
+            assert_eq!(
+                Err(UnsupportedSystemError::SampleType(system.sample_type)),
+                WaveFormatExtensible::from_system(&system)
+            );
+        }
+        let system = DynSystem {
+            sample_type: SampleType::new_be::<i16>(),
+            ..DynSystem::from_system(system!(48_000, Stereo, i16))
+        };
+        assert!(WaveFormatExtensible::from_system(&system).is_err());
+    }
+}
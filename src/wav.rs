@@ -0,0 +1,143 @@
+//! Helpers for measuring and seeking within the `data` chunk of WAV files.
+//!
+//! All math is done in `u64`/`u128`, since `data` chunks may be larger than a
+//! `usize` on 32-bit hosts.
+
+use core::time::Duration;
+
+use crate::{DynSystem, OverflowError, Rounding};
+
+/// Error returned when a `data` chunk doesn't contain a whole number of
+/// frames (e.g. for a truncated file).
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Data chunk is not a whole number of frames ({remainder} trailing bytes)")]
+pub struct PartialFrameError {
+    duration: Duration,
+    remainder: u64,
+}
+
+impl PartialFrameError {
+    /// The duration of the whole frames in the chunk.
+    #[inline]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The number of trailing bytes that do not make up a whole frame.
+    #[inline]
+    pub const fn remainder(&self) -> u64 {
+        self.remainder
+    }
+}
+
+/// The duration of a `data` chunk of `data_len` bytes, truncated to whole
+/// nanoseconds.
+///
+/// Fails if the chunk ends with a partial frame, in which case the error
+/// still carries the (floored) [duration](PartialFrameError::duration) of the
+/// whole frames before it.
+#[inline]
+pub const fn duration_of_data_chunk(
+    sys: &DynSystem,
+    data_len: u64,
+) -> Result<Duration, PartialFrameError> {
+    let remainder = data_len % sys.frame_size() as u64;
+    let duration = sys.to_system().duration_for_bytes(data_len);
+
+    if remainder == 0 {
+        Ok(duration)
+    } else {
+        Err(PartialFrameError {
+            duration,
+            remainder,
+        })
+    }
+}
+
+/// The absolute file offset to seek to for playback to start at `target`,
+/// where the `data` chunk's samples start at offset `data_offset`.
+///
+/// `target` is rounded to a whole frame in the direction of `rounding`, so
+/// the returned offset is always frame-aligned relative to `data_offset`
+/// (which itself needn't be aligned to anything).
+///
+/// Returns an error if the offset does not fit in a `u64`.
+#[inline]
+pub const fn seek_offset(
+    sys: &DynSystem,
+    data_offset: u64,
+    target: Duration,
+    rounding: Rounding,
+) -> Result<u64, OverflowError> {
+    let frames = sys.sample_rate.periods_in_rounded(target, rounding);
+
+    match frames.checked_mul(sys.frame_size() as u128) {
+        Some(bytes) if bytes + (data_offset as u128) <= u64::MAX as u128 => {
+            Ok(data_offset + bytes as u64)
+        }
+        _ => Err(OverflowError(())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system, I24};
+
+    /// Packed 24-bit stereo: 6-byte frames, which don't divide the usual
+    /// 44-byte header (or any power of two).
+    const SYS: DynSystem = DynSystem::from_system(system!(48_000, Stereo, I24));
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(
+            Ok(Duration::from_secs(1)),
+            duration_of_data_chunk(&SYS, 288_000)
+        );
+        assert_eq!(Ok(Duration::ZERO), duration_of_data_chunk(&SYS, 0));
+
+        let err = duration_of_data_chunk(&SYS, 288_004).unwrap_err();
+        assert_eq!(Duration::from_secs(1), err.duration());
+        assert_eq!(4, err.remainder());
+
+        // larger than a 32-bit `usize`: ~6 hours
+        assert_eq!(
+            Ok(Duration::from_secs(6 * 3_600)),
+            duration_of_data_chunk(&SYS, 6 * 3_600 * 288_000)
+        );
+        assert!(duration_of_data_chunk(&SYS, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_seek_offset() -> Result<(), OverflowError> {
+        const DATA_OFFSET: u64 = 44;
+
+        assert_eq!(
+            DATA_OFFSET + 288_000,
+            seek_offset(&SYS, DATA_OFFSET, Duration::from_secs(1), Rounding::Down)?
+        );
+
+        // 1.5 frames
+        let target = Duration::from_nanos(31_250);
+        for (rounding, frames) in [
+            (Rounding::Down, 1),
+            (Rounding::Up, 2),
+            (Rounding::Nearest, 2),
+        ] {
+            assert_eq!(
+                DATA_OFFSET + frames * 6,
+                seek_offset(&SYS, DATA_OFFSET, target, rounding)?
+            );
+        }
+
+        for nanos in (0..1_000_000).step_by(7_919) {
+            let offset = seek_offset(&SYS, 46, Duration::from_nanos(nanos), Rounding::Nearest)?;
+            assert_eq!(0, (offset - 46) % 6, "{nanos}");
+        }
+
+        assert!(seek_offset(&SYS, u64::MAX, Duration::from_secs(1), Rounding::Down).is_err());
+        assert!(seek_offset(&SYS, 0, Duration::MAX, Rounding::Down).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Interop with the Web Audio API, whose `AudioContext` reports its sample
+//! rate as an `f32`, and whose `AudioBuffer`s hold `f32` samples.
+
+use core::time::Duration;
+
+use crate::{
+    stream_format::channel_layout_of, DynSystem, NonIntegralRateError, SampleRate, SampleType,
+    StreamParamsError,
+};
+
+/// The range of sample rates that Web Audio implementations must support, in
+/// hertz.
+const MIN_RATE_HZ: u32 = 3_000;
+const MAX_RATE_HZ: u32 = 768_000;
+
+/// Error returned from [`DynSystem::for_web_audio`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+pub enum WebAudioError {
+    #[error(transparent)]
+    NonIntegralRate(#[from] NonIntegralRateError),
+    #[error(
+        "Sample rate of {0} Hz is outside of the 3 kHz to 768 kHz range supported by Web Audio"
+    )]
+    UnsupportedRate(SampleRate),
+    #[error(transparent)]
+    Params(#[from] StreamParamsError),
+}
+
+impl DynSystem {
+    /// The system of a Web Audio graph with the given `sampleRate` (e.g. of an
+    /// `AudioContext`) and `numberOfChannels`, with `f32` samples.
+    ///
+    /// The rate is snapped to a whole number of hertz within
+    /// [`SampleRate::DEFAULT_HZ_TOLERANCE`], and must then be within the range
+    /// of 3 kHz to 768 kHz that the Web Audio specification requires
+    /// implementations to support.
+    ///
+    /// ```
+    /// # use audio_time::{system, DynSystem};
+    /// #
+    /// let system = DynSystem::for_web_audio(47_999.998, 2).unwrap();
+    /// assert!(system.is::<{ system!(48_000, Stereo, f32) }>());
+    /// ```
+    pub const fn for_web_audio(sample_rate: f32, channels: u32) -> Result<Self, WebAudioError> {
+        let sample_rate =
+            match SampleRate::from_hz_f32(sample_rate, SampleRate::DEFAULT_HZ_TOLERANCE) {
+                Ok(rate) if matches!(rate.get().get(), MIN_RATE_HZ..=MAX_RATE_HZ) => rate,
+                Ok(rate) => return Err(WebAudioError::UnsupportedRate(rate)),
+                Err(err) => return Err(WebAudioError::NonIntegralRate(err)),
+            };
+
+        let channel_layout = match channel_layout_of(channels) {
+            Ok(layout) => layout,
+            Err(err) => return Err(WebAudioError::Params(err)),
+        };
+
+        Ok(Self {
+            sample_rate,
+            channel_layout,
+            sample_type: SampleType::new::<f32>(),
+        })
+    }
+}
+
+/// The duration of an `AudioBuffer` of `length` frames in `system`, truncated
+/// to whole nanoseconds.
+#[inline]
+pub const fn duration_of_web_audio_buffer(length: u32, system: DynSystem) -> Duration {
+    system.sample_rate.duration_of(length as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{presets::TELEPHONY_8K, system, ChannelLayout, Frames, System};
+
+    const SYS: System = system!(44_100, Stereo, f32);
+
+    #[test]
+    fn test_for_web_audio() {
+        let system = DynSystem::for_web_audio(44_100.000_02, 2).unwrap();
+        assert!(system.is::<SYS>());
+        assert_eq!(
+            ChannelLayout::Mono,
+            DynSystem::for_web_audio(44_100., 1).unwrap().channel_layout
+        );
+
+        for hz in [3_000, 22_254, 44_150, 768_000] {
+            let system = DynSystem::for_web_audio(hz as f32, 2).unwrap();
+            assert_eq!(hz, system.sample_rate.get().get());
+        }
+        for hz in [1, 2_999, 768_001, 1_000_000] {
+            assert_eq!(
+                Err(WebAudioError::UnsupportedRate(
+                    SampleRate::from_hz(hz).unwrap()
+                )),
+                DynSystem::for_web_audio(hz as f32, 2)
+            );
+        }
+        assert!(matches!(
+            DynSystem::for_web_audio(44_100.5, 2),
+            Err(WebAudioError::NonIntegralRate(_))
+        ));
+        for channels in [0, 256] {
+            assert_eq!(
+                Err(WebAudioError::Params(
+                    StreamParamsError::UnsupportedChannels(channels)
+                )),
+                DynSystem::for_web_audio(48_000., channels)
+            );
+        }
+
+        // the sample type is always f32, unlike the preset
+        assert!(!DynSystem::for_web_audio(8_000., 1)
+            .unwrap()
+            .is::<TELEPHONY_8K>());
+    }
+
+    #[test]
+    fn test_buffer_duration() {
+        let system = DynSystem::for_web_audio(44_100., 2).unwrap();
+
+        for length in [0, 1, 441, 44_100, 44_101, 1 << 24, u32::MAX] {
+            let nanos = Frames::<SYS>::new(length as usize).to_nanos_u64().unwrap();
+            assert_eq!(
+                Duration::from_nanos(nanos),
+                duration_of_web_audio_buffer(length, system),
+                "{length}"
+            );
+        }
+        assert_eq!(
+            Duration::from_millis(10),
+            duration_of_web_audio_buffer(441, system)
+        );
+    }
+}